@@ -2,6 +2,7 @@
 
 // Preload
 window.load_url = () => new URLSearchParams(window.location.search).get('code') || "";
+window.load_fragment = () => window.location.hash;
 window.loading_finished = () => document.getElementById("loading-text").remove();
 
 // IO
@@ -12,7 +13,7 @@ window.open_file = (ext, done, is_multiple, is_bin) => {
     input.multiple = is_multiple;
     input.onchange = () => [...input.files].forEach(f => {
         if (is_bin)
-            f.arrayBuffer().then(a => done(new Uint8Array(a)));
+            f.arrayBuffer().then(a => done(f.name, new Uint8Array(a)));
         else
             f.text().then(t => done(f.name, t));
     });
@@ -88,6 +88,7 @@ fn welcome(ui: &mut Ui, save_cfg: &mut bool) {
     ui.separator();
     let res = ui.collapsing("📚 Canvas Control Tips", |ui| {
         ui.label("Pan move: Left-drag / Drag");
+        ui.label("Move joint: Drag the pivots or the coupler point");
         ui.label("Zoom: Ctrl+Wheel / Pinch+Stretch");
         ui.label("Box Zoom: Right-drag");
         ui.label("Reset: Double-click");
@@ -125,6 +126,14 @@ impl Welcome {
     }
 }
 
+/// Session file of the whole app state.
+#[derive(Deserialize, Serialize)]
+struct Session<A> {
+    #[serde(default)]
+    version: four_bar::mech::Version,
+    app: A,
+}
+
 /// Main app state.
 #[derive(Default, Deserialize, Serialize)]
 #[serde(default)]
@@ -138,6 +147,8 @@ pub(crate) struct App {
     welcome: Welcome,
     #[serde(skip)]
     panel: Panel,
+    #[serde(skip)]
+    session: crate::io::Cache<App>,
 }
 
 impl App {
@@ -185,11 +196,15 @@ impl App {
             #[wasm_bindgen::prelude::wasm_bindgen]
             extern "C" {
                 fn load_url() -> String;
+                fn load_fragment() -> String;
                 fn loading_finished();
             }
             if let Ok(fb) = ron::from_str(&load_url()) {
                 app.link.projs.queue().push(None, fb);
             }
+            if let Ok(fb) = four_bar::FourBar::from_share_code(&load_fragment()) {
+                app.link.projs.queue().push(None, crate::io::Fb::P(fb));
+            }
             loading_finished();
         }
         Ok(Box::new(app))
@@ -221,15 +236,56 @@ impl App {
                 self.welcome.invert();
             }
             url_btn(ui, "", "Repository", repo!());
+            ui.menu_button("🗐", |ui| self.session_menu(ui))
+                .response
+                .on_hover_text("Session");
         });
     }
 
+    fn session_menu(&mut self, ui: &mut Ui) {
+        if ui.button("💾 Save Session").clicked() {
+            ui.close_menu();
+            // Keep the finished tasks which are not collected yet
+            self.syn.collect_tasks();
+            let session = Session { version: four_bar::mech::Version, app: &*self };
+            crate::io::save_ron_ask(&session, "session.ron", |_| ());
+        }
+        if ui.button("🖴 Load Session").clicked() {
+            ui.close_menu();
+            let cache = self.session.clone();
+            crate::io::open_ron_single(move |_, session: Session<Self>| {
+                *cache.borrow_mut() = Some(session.app);
+            });
+        }
+        ui.label("Projects, synthesis tasks and settings");
+    }
+
+    // Replace the state with the loaded session
+    fn restore(&mut self, ctx: &Context, app: Self) {
+        let Self { link, mut syn, bp, plotter, save_cfg, welcome, .. } = app;
+        // The atlas is never persisted, keep the loaded one
+        syn.keep_atlas(&mut self.syn);
+        *self = Self {
+            link,
+            syn,
+            bp,
+            plotter,
+            save_cfg,
+            welcome,
+            panel: std::mem::take(&mut self.panel),
+            session: self.session.clone(),
+        };
+        self.bp.preload(ctx);
+        self.link.preload(Vec::new());
+    }
+
     fn canvas(&mut self, ui: &mut Ui) {
         egui_plot::Plot::new("canvas")
             .data_aspect(1.)
             .auto_bounds([true; 2].into())
             .legend(Default::default())
             .coordinates_formatter(egui_plot::Corner::LeftBottom, Default::default())
+            .allow_drag(!self.link.projs.on_joint())
             .show(ui, |ui| {
                 self.bp.plot(ui);
                 self.link.plot(ui);
@@ -274,15 +330,50 @@ impl eframe::App for App {
         } else {
             self.pc_view(ctx);
         }
+        let session = self.session.borrow_mut().take();
+        if let Some(app) = session {
+            self.restore(ctx, app);
+        }
         self.link.poll(ctx);
         crate::io::show_err_msg(frame);
     }
 
     fn save(&mut self, storage: &mut dyn eframe::Storage) {
         if self.save_cfg {
+            // Keep the finished tasks which are not collected yet
+            self.syn.collect_tasks();
             eframe::set_value(storage, eframe::APP_KEY, self);
         } else {
             storage.set_string(eframe::APP_KEY, String::new());
         }
     }
 }
+
+#[test]
+fn session_round_trip() {
+    use four_bar::mech::{Version, FORMAT_VERSION};
+    let mut app = App {
+        save_cfg: true,
+        welcome: Welcome { open: false },
+        ..Default::default()
+    };
+    app.link
+        .projs
+        .push_fb(crate::io::Fb::P(four_bar::FourBar::example()));
+    app.link.poll(&Context::default());
+    app.syn = ron::from_str("(global_seed: Some(7), cfg: (gen: 20, pop: 30))").unwrap();
+    let s = ron::to_string(&Session { version: Version, app: &app }).unwrap();
+    let Session { app, .. } = ron::from_str::<Session<App>>(&s).unwrap();
+    assert!(app.save_cfg && !app.welcome.open);
+    assert!(app.link.projs.current_fb_state().is_some());
+    // Projects and synthesis settings are kept as well
+    let session = Session { version: Version, app: &app };
+    assert_eq!(ron::to_string(&session).unwrap(), s);
+    // Newer sessions are rejected
+    let newer = s.replacen(
+        &format!("version:{FORMAT_VERSION}"),
+        &format!("version:{}", FORMAT_VERSION + 1),
+        1,
+    );
+    assert!(ron::from_str::<Session<App>>(&newer).is_err());
+}
@@ -14,7 +14,20 @@ impl Linkages {
         self.projs.show(ui);
     }
 
-    pub(crate) fn plot(&self, ui: &mut egui_plot::PlotUi) {
+    pub(crate) fn plot(&mut self, ui: &mut egui_plot::PlotUi) {
+        if let Some(p) = ui.pointer_coordinate() {
+            let tol = ui.plot_bounds().width() * 0.01;
+            let res = ui.response();
+            let (start, dragged) = (res.drag_started(), res.dragged());
+            self.projs.drag([p.x, p.y], tol, start, dragged);
+        }
+        if ui.response().clicked() {
+            // Probe the branches of the current linkage
+            if let Some(p) = ui.pointer_coordinate() {
+                let tol = ui.plot_bounds().width() * 0.01;
+                self.projs.probe([p.x, p.y], tol);
+            }
+        }
         self.projs.plot(ui);
     }
 
@@ -66,9 +66,18 @@ fn fig_ui<M, const D: usize>(
         }
     });
     ui.collapsing("Curves", |ui| {
-        fig.lock()
-            .unwrap()
-            .retain_lines(|i, line| ui.group(|ui| fig_line_ui(ui, i, line)).inner);
+        {
+            let mut fig = fig.lock().unwrap();
+            let len = fig.lines().count();
+            let mut swap = None;
+            fig.retain_lines(|i, line| {
+                ui.group(|ui| fig_line_ui(ui, i, len, line, &mut swap))
+                    .inner
+            });
+            if let Some((i, j)) = swap {
+                fig.swap_lines(i, j);
+            }
+        }
         ui.horizontal(|ui| {
             if let Some(c) = lnk.projs.current_curve() {
                 if ui.button("🖴 Add from").clicked() {
@@ -101,24 +110,143 @@ fn fig_ui<M, const D: usize>(
     });
     ui.collapsing("Plot Option", |ui| {
         let mut fig = fig.lock().unwrap();
-        nonzero_i(ui, "Stroke size: ", &mut fig.stroke, 1);
+        ui.horizontal(|ui| {
+            nonzero_i(ui, "Stroke size: ", &mut fig.stroke, 1);
+            ui.checkbox(&mut fig.scale_stroke_with_size, "Scale with size")
+                .on_hover_text(format!(
+                    "Scale the stroke by the subplot size, the stroke is for {}px",
+                    plot::Opt::REF_SIZE
+                ));
+        });
         nonzero_i(ui, "Font size: ", &mut fig.font, 1);
+        ui.add(Slider::new(&mut fig.margin, 0.0..=1.0).text("Margin"))
+            .on_hover_text("Whitespace around the data, a fraction of the data range");
         check_on(ui, "Font Family", &mut fig.font_family, |ui, s| {
             ui.text_edit_singleline(s.to_mut())
-        });
+        })
+        .on_hover_text(format!(
+            "Default to {}, the name of an installed family (not a font file), \
+            fall back to the sans-serif font if the family is not installed",
+            plot::Opt::DEFAULT_FONT
+        ));
+        if fig.is_font_fallback() {
+            ui.colored_label(Color32::RED, "⚠ The font family is not installed, use sans-serif");
+        }
         ui.checkbox(&mut fig.grid, "Show grid");
         ui.checkbox(&mut fig.axis, "Show axis");
+        ui.horizontal(|ui| {
+            use plot::TickFormat::*;
+            ui.label("Tick format");
+            let n = match fig.tick_format {
+                Fixed(n) | Scientific(n) => n,
+                Auto => 4,
+            };
+            let list = [Fixed(n), Scientific(n), Auto];
+            combo_enum(ui, "tick_format", &mut fig.tick_format, list, |e| e.name());
+            if let Fixed(n) | Scientific(n) = &mut fig.tick_format {
+                ui.add(DragValue::new(n).range(0..=10).suffix(" places"));
+            }
+        });
         ui.horizontal(|ui| {
             use plot::LegendPos;
             ui.label("Legend");
             combo_enum(ui, "legend", &mut fig.legend, LegendPos::LIST, |e| e.name());
         });
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut fig.coupler_marker, "Coupler marker")
+                .on_hover_text("Highlight the coupler point of the linkage");
+            if fig.coupler_marker {
+                nonzero_i(ui, "size: ", &mut fig.coupler_marker_size, 1);
+                let color = &mut fig.coupler_marker_color;
+                let mut buf = [color.0, color.1, color.2];
+                ui.color_edit_button_srgb(&mut buf);
+                [color.0, color.1, color.2] = buf;
+            }
+        });
+        ui.checkbox(&mut fig.reachable_region, "Show reachable region")
+            .on_hover_text("Shade the reachable region of the coupler point over the both branches, planar linkages only");
+        ui.horizontal(|ui| {
+            use plot::Style;
+            ui.label("Ground marker");
+            combo_enum(
+                ui,
+                "ground_marker",
+                &mut fig.ground_marker,
+                Style::MARKER_LIST,
+                |e| e.name(),
+            );
+        });
+        ui.horizontal(|ui| {
+            use plot::Style;
+            ui.label("Joint marker");
+            combo_enum(
+                ui,
+                "joint_marker",
+                &mut fig.joint_marker,
+                Style::MARKER_LIST,
+                |e| e.name(),
+            );
+        });
+        ui.checkbox(&mut fig.coupler_body, "Show coupler body")
+            .on_hover_text("Fill the coupler link as a rigid triangle, planar linkages only");
+        ui.horizontal(|ui| {
+            let mut sweep = fig.sweep_alpha.is_some();
+            if ui
+                .checkbox(&mut sweep, "Show swept region")
+                .on_hover_text("Shade the region swept by the coupler link, planar linkages only")
+                .changed()
+            {
+                fig.sweep_alpha = sweep.then_some(0.05);
+            }
+            if let Some(alpha) = &mut fig.sweep_alpha {
+                ui.add(Slider::new(alpha, 0.01..=0.5).text("Opacity"));
+            }
+        });
+        ui.horizontal(|ui| {
+            ui.label("Ghost angles");
+            // Keep the text buffer, the incomplete list is not parsed
+            let id = ui.id().with("ghost_angles");
+            let mut text = ui.data_mut(|d| d.get_temp::<String>(id)).unwrap_or_else(|| {
+                let angles = fig.ghost_angles.iter().map(|t| t.to_degrees().to_string());
+                angles.collect::<Vec<_>>().join(", ")
+            });
+            let res = ui.text_edit_singleline(&mut text).on_hover_text(
+                "Draw the linkage faintly at the input angles in degrees, separated by the commas, planar linkages only",
+            );
+            if res.changed() {
+                fig.ghost_angles = (text.split(','))
+                    .filter_map(|s| s.trim().parse::<f64>().ok())
+                    .map(f64::to_radians)
+                    .collect();
+            }
+            ui.data_mut(|d| d.insert_temp(id, text));
+        });
+        ui.horizontal(|ui| {
+            let mut transparent = fig.background.is_none();
+            if ui.checkbox(&mut transparent, "Transparent").changed() {
+                fig.background = (!transparent).then_some(plot::RGBAColor(255, 255, 255, 1.));
+            }
+            if let Some(color) = &mut fig.background {
+                ui.label("Background");
+                let mut buf = [color.0, color.1, color.2];
+                ui.color_edit_button_srgb(&mut buf);
+                [color.0, color.1, color.2] = buf;
+            }
+        });
     });
 }
 
-fn fig_line_ui<const N: usize>(ui: &mut Ui, i: usize, line: &mut plot::LineData<[f64; N]>) -> bool {
+// The index `i` is started from 1, the swap request is in 0-based indices
+fn fig_line_ui<const N: usize>(
+    ui: &mut Ui,
+    i: usize,
+    len: usize,
+    line: &mut plot::LineData<[f64; N]>,
+    swap: &mut Option<(usize, usize)>,
+) -> bool {
     let keep = ui
         .horizontal(|ui| {
+            ui.checkbox(&mut line.visible, "").on_hover_text("Visible");
             match &mut line.line {
                 plot::LineType::Line(..) => _ = ui.label("[Line]"),
                 plot::LineType::Pose { is_frame, .. } => {
@@ -127,7 +255,16 @@ fn fig_line_ui<const N: usize>(ui: &mut Ui, i: usize, line: &mut plot::LineData<
                 }
             };
             ui.with_layout(Layout::right_to_left(Align::LEFT), |ui| {
-                !ui.button("✖").clicked()
+                let keep = !ui.button("✖").clicked();
+                let down = ui.add_enabled(i < len, Button::new("⏷"));
+                if down.on_hover_text("Draw later (on top)").clicked() {
+                    *swap = Some((i - 1, i));
+                }
+                let up = ui.add_enabled(i > 1, Button::new("⏶"));
+                if up.on_hover_text("Draw earlier (below)").clicked() {
+                    *swap = Some((i - 2, i - 1));
+                }
+                keep
             })
             .inner
         })
@@ -150,6 +287,25 @@ fn fig_line_ui<const N: usize>(ui: &mut Ui, i: usize, line: &mut plot::LineData<
         any_i(ui, &mut color.2);
         ui.checkbox(&mut line.color.filled, "Filled");
     });
+    ui.push_id(i, |ui| {
+        ui.collapsing("Annotations", |ui| {
+            let len = line.line.curve().len();
+            line.annotations.retain_mut(|(idx, text)| {
+                ui.horizontal(|ui| {
+                    let keep = !ui.button("✖").clicked();
+                    ui.add(DragValue::new(idx).range(0..=len.saturating_sub(1)))
+                        .on_hover_text("Index of the curve point");
+                    ui.text_edit_singleline(text);
+                    keep
+                })
+                .inner
+            });
+            if ui.button("➕ Add").clicked() {
+                let n = line.annotations.len() + 1;
+                line.annotations.push((0, format!("P{n}")));
+            }
+        });
+    });
     keep
 }
 
@@ -210,6 +366,13 @@ impl PlotType {
                         hint(ui, "Take the sphere without the linkage");
                     }
                 });
+                ui.horizontal(|ui| {
+                    let mut fig = fig.lock().unwrap();
+                    ui.checkbox(&mut fig.sphere_grid, "Show sphere grid");
+                    if fig.sphere_grid {
+                        angle(ui, "every ", &mut fig.sphere_grid_step, "");
+                    }
+                });
                 let get_fb = |fb| match fb {
                     io::Fb::S(fb) => Some(fb),
                     _ => None,
@@ -361,7 +524,11 @@ impl Plotter {
         }
         ui.horizontal(|ui| {
             if ui.button("💾 Save Plot").clicked() {
-                self.save_plot();
+                io::save_svg_ask(&self.render_svg(), "figure.svg");
+            }
+            if ui.button("💾 Save HTML").clicked() {
+                let html = plot::svg_to_html(&self.render_svg(), "Figure");
+                io::save_html_ask(&html, "figure.html");
             }
             if ui
                 .add_enabled(self.gif_pg.is_none(), Button::new("🎥 Save GIF Plot"))
@@ -369,10 +536,34 @@ impl Plotter {
             {
                 self.save_plot_gif();
             }
+            if let Some(Some(PlotType::P(fig))) = self.queue.get(self.curr) {
+                if ui
+                    .button("🎞 Save Animated SVG")
+                    .on_hover_text("Resolution-independent animation of the current planar subplot")
+                    .clicked()
+                {
+                    let svg = fig.lock().unwrap().plot_animated_svg(GIF_RES);
+                    io::save_svg_ask(&svg, "figure.anim.svg");
+                }
+            }
+            if let Some(Some(p)) = self.queue.get(self.curr) {
+                if ui
+                    .button("🏷 Save Legend")
+                    .on_hover_text("Legend of the current subplot without the chart")
+                    .clicked()
+                {
+                    let size = (self.size / 2, self.size / 4);
+                    let svg = match p {
+                        PlotType::P(fig) => fig.lock().unwrap().plot_legend_only(size),
+                        PlotType::S(fig) => fig.lock().unwrap().plot_legend_only(size),
+                    };
+                    io::save_svg_ask(&svg, "legend.svg");
+                }
+            }
         });
     }
 
-    fn save_plot(&mut self) {
+    fn render_svg(&self) -> String {
         let mut buf = String::new();
         let size = (
             self.size * self.shape.1 as u32,
@@ -386,7 +577,7 @@ impl Plotter {
                 Some(PlotType::S(fig)) => io::alert!("Plot", fig.lock().unwrap().plot(root)),
             }
         }
-        io::save_svg_ask(&buf, "figure.svg");
+        buf
     }
 
     fn save_plot_gif(&mut self) {
@@ -434,3 +625,30 @@ impl Plotter {
         f(); // Block
     }
 }
+
+#[test]
+fn background_option_roundtrip() {
+    for background in [None, Some(plot::RGBAColor(30, 30, 30, 1.))] {
+        let opt = plot::Opt { background, ..Default::default() };
+        let s = ron::to_string(&opt).unwrap();
+        assert!(ron::from_str::<plot::Opt>(&s).unwrap() == opt);
+    }
+    // The figures without the option are filled with white
+    let opt = ron::from_str::<plot::Opt>("(stroke: 5)").unwrap();
+    assert!(opt.background == Some(plot::RGBAColor(255, 255, 255, 1.)));
+}
+
+#[test]
+fn marker_option_roundtrip() {
+    use plot::Style;
+    let opt = plot::Opt {
+        ground_marker: Style::Square,
+        joint_marker: Style::Cross,
+        ..Default::default()
+    };
+    let s = ron::to_string(&opt).unwrap();
+    assert!(ron::from_str::<plot::Opt>(&s).unwrap() == opt);
+    // The figures without the option use the triangle and circle
+    let opt = ron::from_str::<plot::Opt>("(stroke: 5)").unwrap();
+    assert!(opt.ground_marker == Style::Triangle && opt.joint_marker == Style::Circle);
+}
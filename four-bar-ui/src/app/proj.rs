@@ -23,10 +23,19 @@ impl Queue {
 pub(crate) struct Projects {
     curr: usize,
     list: Vec<Project>,
+    seed: Option<u64>,
+    #[serde(skip)]
+    rng: Option<four_bar::mh::random::Rng>,
     #[serde(skip)]
     queue: Queue,
     #[serde(skip)]
     path: io::Cache<PathBuf>,
+    #[serde(skip)]
+    drag: Option<usize>,
+    #[serde(skip)]
+    on_joint: bool,
+    #[serde(skip)]
+    compare: Option<usize>,
 }
 
 impl Projects {
@@ -109,7 +118,24 @@ impl Projects {
             if ui.button("✚ [S]").on_hover_text(S_DES).clicked() {
                 self.queue.push(None, io::Fb::S(SFourBar::example()));
             }
+            ui.menu_button("✚ Examples ⏷", |ui| {
+                for (name, fb) in FourBar::benchmarks() {
+                    if ui.button(name).clicked() {
+                        self.queue.push(None, io::Fb::P(fb));
+                        ui.close_menu();
+                    }
+                }
+            })
+            .response
+            .on_hover_text("Load a well-known planar four-bar linkage");
         });
+        let res = check_on(ui, "Random seed", &mut self.seed, any_i);
+        if res
+            .on_hover_text("Seed of the randomized linkages")
+            .changed()
+        {
+            self.rng = None;
+        }
         ui.separator();
         ui.horizontal(|ui| {
             if self.list.is_empty() {
@@ -140,8 +166,17 @@ impl Projects {
             }
         });
         if let Some(proj) = self.list.get_mut(self.curr) {
-            proj.convert_btn(ui);
-            proj.show(ui);
+            ui.horizontal(|ui| {
+                proj.convert_btn(ui);
+                proj.equations_btn(ui);
+                proj.permalink_btn(ui);
+            });
+            self.compare_btn(ui);
+            self.compare_window(ui);
+            let proj = &mut self.list[self.curr];
+            let rng =
+                (self.rng).get_or_insert_with(|| four_bar::mh::random::Rng::new(self.seed.into()));
+            proj.show(ui, rng);
         } else {
             ui.heading("No project here!");
             ui.label("Please open or create a project.");
@@ -175,13 +210,109 @@ impl Projects {
         }
     }
 
+    fn compare_btn(&mut self, ui: &mut Ui) {
+        if self.list[self.curr].as_fb().is_none() {
+            return;
+        }
+        ui.menu_button("⚖ Compare With ⏷", |ui| {
+            for (i, proj) in self.list.iter().enumerate() {
+                if i == self.curr || proj.as_fb().is_none() {
+                    continue;
+                }
+                if ui.button(proj.proj_name()).clicked() {
+                    self.compare = Some(i);
+                    ui.close_menu();
+                }
+            }
+        })
+        .response
+        .on_hover_text("Compare the current planar linkage with another one");
+    }
+
+    fn compare_window(&mut self, ui: &mut Ui) {
+        let Some(i) = self.compare else {
+            return;
+        };
+        let (Some(fb1), Some(Some(fb2))) = (
+            self.list[self.curr].as_fb(),
+            self.list
+                .get(i)
+                .filter(|_| i != self.curr)
+                .map(|p| p.as_fb()),
+        ) else {
+            // The projects are closed or changed
+            self.compare = None;
+            return;
+        };
+        let cmp = fb1.compare(fb2, 180);
+        let title = self.list[i].proj_name();
+        let mut open = true;
+        Window::new("⚖ Comparison")
+            .open(&mut open)
+            .show(ui.ctx(), |ui| {
+                ui.label(format!("Difference: {title} - current"));
+                Grid::new("compare").striped(true).show(ui, |ui| {
+                    let names = four_bar::mech::fb::Comparison::NAMES;
+                    for (i, (name, d)) in std::iter::zip(names, cmp.diff).enumerate() {
+                        ui.label(name);
+                        if matches!(i, 2 | 8) {
+                            ui.label(format!("{:.4}°", d.to_degrees()));
+                        } else {
+                            ui.label(format!("{d:.4}"));
+                        }
+                        ui.end_row();
+                    }
+                });
+                ui.separator();
+                ui.label(format!("Curve Hausdorff distance: {:.4}", cmp.hausdorff));
+                let ty = [fb1.ty().name(), fb2.ty().name()];
+                if cmp.same_ty {
+                    ui.label(format!("Same type: {}", ty[0]));
+                } else {
+                    ui.label(format!("Different types: {} / {}", ty[0], ty[1]));
+                }
+            });
+        if !open {
+            self.compare = None;
+        }
+    }
+
     fn close_curr(&mut self) {
+        self.compare = None;
         self.list.remove(self.curr);
         if self.curr > 0 {
             self.curr -= 1;
         }
     }
 
+    pub(crate) fn probe(&mut self, p: [f64; 2], tol: f64) {
+        if let Some(proj) = self.list.get_mut(self.curr) {
+            proj.probe(p, tol);
+        }
+    }
+
+    // Drag the joints of the current linkage on the canvas
+    pub(crate) fn drag(&mut self, p: [f64; 2], tol: f64, start: bool, dragged: bool) {
+        let Some(proj) = self.list.get_mut(self.curr) else {
+            return;
+        };
+        if start {
+            self.drag = proj.hit_joint(p, tol);
+        } else if !dragged {
+            self.drag = None;
+        }
+        if let Some(i) = self.drag.filter(|_| dragged) {
+            proj.drag_joint(i, p);
+        }
+        self.on_joint = self.drag.is_some() || proj.hit_joint(p, tol).is_some();
+    }
+
+    // Return true if a joint is hovered or dragged, the canvas should not be
+    // panned
+    pub(crate) fn on_joint(&self) -> bool {
+        self.on_joint
+    }
+
     pub(crate) fn current_fb_state(&self) -> Option<(f64, io::Fb)> {
         Some(self.list.get(self.curr)?.fb_state())
     }
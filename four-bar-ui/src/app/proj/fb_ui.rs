@@ -115,7 +115,7 @@ fn plot2d_basic(ui: &mut egui_plot::PlotUi, cache: &Cache<2>, is_main: bool) {
     }
     // Plot curves
     for (i, name) in CURVE_NAME.iter().enumerate() {
-        let iter = cache.curves.iter().map(|c| c[i]).collect::<Vec<_>>();
+        let iter = cache.curve_iter(i).collect::<Vec<_>>();
         let line = egui_plot::Line::new(iter)
             .name(name)
             .width(3.)
@@ -186,7 +186,7 @@ impl ProjPlot<3> for SFourBar {
         // Plot curves
         for (i, name) in CURVE_NAME.iter().enumerate() {
             let color = pick_color(i);
-            let iter = cache.curves.iter().map(|c| c[i]);
+            let iter = cache.curve_iter(i);
             draw_sline(ui, oz, iter, |s| s.name(name).width(3.).color(color));
         }
     }
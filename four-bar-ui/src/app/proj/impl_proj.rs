@@ -72,7 +72,7 @@ impl Project {
             {
                 Self::M(MFbProj {
                     path,
-                    fb: MFourBar::from_fb_angle(fb, 0.),
+                    fb: fb.into_motion(),
                     res,
                     unsaved: true,
                     ..MFbProj::default()
@@ -95,8 +95,82 @@ impl Project {
         unsafe { std::ptr::write(self, new_self) };
     }
 
+    pub(crate) fn probe(&mut self, p: [f64; 2], tol: f64) {
+        if let Self::P(proj) = self {
+            proj.probe = Some((p, proj.fb.branches_reaching(p, tol)));
+        }
+    }
+
+    // Index of the joint that can be dragged under the point
+    pub(crate) fn hit_joint(&self, p: [f64; 2], tol: f64) -> Option<usize> {
+        let joints = match self {
+            Self::P(proj) if !proj.hide => proj.cache.joints?,
+            Self::M(proj) if !proj.hide => proj.cache.joints?,
+            _ => return None,
+        };
+        let [x, y] = p;
+        DRAG_JOINTS
+            .into_iter()
+            .find(|&i| (joints[i][0] - x).hypot(joints[i][1] - y) <= tol)
+    }
+
+    pub(crate) fn drag_joint(&mut self, i: usize, p: [f64; 2]) {
+        match self {
+            Self::P(proj) => {
+                if drag_fb(&mut proj.fb, proj.angle, i, p) {
+                    proj.unsaved = true;
+                    proj.cache();
+                }
+            }
+            Self::M(proj) => {
+                if drag_fb(proj.fb.as_fb_mut(), proj.angle, i, p) {
+                    proj.unsaved = true;
+                    proj.cache();
+                }
+            }
+            Self::S(_) => (),
+        }
+    }
+
+    pub(crate) fn as_fb(&self) -> Option<&FourBar> {
+        match self {
+            Self::P(proj) => Some(&proj.fb),
+            Self::M(proj) => Some(proj.fb.as_fb()),
+            Self::S(_) => None,
+        }
+    }
+
+    pub(crate) fn equations_btn(&self, ui: &mut Ui) {
+        let eq = match self {
+            Self::P(proj) => proj.fb.to_equations(),
+            Self::M(proj) => proj.fb.as_fb().to_equations(),
+            Self::S(_) => return,
+        };
+        if ui
+            .button("🗐 Copy Equations")
+            .on_hover_text("Copy the position equations of the joints")
+            .clicked()
+        {
+            ui.output_mut(|s| s.copied_text = eq);
+        }
+    }
+
+    pub(crate) fn permalink_btn(&self, ui: &mut Ui) {
+        let Some(fb) = self.as_fb() else {
+            return;
+        };
+        if ui
+            .button("🔗 Copy Permalink")
+            .on_hover_text("Copy the link of the web version with the compact code")
+            .clicked()
+        {
+            const URL_PREFIX: &str = "https://kmolyuan.github.io/four-bar-rs/#";
+            ui.output_mut(|s| s.copied_text = format!("{URL_PREFIX}{}", fb.to_share_code()));
+        }
+    }
+
     impl_method! {
-        fn show(self: &mut Self, ui: &mut Ui);
+        fn show(self: &mut Self, ui: &mut Ui, rng: &mut mh::random::Rng);
         fn cache(self: &mut Self);
         fn plot(self: &Self, ui: &mut egui_plot::PlotUi, ind: usize, id: usize);
         fn coupler(self: &Self) -> io::Curve;
@@ -109,10 +183,48 @@ impl Project {
     }
 }
 
+// The driver pivot, the follower pivot and the coupler point
+const DRAG_JOINTS: [usize; 3] = [0, 1, 4];
+
+// Move a joint of the linkage to the point, the changes that make the linkage
+// invalid at the current angle are rejected
+fn drag_fb(fb: &mut FourBar, angle: f64, i: usize, [x, y]: [f64; 2]) -> bool {
+    use four_bar::mech::CurveGen as _;
+    let Some([p1, _, p3, p4, _]) = fb.pos(angle) else {
+        return false;
+    };
+    let mut new = fb.clone();
+    match i {
+        // Move the whole linkage
+        0 => new.unnorm.set_origin(x, y),
+        // Change the ground link
+        1 => {
+            let [dx, dy] = [x - p1[0], y - p1[1]];
+            new.norm.l1 = dx.hypot(dy);
+            new.unnorm.a = dy.atan2(dx);
+        }
+        // Change the extended link
+        4 => {
+            let [dx, dy] = [x - p3[0], y - p3[1]];
+            new.norm.l5 = dx.hypot(dy);
+            new.norm.g = dy.atan2(dx) - (p4[1] - p3[1]).atan2(p4[0] - p3[0]);
+        }
+        _ => return false,
+    }
+    if new.norm.l1 <= 0. || !new.is_valid() || new.pos(angle).is_none() {
+        return false;
+    }
+    *fb = new;
+    true
+}
+
 type FbProj = ProjInner<NormFourBar, 2>;
 type MFbProj = ProjInner<MNormFourBar, 2>;
 type SFbProj = ProjInner<SNormFourBar, 3>;
 
+// Lower bound of the curve resolution
+const RES_MIN: usize = 10;
+
 #[derive(Deserialize, Serialize)]
 struct ProjInner<M, const D: usize>
 where
@@ -125,12 +237,17 @@ where
     angle: f64,
     bound: Option<[f64; 2]>,
     res: usize,
+    precision: Option<usize>,
     hide: bool,
+    #[serde(default)]
+    trace: bool,
     #[serde(skip)]
     unsaved: bool,
     #[serde(skip)]
     cache: Cache<D>,
     #[serde(skip)]
+    probe: Option<([f64; 2], u8)>,
+    #[serde(skip)]
     undo: undo::Undo<<M::De as undo::IntoDelta>::Delta>,
 }
 
@@ -147,9 +264,12 @@ where
             angle: 0.,
             bound: None,
             res: 40,
+            precision: None,
             hide: false,
+            trace: false,
             unsaved: false,
             cache: Default::default(),
+            probe: None,
             undo: Default::default(),
         }
     }
@@ -161,6 +281,23 @@ pub(crate) struct Cache<const D: usize> {
     pub(crate) joints: Option<[[f64; D]; 5]>,
     pub(crate) curves: Vec<[[f64; D]; 3]>,
     pub(crate) state_curves: Vec<Vec<[f64; D]>>,
+    self_intersecting: bool,
+    branch_defect: bool,
+    // Velocity ratio `[theta, ω4/ω2]` over the motion
+    vel_ratio: Vec<[f64; 2]>,
+    // Coefficient of variation of the coupler point speed
+    speed_cv: Option<f64>,
+    // Angle of the mirror symmetry axis of the coupler curve, computed on
+    // demand
+    symmetry: Option<Option<f64>>,
+    // Input torque `[theta, torque]` of a unit load at the coupler point
+    torque: Vec<[f64; 2]>,
+    // Mechanical advantage `[theta, T4/T2]` over the motion
+    mech_adv: Vec<[f64; 2]>,
+    // Input-output function `[theta2, theta4]` in degrees
+    io_func: Vec<[f64; 2]>,
+    // Number of the coupler points to be drawn in the trace mode
+    trace: Option<usize>,
 }
 
 impl<const D: usize> Default for Cache<D> {
@@ -171,10 +308,40 @@ impl<const D: usize> Default for Cache<D> {
             joints: None,
             curves: Vec::new(),
             state_curves: Vec::new(),
+            self_intersecting: false,
+            branch_defect: false,
+            vel_ratio: Vec::new(),
+            speed_cv: None,
+            symmetry: None,
+            torque: Vec::new(),
+            mech_adv: Vec::new(),
+            io_func: Vec::new(),
+            trace: None,
         }
     }
 }
 
+impl<const D: usize> Cache<D> {
+    // The `i`-th joint curve, the coupler curve is cut at the current angle
+    // in the trace mode
+    pub(crate) fn curve_iter(&self, i: usize) -> impl Iterator<Item = [f64; D]> + '_ {
+        let n = match self.trace {
+            Some(n) if i == 2 => n,
+            _ => self.curves.len(),
+        };
+        self.curves[..n].iter().map(move |c| c[i])
+    }
+}
+
+// Number of the curve points from the start of the angle bound to the angle
+fn trace_len(bound: mech::AngleBound, angle: f64, len: usize) -> Option<usize> {
+    use std::f64::consts::TAU;
+    let [start, end] = bound.to_value()?;
+    let end = if end > start { end } else { end + TAU };
+    let frac = ((angle - start).rem_euclid(TAU) / (end - start)).min(1.);
+    Some(((frac * len as f64) as usize + 1).min(len))
+}
+
 fn angle_bound_ui(ui: &mut Ui, theta2: &mut f64, start: f64, end: f64) -> Response {
     fn copy_btn(ui: &mut Ui, start: f64, end: f64, suffix: &str) {
         ui.horizontal(|ui| {
@@ -227,10 +394,15 @@ where
     {
         use four_bar::mech::{CurveGen as _, Statable as _};
         self.cache.changed = false;
+        self.probe = None;
         self.cache.joints = self.fb.pos(self.angle);
         self.cache.angle_bound = self.fb.angle_bound();
         self.cache.curves = self.fb.curves(self.res);
+        self.cache.symmetry = None;
         self.fb.cache_curve(&mut self.cache, self.res);
+        self.cache.trace = (self.trace)
+            .then(|| trace_len(self.cache.angle_bound, self.angle, self.cache.curves.len()))
+            .flatten();
     }
 
     fn plot(&self, ui: &mut egui_plot::PlotUi, ind: usize, id: usize)
@@ -240,6 +412,12 @@ where
         if !self.hide {
             fb_ui::ProjPlot::proj_plot(&self.fb, ui, &self.cache, ind == id);
         }
+        if let Some((p, _)) = self.probe.filter(|_| ind == id) {
+            let p = egui_plot::Points::new(p)
+                .shape(egui_plot::MarkerShape::Cross)
+                .radius(10.);
+            ui.points(p);
+        }
     }
 
     fn name(&self) -> Cow<str> {
@@ -297,6 +475,7 @@ where
         + fb_ui::ProjUi
         + fb_ui::ProjPlot<D>
         + CacheAdaptor<D>
+        + Preset
         + PartialEq
         + Default
         + Serialize
@@ -304,7 +483,7 @@ where
     Self: CouplerGen,
     efd::U<D>: efd::EfdDim<D>,
 {
-    fn show(&mut self, ui: &mut Ui) {
+    fn show(&mut self, ui: &mut Ui, rng: &mut mh::random::Rng) {
         use four_bar::mech::Statable as _;
         ui.horizontal(|ui| {
             if small_btn(ui, "🔗", "Share with Link") {
@@ -353,39 +532,127 @@ where
                 self.undo.clear();
             }
         });
+        ui.horizontal(|ui| {
+            let mut fb = None;
+            if ui
+                .button("🎲 Randomize")
+                .on_hover_text("Generate a random valid linkage at the same offset")
+                .clicked()
+            {
+                fb = self.fb.random(rng);
+            }
+            if ui.button("Reset to Example").clicked() {
+                fb = Some(M::De::example());
+            }
+            if let Some(fb) = fb {
+                self.fb = fb;
+                if let Some(angle) = self.fb.angle_bound().clamp(self.angle) {
+                    self.angle = angle;
+                }
+                self.cache.changed = true;
+                self.unsaved = true;
+            }
+        });
         ui.add_enabled_ui(!self.hide, |ui| self.ui(ui));
         self.undo.fetch(&self.fb);
     }
 
     fn ui(&mut self, ui: &mut Ui) {
+        use four_bar::mech::Statable as _;
         ui.heading("Curve");
         ui.horizontal(|ui| {
-            self.cache.changed |= nonzero_i(ui, "Resolution: ", &mut self.res, 1).changed();
-            hint(ui, "Resolution of rendering and data export");
+            let dv = DragValue::new(&mut self.res)
+                .prefix("Curve resolution: ")
+                .range(RES_MIN..=usize::MAX);
+            self.cache.changed |= ui.add(dv).changed();
+            const HINT: &str = "Number of the curve points in rendering and data export\n\
+                Higher resolution gives smoother curves but costs more time";
+            hint(ui, HINT);
+        });
+        ui.horizontal(|ui| {
+            self.cache.changed |= ui.checkbox(&mut self.trace, "Live coupler trace").changed();
+            hint(
+                ui,
+                "Draw the coupler curve up to the current angle, which shows the motion direction",
+            );
         });
         ui.horizontal(|ui| {
             ui.label("Coupler Motion: ");
             if small_btn(ui, "💾", "Save") {
                 match self.coupler() {
-                    io::Curve::P(c) => io::save_csv_ask(&c),
-                    io::Curve::M(c) => io::save_csv_ask(&c),
-                    io::Curve::S(c) => io::save_csv_ask(&c),
+                    io::Curve::P(c) => io::save_csv_ask(&c, self.precision),
+                    io::Curve::M(c) => io::save_csv_ask(&c, self.precision),
+                    io::Curve::S(c) => io::save_csv_ask(&c, self.precision),
                 }
             }
             if small_btn(ui, "🗐", "Copy") {
                 let text = match self.coupler() {
-                    io::Curve::P(c) => csv::to_string(c).unwrap(),
-                    io::Curve::M(c) => csv::to_string(c).unwrap(),
-                    io::Curve::S(c) => csv::to_string(c).unwrap(),
+                    io::Curve::P(c) => csv::to_string(c, self.precision).unwrap(),
+                    io::Curve::M(c) => csv::to_string(c, self.precision).unwrap(),
+                    io::Curve::S(c) => csv::to_string(c, self.precision).unwrap(),
                 };
                 ui.output_mut(|s| s.copied_text = text);
             }
+            if small_btn(
+                ui,
+                "💾 Curve+Angle",
+                "Save the coupler curve with the input angles",
+            ) {
+                self.save_coupler_angle();
+            }
+            if small_btn(
+                ui,
+                "💾 All Joints",
+                "Save the driver, follower and coupler paths with the input angles",
+            ) {
+                self.save_joints();
+            }
         });
         let callback = |ui: &mut Ui, [start, end]: &mut [_; 2]| {
             ui.vertical(|ui| angle(ui, "start: ", start, "") | angle(ui, "end: ", end, ""))
                 .inner
         };
         check_on(ui, "Export in range", &mut self.bound, callback);
+        check_on(ui, "Export precision", &mut self.precision, |ui, prec| {
+            counter(ui, prec, 0..=17)
+        })
+        .on_hover_text("Decimal places of the CSV data");
+        let coupler = self.cache.curves.iter().map(|c| c[2]).collect::<Vec<_>>();
+        let closed = !self.cache.angle_bound.is_open();
+        let length = four_bar::curve::arc_length(&coupler, closed);
+        ui.label(format!("Coupler curve length: {length:.04}"));
+        if self.cache.self_intersecting {
+            ui.colored_label(Color32::RED, "⚠ The coupler curve crosses itself");
+        }
+        if self.cache.branch_defect {
+            ui.colored_label(Color32::RED, "⚠ The linkage passes through a change point")
+                .on_hover_text("The real mechanism may switch to the other branch");
+        }
+        if D == 2 {
+            ui.collapsing("Symmetry axis", |ui| {
+                let res = self.res;
+                let axis = (self.cache.symmetry).get_or_insert_with(|| self.fb.symmetry_axis(res));
+                ui.horizontal(|ui| {
+                    match axis {
+                        Some(axis) => ui.label(format!("Angle: {:.04}°", axis.to_degrees())),
+                        None => ui.label("Not symmetric"),
+                    };
+                    hint(
+                        ui,
+                        "Angle of the mirror symmetry axis through the curve centroid",
+                    );
+                });
+            });
+        }
+        if let Some(([x, y], n)) = self.probe {
+            ui.horizontal(|ui| {
+                ui.label(format!("Branches reaching ({x:.04}, {y:.04}): {n}"));
+                hint(
+                    ui,
+                    "Number of the assembly modes that trace the clicked point",
+                );
+            });
+        }
         ui.separator();
         ui.horizontal(|ui| {
             ui.heading("Offset");
@@ -396,7 +663,17 @@ where
             }
             hint(ui, "Remove offset, rotation and scaling");
         });
+        let stat = self.fb.stat();
         let mut res = fb_ui::ProjUi::proj_ui(&mut self.fb, ui);
+        if self.fb.stat() != stat {
+            // Keep the current pose in the angle bound of the new branch
+            if let Some(angle) = self.fb.angle_bound().clamp(self.angle) {
+                if angle != self.angle {
+                    self.angle = angle;
+                    res = res.on_hover_text("Theta is clamped into the new angle bound");
+                }
+            }
+        }
         self.unsaved |= res.changed();
         ui.separator();
         ui.heading("Angle");
@@ -404,6 +681,89 @@ where
             res |= angle_bound_ui(ui, &mut self.angle, start, end);
         }
         res |= angle(ui, "Theta: ", &mut self.angle, "");
+        if let Some(cv) = self.cache.speed_cv {
+            ui.horizontal(|ui| {
+                ui.label(format!("Speed variation: {cv:.04}"));
+                hint(
+                    ui,
+                    "Coefficient of variation of the coupler point speed, lower is smoother",
+                );
+            });
+        }
+        if let (Some([start, _]), false) = (
+            self.cache.angle_bound.to_value(),
+            self.cache.torque.is_empty(),
+        ) {
+            ui.collapsing("Input torque", |ui| {
+                ui.label("Balancing a unit downward load at the coupler point");
+                let theta = if self.angle < start {
+                    self.angle + std::f64::consts::TAU
+                } else {
+                    self.angle
+                };
+                let plot = static_plot("plot_torque").x_axis_label("theta (deg)");
+                plot.height(150.).show(ui, |ui| {
+                    ui.line(egui_plot::Line::new(self.cache.torque.clone()).name("torque"));
+                    ui.vline(egui_plot::VLine::new(theta.to_degrees()).name("theta"));
+                });
+            });
+        }
+        if let (Some([start, _]), false) = (
+            self.cache.angle_bound.to_value(),
+            self.cache.vel_ratio.is_empty(),
+        ) {
+            ui.collapsing("Velocity ratio", |ui| {
+                ui.label("Angular velocity ratio of the follower to the driver (ω4/ω2)");
+                let theta = if self.angle < start {
+                    self.angle + std::f64::consts::TAU
+                } else {
+                    self.angle
+                };
+                let plot = static_plot("plot_vel_ratio").x_axis_label("theta (deg)");
+                plot.height(150.).show(ui, |ui| {
+                    ui.line(egui_plot::Line::new(self.cache.vel_ratio.clone()).name("ω4/ω2"));
+                    ui.vline(egui_plot::VLine::new(theta.to_degrees()).name("theta"));
+                });
+            });
+        }
+        if let (Some([start, _]), false) = (
+            self.cache.angle_bound.to_value(),
+            self.cache.mech_adv.is_empty(),
+        ) {
+            ui.collapsing("Mechanical advantage", |ui| {
+                ui.label("Output torque to input torque (T4/T2), the reciprocal of ω4/ω2");
+                let theta = if self.angle < start {
+                    self.angle + std::f64::consts::TAU
+                } else {
+                    self.angle
+                };
+                let plot = static_plot("plot_mech_adv").x_axis_label("theta (deg)");
+                plot.height(150.).show(ui, |ui| {
+                    ui.line(egui_plot::Line::new(self.cache.mech_adv.clone()).name("T4/T2"));
+                    ui.vline(egui_plot::VLine::new(theta.to_degrees()).name("theta"));
+                });
+            });
+        }
+        if let (Some([start, _]), false) = (
+            self.cache.angle_bound.to_value(),
+            self.cache.io_func.is_empty(),
+        ) {
+            ui.collapsing("Input-output function", |ui| {
+                ui.label("Follower angle versus driver angle, measured from the ground link");
+                let theta = if self.angle < start {
+                    self.angle + std::f64::consts::TAU
+                } else {
+                    self.angle
+                };
+                let plot = static_plot("plot_io_func")
+                    .x_axis_label("theta2 (deg)")
+                    .y_axis_label("theta4 (deg)");
+                plot.height(150.).show(ui, |ui| {
+                    ui.line(egui_plot::Line::new(self.cache.io_func.clone()).name("theta4"));
+                    ui.vline(egui_plot::VLine::new(theta.to_degrees()).name("theta2"));
+                });
+            });
+        }
         self.cache.changed |= res.changed();
         if self.cache.changed {
             self.cache();
@@ -413,24 +773,112 @@ where
 
 trait CouplerGen {
     fn coupler(&self) -> io::Curve;
+    // Save the coupler curve with the input angle column
+    fn save_coupler_angle(&self);
+    // Save the paths of all moving joints with the input angle column
+    fn save_joints(&self);
 }
 
 impl CouplerGen for FbProj {
     fn coupler(&self) -> io::Curve {
         io::Curve::P(self.fb.curve(self.res))
     }
+
+    fn save_coupler_angle(&self) {
+        let curve = mech::CurveGen::curve_angle(&self.fb, self.res);
+        io::save_csv_ask(&curve, self.precision);
+    }
+
+    fn save_joints(&self) {
+        let curves = mech::CurveGen::curves_angle(&self.fb, self.res);
+        io::save_joints_ask(&curves, self.precision);
+    }
 }
 
 impl CouplerGen for MFbProj {
     fn coupler(&self) -> io::Curve {
         io::Curve::M(self.fb.pose_zipped(self.res))
     }
+
+    fn save_coupler_angle(&self) {
+        let curve = mech::CurveGen::curve_angle(&self.fb, self.res);
+        io::save_csv_ask(&curve, self.precision);
+    }
+
+    fn save_joints(&self) {
+        let curves = mech::CurveGen::curves_angle(&self.fb, self.res);
+        io::save_joints_ask(&curves, self.precision);
+    }
 }
 
 impl CouplerGen for SFbProj {
     fn coupler(&self) -> io::Curve {
         io::Curve::S(self.fb.curve(self.res))
     }
+
+    fn save_coupler_angle(&self) {
+        let curve = mech::CurveGen::curve_angle(&self.fb, self.res);
+        io::save_csv_ask(&curve, self.precision);
+    }
+
+    fn save_joints(&self) {
+        let curves = mech::CurveGen::curves_angle(&self.fb, self.res);
+        io::save_joints_ask(&curves, self.precision);
+    }
+}
+
+trait Preset: Sized {
+    fn example() -> Self;
+    // Random valid linkage with the same offset, or `None` if not found
+    fn random(&self, rng: &mut mh::random::Rng) -> Option<Self>;
+}
+
+// Rejection sampling of the valid normalized linkages
+fn sample_valid<M, const N: usize>(rng: &mut mh::random::Rng) -> Option<M>
+where
+    M: syn::SynBound<N>,
+{
+    const MAX_TRY: usize = 1000;
+    (0..MAX_TRY).find_map(|_| {
+        (rng.sample(atlas::Distr::<M, N>::new()).into_iter()).find(|fb| fb.is_valid())
+    })
+}
+
+impl Preset for FourBar {
+    fn example() -> Self {
+        Self::example()
+    }
+
+    fn random(&self, rng: &mut mh::random::Rng) -> Option<Self> {
+        use atlas::Offset as _;
+        let mech::fb::UnNorm { p1x, p1y, a, l2 } = self.unnorm;
+        Some(sample_valid::<NormFourBar, 5>(rng)?.denorm_offset(&[p1x, p1y, a, l2]))
+    }
+}
+
+impl Preset for MFourBar {
+    fn example() -> Self {
+        Self::example()
+    }
+
+    fn random(&self, rng: &mut mh::random::Rng) -> Option<Self> {
+        use atlas::Offset as _;
+        let mech::fb::UnNorm { p1x, p1y, a, l2 } = self.unnorm;
+        let MNormFourBar { base, e } = sample_valid::<MNormFourBar, 6>(rng)?;
+        let fb = base.denorm_offset(&[p1x, p1y, a, l2]);
+        Some(Self::from_fb_angle(fb, e))
+    }
+}
+
+impl Preset for SFourBar {
+    fn example() -> Self {
+        Self::example()
+    }
+
+    fn random(&self, rng: &mut mh::random::Rng) -> Option<Self> {
+        let norm = sample_valid::<SNormFourBar, 6>(rng)?;
+        Some(Self::new(self.unnorm.clone(), norm))
+    }
 }
 
 fn state_curves<M, const D: usize>(
@@ -450,16 +898,96 @@ where
 trait CacheAdaptor<const D: usize> {
     // How to cache the "state_curves" field.
     fn cache_curve(&self, cache: &mut Cache<D>, res: usize);
+    // Angle of the mirror symmetry axis of the planar coupler curves
+    fn symmetry_axis(&self, _res: usize) -> Option<f64> {
+        None
+    }
+}
+// Sample a quantity `[theta, value]` over the angle bound, the singular
+// positions are skipped
+fn angle_profile<F>(bound: mech::AngleBound, res: usize, f: F) -> Vec<[f64; 2]>
+where
+    F: Fn(f64) -> Option<f64>,
+{
+    use std::f64::consts::TAU;
+    let Some([start, end]) = bound.to_value() else {
+        return Vec::new();
+    };
+    let end = if end > start { end } else { end + TAU };
+    let step = (end - start) / res as f64;
+    (0..res)
+        .map(|i| start + i as f64 * step)
+        .filter_map(|t| Some([t.to_degrees(), f(t)?]))
+        .collect()
+}
+
+// Input torque over the angle bound, balancing a unit downward load at the
+// coupler point
+fn torque_profile(fb: &FourBar, bound: mech::AngleBound, res: usize) -> Vec<[f64; 2]> {
+    angle_profile(bound, res, |t| fb.input_torque(t, [0., -1.]))
 }
+
+// Tolerance of the mirror error of the symmetric coupler curves
+const SYMMETRY_TOL: f64 = 1e-2;
+
+// Angular velocity ratio over the angle bound
+fn vel_ratio_profile(fb: &FourBar, bound: mech::AngleBound, res: usize) -> Vec<[f64; 2]> {
+    angle_profile(bound, res, |t| fb.velocity_ratio(t))
+}
+
+// Mechanical advantage over the angle bound
+fn mech_adv_profile(fb: &FourBar, bound: mech::AngleBound, res: usize) -> Vec<[f64; 2]> {
+    angle_profile(bound, res, |t| fb.mechanical_advantage(t))
+}
+
+// Input-output function of the planar linkages in degrees
+fn io_func(fb: &FourBar, res: usize) -> Vec<[f64; 2]> {
+    (fb.io_function(res).into_iter())
+        .map(|(t2, t4)| [t2.to_degrees(), t4.to_degrees()])
+        .collect()
+}
+
+// Check the self-intersection of the planar coupler curve
+fn self_intersecting(cache: &Cache<2>) -> bool {
+    let coupler = cache.curves.iter().map(|c| c[2]).collect::<Vec<_>>();
+    if cache.angle_bound.is_open() {
+        curve::is_self_intersecting(&coupler)
+    } else {
+        curve::is_self_intersecting(&curve::close_loop(&coupler))
+    }
+}
+
 impl CacheAdaptor<2> for FourBar {
     fn cache_curve(&self, cache: &mut Cache<2>, res: usize) {
         cache.state_curves = state_curves(self, cache.angle_bound, res);
+        cache.self_intersecting = self_intersecting(cache);
+        cache.branch_defect = self.has_branch_defect(res);
+        cache.speed_cv = self.speed_uniformity(res);
+        cache.torque = torque_profile(self, cache.angle_bound, res);
+        cache.vel_ratio = vel_ratio_profile(self, cache.angle_bound, res);
+        cache.mech_adv = mech_adv_profile(self, cache.angle_bound, res);
+        cache.io_func = io_func(self, res);
+    }
+
+    fn symmetry_axis(&self, res: usize) -> Option<f64> {
+        self.coupler_symmetry(res, SYMMETRY_TOL)
     }
 }
 impl CacheAdaptor<2> for MFourBar {
-    fn cache_curve(&self, cache: &mut Cache<2>, _res: usize) {
+    fn cache_curve(&self, cache: &mut Cache<2>, res: usize) {
         use mech::PoseGen as _;
         cache.state_curves = vec![cache.curves.iter().map(|p| self.uvec(p)).collect()];
+        cache.self_intersecting = self_intersecting(cache);
+        cache.branch_defect = self.as_fb().has_branch_defect(res);
+        cache.speed_cv = self.as_fb().speed_uniformity(res);
+        cache.torque = torque_profile(self.as_fb(), cache.angle_bound, res);
+        cache.vel_ratio = vel_ratio_profile(self.as_fb(), cache.angle_bound, res);
+        cache.mech_adv = mech_adv_profile(self.as_fb(), cache.angle_bound, res);
+        cache.io_func = io_func(self.as_fb(), res);
+    }
+
+    fn symmetry_axis(&self, res: usize) -> Option<f64> {
+        self.as_fb().coupler_symmetry(res, SYMMETRY_TOL)
     }
 }
 impl CacheAdaptor<3> for SFourBar {
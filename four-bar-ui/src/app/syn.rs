@@ -1,7 +1,7 @@
 use super::{link::Linkages, widgets::*};
 use crate::{io, syn_cmd, syn_cmd::Target};
 use eframe::egui::*;
-use four_bar::{atlas, csv, mh, syn};
+use four_bar::{atlas, csv, mech, mh, syn};
 use serde::{Deserialize, Serialize};
 use std::{
     iter::zip,
@@ -14,6 +14,9 @@ use std::{
     },
 };
 
+// Seconds of capturing the pasted target after the paste button is clicked
+const PASTE_TIMEOUT: f64 = 5.;
+
 #[inline]
 fn ron_pretty<S: ?Sized + Serialize>(s: &S) -> String {
     ron::ser::to_string_pretty(s, Default::default()).unwrap()
@@ -23,6 +26,39 @@ fn ron_pretty<S: ?Sized + Serialize>(s: &S) -> String {
 struct Task {
     time: std::time::Duration,
     conv: Vec<f64>,
+    // Cumulative best fitness, empty in the older versions
+    #[serde(default)]
+    best: Vec<f64>,
+    #[serde(default)]
+    result: Option<io::Fb>,
+    // The older versions only saved the finished tasks
+    #[serde(default)]
+    state: TaskState,
+    #[serde(skip)]
+    selected: bool,
+}
+
+#[derive(Deserialize, Serialize, Clone, Copy, Default, PartialEq, Eq)]
+enum TaskState {
+    #[default]
+    Finished,
+    Running,
+}
+
+impl Task {
+    fn push(&mut self, best_f: f64) {
+        self.conv.push(best_f);
+        let best = self.best.last().map_or(best_f, |b| b.min(best_f));
+        self.best.push(best);
+    }
+
+    fn best(&self) -> std::borrow::Cow<[f64]> {
+        if self.best.len() == self.conv.len() {
+            self.best.as_slice().into()
+        } else {
+            syn_cmd::best_so_far(&self.conv).into()
+        }
+    }
 }
 
 #[derive(Clone)]
@@ -76,11 +112,53 @@ struct AtlasCfg {
     size: usize,
     harmonic: usize,
     is_open: bool,
+    #[serde(default)]
+    link_range: Option<LinkRange>,
 }
 
 impl Default for AtlasCfg {
     fn default() -> Self {
-        Self { size: 10000, harmonic: 20, is_open: false }
+        Self {
+            size: 10000,
+            harmonic: 20,
+            is_open: false,
+            link_range: None,
+        }
+    }
+}
+
+// Sampling range of the normalized link lengths
+#[derive(Deserialize, Serialize, Clone, Copy)]
+struct LinkRange {
+    min: f64,
+    max: f64,
+}
+
+impl Default for LinkRange {
+    fn default() -> Self {
+        Self { min: 0.5, max: 2. }
+    }
+}
+
+impl LinkRange {
+    // Restrict the links of the code bound, the last one is the angle
+    fn apply<const N: usize>(self, mut bound: [[f64; 2]; N]) -> Vec<[f64; 2]> {
+        for [u, l] in &mut bound[..N - 1] {
+            let (min, max) = (self.min.max(*u), self.max.min(*l));
+            if min < max {
+                [*u, *l] = [min, max];
+            }
+        }
+        bound.to_vec()
+    }
+}
+
+// Window size of the target smoothing, at least 3 points to take effect
+struct SmoothWin(usize);
+
+impl Default for SmoothWin {
+    fn default() -> Self {
+        Self(5)
     }
 }
 
@@ -90,8 +168,12 @@ pub(crate) struct Synthesis {
     alg: syn_cmd::SynAlg,
     cfg: syn_cmd::SynCfg,
     atlas_cfg: AtlasCfg,
+    // Seed of both the optimizer and the atlas sampling, random if unset
+    global_seed: Option<u64>,
     target: io::Curve,
+    #[serde(deserialize_with = "finished_tasks")]
     tasks: Vec<Task>,
+    // The tasks in progress are never persisted
     #[serde(skip)]
     task_queue: Vec<TaskInProg>,
     #[serde(skip)]
@@ -105,27 +187,109 @@ pub(crate) struct Synthesis {
     #[serde(skip)]
     conv_open: bool,
     #[serde(skip)]
+    conv_log: bool,
+    #[serde(skip)]
     atlas_vis_open: bool,
     #[serde(skip)]
     from_plot_open: bool,
+    // The deadline of capturing the pasted target
+    #[serde(skip)]
+    paste_until: Option<f64>,
+    #[serde(skip)]
+    smooth_win: SmoothWin,
+    #[serde(skip)]
+    overlay: Vec<(String, Vec<[f64; 2]>)>,
 }
 
 impl Synthesis {
+    // Take over the atlas (and the loading progress) from the replaced state
+    pub(crate) fn keep_atlas(&mut self, old: &mut Self) {
+        self.atlas = std::mem::take(&mut old.atlas);
+        self.atlas_vis = std::mem::take(&mut old.atlas_vis);
+        self.atlas_pg = old.atlas_pg.take();
+        self.queue = old.queue.clone();
+    }
+
     pub(crate) fn show(&mut self, ui: &mut Ui, lnk: &mut Linkages) {
         ui.horizontal(|ui| {
             ui.heading("Synthesis");
             reset_button(ui, &mut self.cfg, "Reset");
         });
+        check_on(ui, "Global seed", &mut self.global_seed, any_i).on_hover_text(
+            "Seed both the optimizer and the atlas sampling to reproduce the whole session, \
+            unset means fully random",
+        );
         ui.collapsing("Algorithm", |ui| {
             ui.group(|ui| self.opt_setting(ui));
-            check_on(ui, "Random seed", &mut self.cfg.seed, any_i);
+            ui.add_enabled_ui(self.global_seed.is_none(), |ui| {
+                check_on(ui, "Random seed", &mut self.cfg.seed, any_i)
+            });
             nonzero_i(ui, "Generation: ", &mut self.cfg.gen, 1);
             nonzero_i(ui, "Population: ", &mut self.cfg.pop, 1);
+            ui.horizontal(|ui| {
+                let stag = &mut self.cfg.stagnation;
+                check_on(ui, "Stagnation", stag, |ui, k| nonzero_i(ui, "", k, 1));
+                hint(ui, "Stop early if the best fitness is not improved for the generations.");
+            });
             nonzero_i(ui, "Resolution: ", &mut self.cfg.res, 1);
+            ui.horizontal(|ui| {
+                ui.label("Pivot");
+                let pivot = &mut self.cfg.pivot;
+                combo_enum(ui, "pivot", pivot, syn::Pivot::LIST, |e| e.name());
+                hint(ui, "The joint that traces the target curve, path synthesis only, the atlas only supports the coupler.");
+            });
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut self.cfg.mirror, "Match mirror image");
+                hint(ui, "Also compare the mirror images of the candidates, planar linkages only.");
+            });
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut self.cfg.flip, "Flip starting point");
+                hint(ui, "Also compare the other starting point of the first harmonic, closed planar curves only.");
+            });
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut self.cfg.ccw, "Counter-clockwise");
+                hint(ui, "Normalize the target and the candidate curves to counter-clockwise, planar linkages only.");
+            });
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut self.cfg.symmetry, "Exploit symmetry");
+                hint(ui, "Search the symmetric linkages only if the target curve is mirror symmetric, planar linkages only.");
+            });
             ui.horizontal(|ui| {
                 ui.checkbox(&mut self.cfg.use_dd, "Use distance discrepancy");
                 hint(ui, "When the point number is small, the distance discrepancy may help to find the better solution.");
             });
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut self.cfg.use_point, "Use precision points");
+                hint(ui, "Match the target points with the nearest positions of the coupler curve, planar linkages only.");
+            });
+            ui.horizontal(|ui| {
+                check_on(ui, "Input range", &mut self.cfg.input_range, |ui, [start, end]| {
+                    angle(ui, "", start, "") | angle(ui, "to ", end, "")
+                });
+                hint(ui, "Only use a portion of the driver rotation for the open targets, the atlas is disabled.");
+            });
+        });
+        ui.collapsing("Lock Parameters", |ui| {
+            ui.label("Lock the normalized parameters of the planar linkages.");
+            const LABELS: [&str; 4] = ["Ground: ", "Coupler: ", "Follower: ", "Extended: "];
+            let [l1, l3, l4, l5, g] = &mut self.cfg.fixed;
+            let mut lock_ext = l5.is_some() && g.is_some();
+            let res = ui.checkbox(&mut lock_ext, "Lock extension");
+            if res
+                .on_hover_text("Lock the extended link and its angle together")
+                .changed()
+            {
+                if lock_ext {
+                    l5.get_or_insert(1.);
+                    g.get_or_insert(0.);
+                } else {
+                    (*l5, *g) = (None, None);
+                }
+            }
+            for (label, val) in zip(LABELS, [l1, l3, l4, l5]) {
+                check_on(ui, label, val, |ui, v| nonzero_f(ui, "", v, 0.01));
+            }
+            check_on(ui, "Extended angle: ", g, |ui, v| angle(ui, "", v, ""));
         });
         ui.collapsing("Atlas Database", |ui| self.atlas_setting(ui));
         ui.separator();
@@ -155,12 +319,22 @@ impl Synthesis {
         ui.horizontal(|ui| {
             ui.label("Mode: ");
             for (mode, name) in [
+                (syn::Mode::Auto, "Auto"),
                 (syn::Mode::Closed, "Closed"),
                 (syn::Mode::Open, "Open"),
                 (syn::Mode::Partial, "Partial"),
             ] {
                 ui.selectable_value(&mut self.cfg.mode, mode, name);
             }
+            if self.cfg.mode.is_auto() && !self.target.is_empty() {
+                let mode = self.target.resolve_mode(self.cfg.mode);
+                ui.label(if mode.is_target_open() {
+                    "(Open)"
+                } else {
+                    "(Closed)"
+                });
+            }
+            hint(ui, "Auto: pick the closed or open mode by the target");
         });
         match std::mem::replace(&mut *self.queue.lock(), Cache::Empty) {
             Cache::Curve(curve) => self.target = curve,
@@ -187,18 +361,48 @@ impl Synthesis {
             }
             if ui.button("💾 Save CSV").clicked() {
                 match &self.target {
-                    io::Curve::P(t) => io::save_csv_ask(t),
-                    io::Curve::M(t) => io::save_csv_ask(t),
-                    io::Curve::S(t) => io::save_csv_ask(t),
+                    io::Curve::P(t) => io::save_csv_ask(t, None),
+                    io::Curve::M(t) => io::save_csv_ask(t, None),
+                    io::Curve::S(t) => io::save_csv_ask(t, None),
                 }
             }
+            if ui
+                .button("📋 Paste Target")
+                .on_hover_text(format!(
+                    "Paste CSV or RON array from the clipboard. \
+                    If the clipboard is not accessible (e.g. in the browser), \
+                    the next Ctrl+V within {PASTE_TIMEOUT} seconds is captured as the target"
+                ))
+                .clicked()
+            {
+                self.paste_until = Some(ui.input(|s| s.time) + PASTE_TIMEOUT);
+                ui.ctx().send_viewport_cmd(ViewportCommand::RequestPaste);
+            }
         });
+        if let Some(until) = self.paste_until {
+            let (time, text) = ui.input(|s| {
+                let text = s.events.iter().find_map(|e| match e {
+                    Event::Paste(text) => Some(text.clone()),
+                    _ => None,
+                });
+                (s.time, text)
+            });
+            if time > until {
+                self.paste_until = None;
+            } else if let Some(text) = text {
+                self.paste_until = None;
+                io::alert!(
+                    ("Paste Target", io::Curve::from_text(&text)),
+                    ("*", |c| self.target = c)
+                );
+            }
+        }
         ui.horizontal_wrapped(|ui| {
             if ui.button("🗐 Copy CSV").clicked() {
                 let text = match &self.target {
-                    io::Curve::P(t) => csv::to_string(t).unwrap(),
-                    io::Curve::M(t) => csv::to_string(t).unwrap(),
-                    io::Curve::S(t) => csv::to_string(t).unwrap(),
+                    io::Curve::P(t) => csv::to_string(t, None).unwrap(),
+                    io::Curve::M(t) => csv::to_string(t, None).unwrap(),
+                    io::Curve::S(t) => csv::to_string(t, None).unwrap(),
                 };
                 ui.output_mut(|s| s.copied_text = text);
             }
@@ -228,17 +432,36 @@ impl Synthesis {
                 ui.output_mut(|s| s.copied_text = text);
             }
         });
+        ui.horizontal(|ui| {
+            if ui.button("〰 Smooth target").clicked() {
+                let closed = !self.target.resolve_mode(self.cfg.mode).is_target_open();
+                let win = self.smooth_win.0;
+                match &mut self.target {
+                    io::Curve::P(t) => *t = four_bar::curve::smooth(t, win, closed),
+                    io::Curve::M(t) => {
+                        let (c, v): (Vec<_>, Vec<_>) = t.iter().copied().unzip();
+                        let c = four_bar::curve::smooth(&c, win, closed);
+                        let v = four_bar::curve::smooth(&v, win, closed);
+                        *t = zip(c, v).collect();
+                        io::alert!("Smooth target", io::normalize_uvec(t));
+                    }
+                    io::Curve::S(t) => *t = four_bar::curve::smooth(t, win, closed),
+                }
+            }
+            ui.add(
+                DragValue::new(&mut self.smooth_win.0)
+                    .prefix("Window: ")
+                    .range(3..=usize::MAX),
+            );
+            hint(ui, "Moving average of the noisy target points");
+        });
         match &mut self.target {
             io::Curve::P(t) => table(ui, t),
             io::Curve::M(t) => {
                 // Safety: Same memory layout of `([f64; 2], [f64; 2])` and `[f64; 4]`
                 table(ui, unsafe { &mut *(t as *mut _ as *mut Vec<[f64; 4]>) });
                 if ui.button("Normalize vectors").clicked() {
-                    for (_, v) in t {
-                        let norm = v[0].hypot(v[1]);
-                        v[0] /= norm;
-                        v[1] /= norm;
-                    }
+                    io::alert!("Normalize vectors", io::normalize_uvec(t));
                 }
             }
             io::Curve::S(t) => table(ui, t),
@@ -246,11 +469,16 @@ impl Synthesis {
         ui.separator();
         ui.heading("Optimization");
         toggle_btn(ui, &mut self.conv_open, "📉 Convergence Plot");
-        self.tasks.retain(|task| {
+        let mut i = 0;
+        self.tasks.retain_mut(|task| {
             ui.horizontal(|ui| {
+                let enabled = task.result.is_some();
+                ui.add_enabled(enabled, Checkbox::without_text(&mut task.selected))
+                    .on_hover_text(format!("Task {i}"));
+                i += 1;
                 let keep = !small_btn(ui, "✖", "Delete");
                 if small_btn(ui, "💾", "Save history plot") {
-                    io::save_history_ask(&task.conv, "history.svg");
+                    io::save_history_ask(&task.conv, &task.best(), "history.svg");
                 }
                 ui.label(format!("{:.4?}", task.time));
                 ui.colored_label(Color32::GREEN, "Finished");
@@ -267,15 +495,24 @@ impl Synthesis {
                 ui.label(format!("{:.4?}", task.lock().unwrap().time));
                 ui.add(ProgressBar::new(pg_get(pg)).show_percentage().animate(true));
             });
-            // Export the finished task to history
-            if Arc::strong_count(task) == 1 {
-                let task = self.task_queue.swap_remove(i).task;
-                self.tasks
-                    .push(Arc::into_inner(task).unwrap().into_inner().unwrap());
-            }
         }
-        #[cfg(target_arch = "wasm32")]
-        ui.colored_label(Color32::RED, "Web version freezes UI when solving starts!");
+        self.collect_tasks();
+        ui.horizontal(|ui| {
+            let enabled = self.tasks.iter().any(|task| task.selected);
+            if ui
+                .add_enabled(enabled, Button::new("🗠 Overlay selected"))
+                .clicked()
+            {
+                self.overlay_selected();
+            }
+            if !self.overlay.is_empty() && ui.button("✖ Clear overlay").clicked() {
+                self.overlay.clear();
+            }
+            hint(
+                ui,
+                "Compare the coupler curves of the finished tasks on the canvas",
+            );
+        });
         ui.horizontal(|ui| {
             let enabled = !self.target.is_empty();
             if ui.add_enabled(enabled, Button::new("▶ Start")).clicked() {
@@ -287,6 +524,38 @@ impl Synthesis {
         self.convergence_plot(ui);
     }
 
+    /// Move the finished tasks from the queue to the history.
+    ///
+    /// The unfinished tasks (e.g. the solver is panicked) are discarded.
+    pub(crate) fn collect_tasks(&mut self) {
+        for i in (0..self.task_queue.len()).rev() {
+            if Arc::strong_count(&self.task_queue[i].task) > 1 {
+                continue;
+            }
+            let task = self.task_queue.swap_remove(i).task;
+            match Arc::into_inner(task).unwrap().into_inner() {
+                Ok(task) if task.state == TaskState::Finished => self.tasks.push(task),
+                _ => (),
+            }
+        }
+    }
+
+    fn overlay_selected(&mut self) {
+        use four_bar::mech::CurveGen as _;
+        let res = self.cfg.res;
+        self.overlay = zip(0.., &self.tasks)
+            .filter(|(_, task)| task.selected)
+            .filter_map(|(i, task)| {
+                let curve = match task.result.as_ref()? {
+                    io::Fb::P(fb) => fb.curve(res),
+                    io::Fb::M(fb) => fb.curve(res),
+                    io::Fb::S(fb) => fb.curve(res).into_iter().map(|[x, y, _]| [x, y]).collect(),
+                };
+                Some((format!("Task {i}"), curve))
+            })
+            .collect();
+    }
+
     fn opt_setting(&mut self, ui: &mut Ui) {
         ui.horizontal_wrapped(|ui| {
             for &(name, abbr, f) in syn_cmd::SynAlg::LIST {
@@ -301,6 +570,21 @@ impl Synthesis {
             ui.hyperlink_to(m.name(), m.link())
                 .on_hover_text(format!("More about {}", m.name()));
         });
+        let presets = m.presets().collect::<Vec<_>>();
+        if !presets.is_empty() {
+            let curr = (presets.iter().find(|(_, s)| s == m)).map_or("Custom", |(name, _)| *name);
+            ComboBox::from_label("Presets")
+                .selected_text(curr)
+                .show_ui(ui, |ui| {
+                    for (name, s) in presets {
+                        if ui.selectable_label(curr == *name, *name).clicked() {
+                            *m = s.clone();
+                        }
+                    }
+                    // Keep the current values, which are always editable
+                    let _ = ui.selectable_label(curr == "Custom", "Custom");
+                });
+        }
         macro_rules! param {
             ($s:ident, $($name:ident),+) => {{$(
                 percent(ui, concat![stringify!($name), ": "], &mut $s.$name);
@@ -328,7 +612,11 @@ impl Synthesis {
         ui.horizontal(|ui| {
             if ui.button("🖴 Load").clicked() {
                 let queue = self.queue.clone();
-                io::open_atlas(move |atlas| match &mut *queue.lock() {
+                let cfg = atlas::Cfg::new()
+                    .res(self.cfg.res)
+                    .harmonic(self.atlas_cfg.harmonic)
+                    .is_open(self.atlas_cfg.is_open);
+                io::open_atlas(cfg, move |atlas| match &mut *queue.lock() {
                     Cache::Atlas(pool) => pool.merge_atlas_inplace(atlas),
                     q => *q = Cache::Atlas(Box::new(atlas.into())),
                 });
@@ -343,20 +631,49 @@ impl Synthesis {
         if !self.atlas_vis_open {
             self.atlas_vis.clear();
         }
+        ui.horizontal(|ui| {
+            nonzero_i(ui, "Candidates: ", &mut self.cfg.atlas_candidates, 1);
+            hint(
+                ui,
+                "Number of the best atlas matches to be shown as projects",
+            );
+        });
+        ui.horizontal(|ui| {
+            ui.label("Metric");
+            let metric = &mut self.cfg.atlas_metric;
+            combo_enum(ui, "atlas_metric", metric, atlas::EfdMetric::LIST, |e| {
+                e.name()
+            });
+            hint(
+                ui,
+                "Manhattan works best for closed curves, cosine is more tolerant for open curves",
+            );
+        });
         ui.separator();
         ui.horizontal(|ui| {
             nonzero_i(ui, "Size: ", &mut self.atlas_cfg.size, 1);
             nonzero_i(ui, "Harmonic: ", &mut self.atlas_cfg.harmonic, 1);
             ui.checkbox(&mut self.atlas_cfg.is_open, "Is open curve");
         });
+        check_on(ui, "Link range", &mut self.atlas_cfg.link_range, |ui, r| {
+            let LinkRange { min, max } = r;
+            nonzero_f(ui, "min: ", min, 0.01) | nonzero_f(ui, "max: ", max, 0.01)
+        })
+        .on_hover_text("Sample the normalized link lengths in this range");
+        if let Some(r) = &mut self.atlas_cfg.link_range {
+            r.max = r.max.max(r.min + 1e-2);
+        }
         macro_rules! impl_make_cb {
-            ($atlas:ident, $atlas_ty:ident) => {
+            ($atlas:ident, $atlas_ty:ident, $norm:ident, $n:literal) => {
                 let size = self.atlas_cfg.size;
-                let cfg = atlas::Cfg::new()
+                let mut cfg = atlas::Cfg::new()
                     .res(self.cfg.res)
                     .size(size)
                     .harmonic(self.atlas_cfg.harmonic)
-                    .is_open(self.atlas_cfg.is_open);
+                    .is_open(self.atlas_cfg.is_open)
+                    .seed(self.global_seed.into());
+                cfg.range = (self.atlas_cfg.link_range)
+                    .map(|r| r.apply(<four_bar::$norm as mech::FromVectorized<$n>>::BOUND));
                 let queue = self.queue.clone();
                 let pg = Arc::new(AtomicU32::new(0f32.to_bits()));
                 self.atlas_pg = Some(pg.clone());
@@ -395,10 +712,20 @@ impl Synthesis {
             ui.label("Generate");
             let enabled = self.atlas_pg.is_none();
             if ui.add_enabled(enabled, Button::new("✚")).clicked() {
-                impl_make_cb!(FbAtlas, P);
+                impl_make_cb!(FbAtlas, P, NormFourBar, 5);
             }
             if ui.add_enabled(enabled, Button::new("✚")).clicked() {
-                impl_make_cb!(SFbAtlas, S);
+                impl_make_cb!(SFbAtlas, S, SNormFourBar, 6);
+            }
+            ui.end_row();
+            ui.label("Recompute")
+                .on_hover_text("Recompute the EFD at the harmonic above without resampling");
+            let (harmonic, res) = (self.atlas_cfg.harmonic, self.cfg.res);
+            if ui.button("🔄").clicked() {
+                self.atlas.as_fb_mut().recompute_efd(harmonic, res);
+            }
+            if ui.button("🔄").clicked() {
+                self.atlas.as_sfb_mut().recompute_efd(harmonic, res);
             }
             ui.end_row();
             ui.label("Clear");
@@ -422,12 +749,37 @@ impl Synthesis {
         Window::new("📉 Convergence Plot")
             .open(&mut self.conv_open)
             .show(ui.ctx(), |ui| {
-                static_plot("plot_conv").show(ui, |ui| {
+                ui.checkbox(&mut self.conv_log, "Log scale")
+                    .on_hover_text("Plot the natural logarithm of the best fitness");
+                let log = self.conv_log;
+                let y_label = if log {
+                    "ln(best fitness)"
+                } else {
+                    "best fitness"
+                };
+                let plot = static_plot("plot_conv").x_axis_label("generation");
+                plot.y_axis_label(y_label).show(ui, |ui| {
+                    let series = |conv: &[f64]| {
+                        if log {
+                            // Non-positive values are not defined in log scale
+                            (conv.iter().enumerate())
+                                .filter(|(_, f)| **f > 0.)
+                                .map(|(i, f)| [i as f64, f.ln()])
+                                .collect::<Vec<_>>()
+                        } else {
+                            (conv.iter().enumerate())
+                                .map(|(i, f)| [i as f64, *f])
+                                .collect()
+                        }
+                    };
                     let mut draw = |name: &str, task: &Task| {
-                        let pts1 = egui_plot::PlotPoints::from_ys_f64(&task.conv);
-                        let pts2 = egui_plot::PlotPoints::from_ys_f64(&task.conv);
-                        ui.line(egui_plot::Line::new(pts1).fill(-1.5).name(name));
-                        ui.points(egui_plot::Points::new(pts2).name(name).stems(0.));
+                        let pts = series(&task.conv);
+                        ui.line(egui_plot::Line::new(pts.clone()).fill(-1.5).name(name));
+                        ui.points(egui_plot::Points::new(pts).name(name).stems(0.));
+                        let best = egui_plot::Line::new(series(&task.best()))
+                            .style(egui_plot::LineStyle::dashed_dense())
+                            .name(format!("{name} (best so far)"));
+                        ui.line(best);
                     };
                     for (i, task) in self.tasks.iter().enumerate() {
                         draw(&format!("Task {i}"), task);
@@ -479,6 +831,16 @@ impl Synthesis {
             }
             io::Curve::S(t) => draw_curve(t.iter().map(|&[x, y, _]| [x, y]).collect()),
         }
+        // Overlay the results above the target curve
+        for (i, (name, curve)) in self.overlay.iter().enumerate() {
+            use four_bar::plot::{Color as _, Palette as _, Palette99};
+            let (r, g, b) = Palette99::pick(i).to_rgba().rgb();
+            let line = egui_plot::Line::new(curve.clone())
+                .name(name)
+                .color(Color32::from_rgb(r, g, b))
+                .width(3.);
+            ui.line(line);
+        }
     }
 
     fn on_click_canvas(&mut self, ui: &mut egui_plot::PlotUi, lnk: &Linkages) {
@@ -577,20 +939,28 @@ impl Synthesis {
         use instant::Instant;
         #[cfg(not(target_arch = "wasm32"))]
         use std::time::Instant;
+        let target = syn_target(self.target.clone(), Some(&self.atlas));
+        if let Err(e) = self.cfg.check(&target) {
+            io::alert!("Start Synthesis", Err::<(), _>(e));
+            return;
+        }
         let task = Task {
             time: std::time::Duration::from_secs(0),
             conv: Vec::new(),
+            best: Vec::new(),
+            result: None,
+            state: TaskState::Running,
+            selected: false,
         };
         let task = TaskInProg::new(task);
         self.task_queue.push(task.clone());
         let alg = self.alg.clone();
-        let cfg = self.cfg.clone();
-        let target = match self.target.clone() {
-            io::Curve::P(t) => Target::fb(t.into(), None, Some(self.atlas.as_fb())),
-            io::Curve::M(t) => Target::mfb(t.into(), None),
-            io::Curve::S(t) => Target::sfb(t.into(), None, Some(self.atlas.as_sfb())),
-        };
+        let mut cfg = self.cfg.clone();
+        if self.global_seed.is_some() {
+            cfg.seed = self.global_seed;
+        }
         let queue = lnk.projs.queue();
+        let result = task.task.clone();
         let stop = {
             let pg = task.pg.clone();
             let finish = 1f32.to_bits();
@@ -598,18 +968,120 @@ impl Synthesis {
         };
         let total_gen = cfg.gen;
         let t0 = Instant::now();
-        let s = syn_cmd::Solver::new(alg, target, cfg, stop, move |best_f, gen| {
-            pg_set(&task.pg, gen as f32 / total_gen as f32);
-            let mut task = task.task.lock().unwrap();
-            task.conv.push(best_f);
-            task.time = t0.elapsed();
-        });
-        let f = move || queue.push(None, s.solve());
+        // The callback of the solver starts from the generation `offset`
+        let callback = move |offset: u64| {
+            let task = task.clone();
+            move |best_f, gen: u64| {
+                // Skip the last generation of the previous chunk
+                if offset > 0 && gen == 0 {
+                    return;
+                }
+                let gen = offset + gen;
+                pg_set(&task.pg, gen as f32 / total_gen as f32);
+                let mut task = task.task.lock().unwrap();
+                task.push(best_f);
+                task.time = t0.elapsed();
+            }
+        };
+        #[cfg(target_arch = "wasm32")]
+        let (cfg, chunk) = {
+            // The configuration of the chunk starts from the generation `start`
+            let chunk = move |start: u64| syn_cmd::SynCfg {
+                gen: (total_gen - start).min(syn_cmd::CHUNK_GEN),
+                seed: cfg.seed.map(|seed| seed.wrapping_add(start)),
+                ..cfg.clone()
+            };
+            (chunk(0), chunk)
+        };
+        #[cfg(target_arch = "wasm32")]
+        let (alg_c, stop_c, target_c) = (alg.clone(), stop.clone(), self.target.clone());
+        // The stagnation is checked over the chunks by the fitness history
+        #[cfg(target_arch = "wasm32")]
+        let stagnant = {
+            let (result, k) = (result.clone(), cfg.stagnation);
+            move || {
+                let mut stag = syn_cmd::Stagnation::new(k);
+                let task = result.lock().unwrap();
+                (task.conv.iter().enumerate()).any(|(gen, f)| stag.update(*f, gen as u64))
+            }
+        };
+        let s = syn_cmd::Solver::new(alg, target, cfg, stop, callback(0));
+        if self.cfg.atlas_candidates > 1 {
+            for fb in s.atlas_candidates() {
+                queue.push(None, fb);
+            }
+        }
         #[cfg(not(target_arch = "wasm32"))]
-        mh::rayon::spawn(f);
+        mh::rayon::spawn(move || {
+            let fb = s.solve();
+            let mut task = result.lock().unwrap();
+            task.result = Some(fb.clone());
+            task.state = TaskState::Finished;
+            drop(task);
+            queue.push(None, fb);
+        });
+        // Solve the generations chunk by chunk, the later chunks are resumed
+        // from the population of the previous chunk
         #[cfg(target_arch = "wasm32")]
-        f(); // Block
+        wasm_bindgen_futures::spawn_local(async move {
+            let mut s = s;
+            let mut start = 0;
+            let fb = loop {
+                let pool = s.pool();
+                let fb = s.solve();
+                start += syn_cmd::CHUNK_GEN;
+                if start >= total_gen || stop_c() || stagnant() {
+                    break fb;
+                }
+                next_frame().await;
+                let target = syn_target(target_c.clone(), None);
+                let (alg, stop) = (alg_c.clone(), stop_c.clone());
+                s = syn_cmd::Solver::new(alg, target, chunk(start), stop, callback(start))
+                    .resume(&pool);
+            };
+            let mut task = result.lock().unwrap();
+            task.result = Some(fb.clone());
+            task.state = TaskState::Finished;
+            drop(task);
+            queue.push(None, fb);
+        });
+    }
+}
+
+// Create the synthesis target, the atlas is used to initialize the population
+fn syn_target(curve: io::Curve, atlas: Option<&io::AtlasPool>) -> Target<'static, '_> {
+    match curve {
+        io::Curve::P(t) => Target::fb(t.into(), None, atlas.map(|a| a.as_fb())),
+        io::Curve::M(t) => Target::mfb(t.into(), None),
+        io::Curve::S(t) => Target::sfb(t.into(), None, atlas.map(|a| a.as_sfb())),
+    }
+}
+
+// Return the control to the event loop until the next frame of the browser
+#[cfg(target_arch = "wasm32")]
+async fn next_frame() {
+    use wasm_bindgen::prelude::wasm_bindgen;
+    use wasm_bindgen_futures::{js_sys, JsFuture};
+
+    #[wasm_bindgen]
+    extern "C" {
+        #[wasm_bindgen(js_name = requestAnimationFrame)]
+        fn request_animation_frame(f: &js_sys::Function) -> i32;
     }
+    let frame = js_sys::Promise::new(&mut |resolve, _| {
+        request_animation_frame(&resolve);
+    });
+    _ = JsFuture::from(frame).await;
+}
+
+// Drop the unfinished tasks, the tasks without a state are finished
+fn finished_tasks<'de, D>(d: D) -> Result<Vec<Task>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let mut tasks = Vec::<Task>::deserialize(d)?;
+    tasks.retain(|task| task.state == TaskState::Finished);
+    Ok(tasks)
 }
 
 fn pg_get(pg: &AtomicU32) -> f32 {
@@ -619,3 +1091,67 @@ fn pg_get(pg: &AtomicU32) -> f32 {
 fn pg_set(pg: &AtomicU32, v: f32) {
     pg.store(v.to_bits(), Relaxed);
 }
+
+#[test]
+fn queued_tasks_not_persisted() {
+    let task = Task {
+        time: std::time::Duration::from_secs(1),
+        conv: vec![1., 0.5],
+        best: vec![1., 0.5],
+        result: None,
+        state: TaskState::Running,
+        selected: false,
+    };
+    let finished = Task {
+        result: Some(io::Fb::P(four_bar::FourBar::example())),
+        state: TaskState::Finished,
+        ..task.clone()
+    };
+    let mut syn = Synthesis::default();
+    // Still running, a reference is held by the solver
+    let running = TaskInProg::new(task.clone());
+    syn.task_queue.push(running.clone());
+    syn.task_queue.push(TaskInProg::new(finished));
+    // Panicked solver without a result
+    syn.task_queue.push(TaskInProg::new(task));
+    syn.collect_tasks();
+    assert_eq!(syn.task_queue.len(), 1);
+    assert_eq!(syn.tasks.len(), 1);
+    let syn = ron::from_str::<Synthesis>(&ron::to_string(&syn).unwrap()).unwrap();
+    assert!(syn.task_queue.is_empty());
+    assert_eq!(syn.tasks.len(), 1);
+    assert!(syn.tasks[0].result.is_some());
+    drop(running);
+}
+
+#[test]
+fn legacy_tasks_kept() {
+    // The older saves have neither the result nor the state
+    let legacy = "(time: (secs: 3, nanos: 0), conv: [1.0, 0.5])";
+    let running = "(time: (secs: 1, nanos: 0), conv: [1.0], state: Running)";
+    let syn =
+        ron::from_str::<Synthesis>(&format!("(tasks: [{legacy}, {running}, {legacy}])")).unwrap();
+    assert_eq!(syn.tasks.len(), 2);
+    assert!(syn.tasks.iter().all(|task| task.result.is_none()));
+    assert_eq!(*syn.tasks[0].best(), [1., 0.5]);
+}
+
+#[test]
+fn best_so_far_non_increasing() {
+    let mut task = Task {
+        time: std::time::Duration::from_secs(0),
+        conv: Vec::new(),
+        best: Vec::new(),
+        result: None,
+        state: TaskState::Finished,
+        selected: false,
+    };
+    for f in [3., 1., 2., 0.5, 0.7, 0.5] {
+        task.push(f);
+    }
+    assert_eq!(task.best, [3., 1., 1., 0.5, 0.5, 0.5]);
+    assert!(task.best.windows(2).all(|w| w[1] <= w[0]));
+    // Older saves without the cumulative series
+    let old = Task { best: Vec::new(), ..task.clone() };
+    assert_eq!(old.best(), task.best);
+}
@@ -2,7 +2,10 @@ use crate::app::{App, APP_NAME, VERSION};
 use std::path::PathBuf;
 
 mod atlas;
+mod bench;
+mod diff;
 mod syn;
+mod tournament;
 
 #[derive(clap::Parser)]
 #[clap(name = APP_NAME, version = VERSION, author, about)]
@@ -24,6 +27,15 @@ enum Cmd {
     Syn(syn::Syn),
     /// Generate atlas database without GUI
     Atlas(atlas::AtlasCfg),
+    /// Print the information of an atlas database
+    AtlasInfo(atlas::AtlasInfoCfg),
+    /// Time the key operations on fixed inputs
+    Bench(bench::BenchCfg),
+    /// Compare the linkages and the logs of two synthesis result folders
+    Diff(diff::DiffCfg),
+    /// Run all the synthesis algorithms on a target with the same seed and
+    /// rank their results
+    SynTournament(tournament::TournamentCfg),
 }
 
 impl Entry {
@@ -40,6 +52,22 @@ impl Entry {
                 register_panic_hook();
                 atlas::atlas(atlas);
             }
+            Some(Cmd::AtlasInfo(cfg)) => {
+                register_panic_hook();
+                atlas::atlas_info(cfg);
+            }
+            Some(Cmd::Bench(bench)) => {
+                register_panic_hook();
+                bench::bench(bench);
+            }
+            Some(Cmd::Diff(diff)) => {
+                register_panic_hook();
+                diff::diff(diff);
+            }
+            Some(Cmd::SynTournament(cfg)) => {
+                register_panic_hook();
+                tournament::tournament(cfg);
+            }
         }
     }
 }
@@ -4,8 +4,10 @@ use four_bar::atlas;
 pub(super) struct AtlasCfg {
     /// Output path of the atlas (in NPZ format)
     file: std::path::PathBuf,
-    /// Generate for open curve
-    #[clap(long)]
+    /// Generate for open curve, default to closed curve
+    ///
+    /// The atlas is not used in the partial mode of the synthesis
+    #[clap(long, alias = "open-curve")]
     is_open: bool,
     /// Generate for spherical linkage
     #[clap(long)]
@@ -22,6 +24,9 @@ pub(super) struct AtlasCfg {
     /// Fix the seed to get a determined result, default to random
     #[clap(short, long)]
     seed: Option<u64>,
+    /// Store the sampled offset (unnormalized) linkages, planar atlas only
+    #[clap(long)]
+    with_offset: bool,
 }
 
 pub(super) fn atlas(atlas: AtlasCfg) {
@@ -33,6 +38,7 @@ pub(super) fn atlas(atlas: AtlasCfg) {
         res,
         harmonic,
         seed,
+        with_offset,
     } = atlas;
     let ext = file.extension().and_then(std::ffi::OsStr::to_str);
     if !matches!(ext, Some("npz")) {
@@ -41,7 +47,16 @@ pub(super) fn atlas(atlas: AtlasCfg) {
     println!("Generate to: {}", file.display());
     println!("open={is_open}, size={size}, res={res}, harmonic={harmonic}");
     let t0 = std::time::Instant::now();
-    let cfg = atlas::Cfg { is_open, size, res, harmonic, seed: seed.into() };
+    let seed = seed.into();
+    let cfg = atlas::Cfg {
+        is_open,
+        size,
+        res,
+        harmonic,
+        seed,
+        with_offset,
+        range: None,
+    };
     let pb = indicatif::ProgressBar::new(size as u64);
     let callback = |n| pb.set_position(n as u64);
     let fs = || std::fs::File::create(file).unwrap();
@@ -55,3 +70,145 @@ pub(super) fn atlas(atlas: AtlasCfg) {
     println!("Time spent: {:?}", t0.elapsed());
     println!("Done");
 }
+
+#[derive(clap::Args)]
+pub(super) struct AtlasInfoCfg {
+    /// Path of the atlas (in NPZ format)
+    file: std::path::PathBuf,
+    /// Print as JSON for scripting
+    #[clap(long)]
+    json: bool,
+}
+
+// Summary of an atlas
+struct Info {
+    sphere: bool,
+    size: usize,
+    harmonic: usize,
+    // Number of the open curves
+    open: usize,
+    offset: bool,
+    // Name, minimum and maximum of each code
+    range: Vec<(&'static str, f64, f64)>,
+}
+
+impl Info {
+    // Read as the planar atlas first, then the spherical atlas
+    fn read<R>(mut r: R) -> Result<Self, four_bar::Error>
+    where
+        R: std::io::Read + std::io::Seek,
+    {
+        macro_rules! info {
+            ($atlas:ident, $sphere:literal, $names:expr) => {
+                Self {
+                    sphere: $sphere,
+                    size: $atlas.len(),
+                    harmonic: $atlas.harmonic(),
+                    open: $atlas.is_open_iter().filter(|is_open| *is_open).count(),
+                    offset: $atlas.offset_data().is_some(),
+                    range: std::iter::zip($names, $atlas.fb_data().columns())
+                        .map(|(name, col)| {
+                            let min = col.fold(f64::INFINITY, |a, b| a.min(*b));
+                            let max = col.fold(f64::NEG_INFINITY, |a, b| a.max(*b));
+                            (name, min, max)
+                        })
+                        .collect(),
+                }
+            };
+        }
+        if let Ok(atlas) = atlas::FbAtlas::read(&mut r) {
+            return Ok(info!(atlas, false, ["l1", "l3", "l4", "l5", "g"]));
+        }
+        let atlas = atlas::SFbAtlas::read(r)?;
+        Ok(info!(atlas, true, ["l1", "l2", "l3", "l4", "l5", "g"]))
+    }
+
+    // The non-finite ranges of the empty atlas are written as `null`
+    fn to_json(&self) -> String {
+        let Self { sphere, size, harmonic, open, offset, range } = self;
+        let range = range
+            .iter()
+            .map(|(name, min, max)| {
+                (
+                    name.to_string(),
+                    serde_json::json!({"min": min, "max": max}),
+                )
+            })
+            .collect::<serde_json::Map<_, _>>();
+        serde_json::json!({
+            "sphere": sphere,
+            "size": size,
+            "harmonic": harmonic,
+            "open": open,
+            "closed": size - open,
+            "offset": offset,
+            "range": range,
+        })
+        .to_string()
+    }
+
+    fn print_table(&self) {
+        let Self { sphere, size, harmonic, open, offset, range } = self;
+        println!("type={}", if *sphere { "spherical" } else { "planar" });
+        println!("size={size}, harmonic={harmonic}, offset={offset}");
+        println!("open={open}, closed={}", size - open);
+        println!("{:<6} {:>12} {:>12}", "", "min", "max");
+        for (name, min, max) in range {
+            println!("{name:<6} {min:>12.04} {max:>12.04}");
+        }
+    }
+}
+
+pub(super) fn atlas_info(cfg: AtlasInfoCfg) {
+    let AtlasInfoCfg { file, json } = cfg;
+    let info = std::fs::File::open(&file)
+        .map_err(|e| e.to_string())
+        .and_then(|f| Info::read(f).map_err(|e| e.to_string()))
+        .unwrap_or_else(|e| {
+            eprintln!("{}: {e}", file.display());
+            std::process::exit(1);
+        });
+    if json {
+        println!("{}", info.to_json());
+    } else {
+        info.print_table();
+    }
+}
+
+#[test]
+fn atlas_info_generated() {
+    let cfg = atlas::Cfg::new()
+        .size(20)
+        .res(90)
+        .harmonic(5)
+        .seed(Some(0u64).into());
+    for sphere in [false, true] {
+        let mut buf = std::io::Cursor::new(Vec::new());
+        let res = if sphere {
+            atlas::SFbAtlas::make(cfg.clone()).write(&mut buf)
+        } else {
+            atlas::FbAtlas::make(cfg.clone()).write(&mut buf)
+        };
+        res.unwrap();
+        buf.set_position(0);
+        let info = Info::read(buf).unwrap();
+        assert_eq!(info.sphere, sphere);
+        assert_eq!((info.size, info.harmonic, info.open), (20, 5, 0));
+        assert_eq!(info.range.len(), if sphere { 6 } else { 5 });
+        assert!(info.range.iter().all(|(_, min, max)| min <= max));
+        let json = serde_json::from_str::<serde_json::Value>(&info.to_json()).unwrap();
+        assert_eq!(json["size"], 20);
+        assert_eq!(json["closed"], 20);
+    }
+    // Empty atlas
+    let info = Info {
+        sphere: false,
+        size: 0,
+        harmonic: 0,
+        open: 0,
+        offset: false,
+        range: vec![("l1", f64::INFINITY, f64::NEG_INFINITY)],
+    };
+    let json = serde_json::from_str::<serde_json::Value>(&info.to_json()).unwrap();
+    assert!(json["range"]["l1"]["min"].is_null());
+}
@@ -0,0 +1,60 @@
+use four_bar::{efd, mh, syn, FourBar};
+use std::time::{Duration, Instant};
+
+#[derive(clap::Args)]
+pub(super) struct BenchCfg {
+    /// Number of runs of the curve generation and the EFD computation
+    #[clap(short, long, default_value_t = 1000)]
+    iter: usize,
+    /// Number of runs of the synthesis
+    #[clap(long, default_value_t = 5)]
+    syn_iter: usize,
+    /// Number of generations of each synthesis run
+    #[clap(long, default_value_t = 20)]
+    gen: u64,
+    /// Number of the points (resolution) in curve production
+    #[clap(long, default_value_t = 180)]
+    res: usize,
+}
+
+pub(super) fn bench(bench: BenchCfg) {
+    let BenchCfg { iter, syn_iter, gen, res } = bench;
+    println!("iter={iter}, syn_iter={syn_iter}, gen={gen}, res={res}");
+    // Fixed inputs
+    let fb = FourBar::example();
+    let curve = fb.curve(res);
+    report("Curve generation", iter, || {
+        std::hint::black_box(fb.curve(res));
+    });
+    report("EFD computation", iter, || {
+        std::hint::black_box(efd::Efd2::from_curve(&curve, false));
+    });
+    report("Synthesis", syn_iter, || {
+        let func = syn::FbSyn::from_curve(&curve, syn::Mode::Closed).res(res);
+        let s = mh::Solver::build(mh::Rga::default(), func)
+            .seed(Some(0u64))
+            .task(|ctx| ctx.gen >= gen)
+            .solve();
+        std::hint::black_box(s);
+    });
+}
+
+fn report<F>(name: &str, n: usize, mut f: F)
+where
+    F: FnMut(),
+{
+    if n == 0 {
+        return;
+    }
+    let mut t = (0..n)
+        .map(|_| {
+            let t0 = Instant::now();
+            f();
+            t0.elapsed()
+        })
+        .collect::<Vec<_>>();
+    t.sort_unstable();
+    let mean = t.iter().sum::<Duration>() / n as u32;
+    let median = t[n / 2];
+    println!("{name}: mean={mean:?}, median={median:?}");
+}
@@ -0,0 +1,86 @@
+use four_bar::{mech::fb::Comparison, FourBar, MFourBar};
+use std::path::{Path, PathBuf};
+
+#[derive(clap::Args)]
+pub(super) struct DiffCfg {
+    /// The first result folder of the synthesis
+    dir_a: PathBuf,
+    /// The second result folder of the synthesis
+    dir_b: PathBuf,
+    /// Number of the points (resolution) in curve production
+    #[clap(long, default_value_t = 180)]
+    res: usize,
+    /// Exit with an error if any parameter difference or the curve distance
+    /// exceeds this tolerance
+    #[clap(long)]
+    tol: Option<f64>,
+}
+
+pub(super) fn diff(diff: DiffCfg) {
+    let DiffCfg { dir_a, dir_b, res, tol } = diff;
+    let load = |dir: &Path| {
+        load_fb(dir).unwrap_or_else(|e| {
+            eprintln!("{}: {e}", dir.display());
+            std::process::exit(1);
+        })
+    };
+    let (a, b) = (load(&dir_a), load(&dir_b));
+    let Comparison { diff, hausdorff, same_ty } = a.compare(&b, res);
+    let (row_a, row_b) = (a.to_csv_row(), b.to_csv_row());
+    println!("{:<12} {:>12} {:>12} {:>12}", "", "A", "B", "delta");
+    for (i, name) in Comparison::NAMES.into_iter().enumerate() {
+        let (va, vb, d) = (row_a[i], row_b[i], diff[i]);
+        println!("{name:<12} {va:>12.04} {vb:>12.04} {d:>+12.04}");
+    }
+    let (log_a, log_b) = (read_log(&dir_a), read_log(&dir_b));
+    for key in ["cost", "dist-err"] {
+        let (Some(va), Some(vb)) = (log_a.get(key), log_b.get(key)) else {
+            continue;
+        };
+        println!("{key:<12} {va:>12.04} {vb:>12.04} {:>+12.04}", vb - va);
+    }
+    println!("Hausdorff distance of the coupler curves: {hausdorff:.04}");
+    if !same_ty {
+        println!("Linkage type: {} -> {}", a.ty().name(), b.ty().name());
+    }
+    if let Some(tol) = tol {
+        let max = diff.iter().fold(hausdorff, |m, d| m.max(d.abs()));
+        if max > tol {
+            eprintln!("The difference {max:.04} exceeds the tolerance {tol}");
+            std::process::exit(1);
+        }
+    }
+}
+
+// Load the planar linkage of a result folder
+fn load_fb(dir: &Path) -> Result<FourBar, String> {
+    let s = std::fs::read_to_string(dir.join("linkage.ron")).map_err(|e| e.to_string())?;
+    if let Ok(fb) = ron::from_str::<FourBar>(&s) {
+        Ok(fb)
+    } else if let Ok(fb) = ron::from_str::<MFourBar>(&s) {
+        Ok(fb.as_fb().clone())
+    } else {
+        Err("only the planar linkages are supported".to_string())
+    }
+}
+
+// Read the values of the "[optimized]" section from the synthesis log
+fn read_log(dir: &Path) -> std::collections::HashMap<String, f64> {
+    let log = std::fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|e| e.path())
+        .find(|p| p.extension().is_some_and(|ext| ext == "log"))
+        .and_then(|p| std::fs::read_to_string(p).ok())
+        .unwrap_or_default();
+    log.lines()
+        .skip_while(|line| *line != "[optimized]")
+        .skip(1)
+        .take_while(|line| !line.starts_with('['))
+        .filter_map(|line| {
+            let (key, value) = line.split_once('=')?;
+            Some((key.to_string(), value.parse().ok()?))
+        })
+        .collect()
+}
@@ -1,8 +1,10 @@
+use self::format::PlotFormat;
 use crate::{io, syn_cmd::*};
 use four_bar::*;
-use indicatif::{ProgressBar, ProgressStyle};
+use indicatif::{ProgressBar, ProgressDrawTarget, ProgressStyle};
 use std::path::{Path, PathBuf};
 
+pub(super) mod format;
 mod logger;
 mod solver;
 
@@ -19,8 +21,10 @@ pub(crate) enum SynErr {
     Format,
     Io(std::io::Error),
     Plot(plot::DrawingAreaErrorKind<std::io::Error>),
-    Gif(image::ImageError),
-    CsvSer(csv::Error),
+    Bitmap(plot::DrawingAreaErrorKind<plot::backend::BitMapBackendError>),
+    Image(image::ImageError),
+    Lib(four_bar::Error),
+    Cfg(CfgErr),
     RonSerde(ron::error::SpannedError),
     RonIo(ron::error::Error),
     Linkage,
@@ -32,8 +36,10 @@ impl std::fmt::Display for SynErr {
             Self::Format => write!(f, "unsupported format"),
             Self::Io(e) => write!(f, "[IO] {e}"),
             Self::Plot(e) => write!(f, "[Plot] {e}"),
-            Self::Gif(e) => write!(f, "[GIF] {e}"),
-            Self::CsvSer(e) => write!(f, "[CSV] {e}"),
+            Self::Bitmap(e) => write!(f, "[Plot] {e}"),
+            Self::Image(e) => write!(f, "[Image] {e}"),
+            Self::Lib(e) => write!(f, "{e}"),
+            Self::Cfg(e) => write!(f, "{e}"),
             Self::RonSerde(e) => write!(f, "[RON-Serde] {e}"),
             Self::RonIo(e) => write!(f, "[RON-IO] {e}"),
             Self::Linkage => write!(f, "invalid linkage input"),
@@ -44,23 +50,33 @@ impl std::error::Error for SynErr {}
 impl_err_from!(
     (std::io::Error, Io),
     (plot::DrawingAreaErrorKind<std::io::Error>, Plot),
-    (image::ImageError, Gif),
-    (csv::Error, CsvSer),
+    (
+        plot::DrawingAreaErrorKind<plot::backend::BitMapBackendError>,
+        Bitmap
+    ),
+    (image::ImageError, Image),
+    (four_bar::Error, Lib),
+    (CfgErr, Cfg),
     (ron::error::SpannedError, RonSerde),
     (ron::error::Error, RonIo),
 );
-impl From<String> for SynErr {
-    fn from(e: String) -> Self {
-        Self::Io(std::io::Error::other(e))
-    }
-}
 
 #[derive(clap::Args)]
 #[clap(subcommand_precedence_over_arg = true)]
 pub(super) struct Syn {
     /// Target file paths in "[path]/[name].[mode].[ron|csv|txt]" pattern
+    ///
+    /// The mode is "closed", "open", "partial" or "auto", where the automatic
+    /// mode picks the closed or open mode by the target curve
+    ///
+    /// Use "-" to read a CSV curve from the standard input, which is titled
+    /// as "stdin.auto"
     #[clap(required = true)]
     files: Vec<PathBuf>,
+    /// Output folder of the projects, default to the folder of each target
+    /// file, or the current folder for the standard input
+    #[clap(long)]
+    output_dir: Option<PathBuf>,
     /// Force to rerun the result
     ///
     /// If the last result exists, the program will only redraw it
@@ -85,40 +101,101 @@ pub(super) struct Syn {
     /// ";" (Windows) or ":" (Unix) characters
     #[clap(long)]
     atlas: Option<std::ffi::OsString>,
-    /// Competitor (reference) folder path, under the same folder of the target
-    /// file
+    /// Competitor (reference) folder paths, under the same folder of the
+    /// target file, can be specified multiple times
     ///
-    /// The reference file should be named as "[target_name].[target_mode].ron"
+    /// The reference file should be named as "[target_name].[target_mode].ron",
+    /// and labeled as "Ref. [1]", "Ref. [2]", etc. in the given order
     #[clap(short, long, default_value = "refer")]
-    refer: PathBuf,
+    refer: Vec<PathBuf>,
     /// The legend position of the plot
     ///
     /// Defalut to upper right (ur), override when redrawing
     #[clap(long)]
     legend: Option<plot::LegendPos>,
+    /// The output format of the plots
+    ///
+    /// The file extensions are replaced by the format, override when redrawing
+    #[clap(long, value_enum, default_value_t)]
+    format: PlotFormat,
+    /// The progress output of the synthesis
+    ///
+    /// The JSON lines are printed to the standard error for the scripts,
+    /// instead of the progress bar
+    #[clap(long, value_enum, default_value_t)]
+    progress: ProgressFmt,
+    /// Draw the markers on the history plot at every N generations, zero to
+    /// disable
+    #[clap(long, default_value_t = 0)]
+    history_markers: usize,
+    /// Decimal places of the target EFD coefficients in the CSV dumps
+    #[clap(long, default_value_t = 4)]
+    precision: usize,
     /// Disable reference comparison
     #[clap(long)]
     no_ref: bool,
+    /// Force to match the targets as open curves, ignore the "[mode]" of the
+    /// file names
+    ///
+    /// The partial mode is kept, which is also an open target but matched by
+    /// the closed curves
+    // Not a part of `SynCfg`, the mode selector of the GUI is not affected
+    #[clap(long, conflicts_with = "closed_curve")]
+    open_curve: bool,
+    /// Force to match the targets as closed curves, ignore the "[mode]" of the
+    /// file names, including the partial mode
+    #[clap(long)]
+    closed_curve: bool,
     #[clap(flatten)]
     cfg: SynCfg,
     #[clap(subcommand)]
     alg: Option<SynAlg>,
 }
 
+/// Progress output format of the synthesis
+#[derive(clap::ValueEnum, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) enum ProgressFmt {
+    /// Visual progress bar
+    #[default]
+    Bar,
+    /// One JSON object per line, with the title, generation and percentage
+    Json,
+}
+
 pub(crate) struct Info<'a> {
     pub(crate) pb: ProgressBar,
+    pub(crate) progress: ProgressFmt,
     pub(crate) root: PathBuf,
     pub(crate) title: String,
     pub(crate) mode: syn::Mode,
-    pub(crate) refer: Option<&'a Path>,
+    pub(crate) refer: &'a [PathBuf],
     pub(crate) legend: Option<plot::LegendPos>,
+    pub(crate) format: PlotFormat,
+    pub(crate) precision: usize,
+    pub(crate) markers: usize,
     pub(crate) rerun: bool,
     pub(crate) video: bool,
 }
 
+// The path of the standard input
+const STDIN: &str = "-";
+
+// Read the CSV curve from a file, or from the standard input
+pub(super) fn read_csv_curve(file: &Path) -> Result<io::Curve, SynErr> {
+    if file == Path::new(STDIN) {
+        // The standard input is not seekable, buffer it first
+        let mut buf = Vec::new();
+        std::io::Read::read_to_end(&mut std::io::stdin(), &mut buf)?;
+        Ok(io::Curve::from_csv_reader(std::io::Cursor::new(buf))?)
+    } else {
+        Ok(io::Curve::from_csv_reader(std::fs::File::open(file)?)?)
+    }
+}
+
 pub(super) fn loader(syn: Syn) {
     let Syn {
         files,
+        output_dir,
         each,
         cfg,
         mut atlas,
@@ -129,6 +206,12 @@ pub(super) fn loader(syn: Syn) {
         clean,
         video,
         legend,
+        format,
+        progress,
+        history_markers,
+        precision,
+        open_curve,
+        closed_curve,
     } = syn;
     println!("=====");
     if let Some(seed) = cfg.seed {
@@ -139,10 +222,16 @@ pub(super) fn loader(syn: Syn) {
     if !rerun {
         atlas = None;
     }
-    println!("rerun={rerun} clean={clean} dd={}", cfg.use_dd);
+    println!(
+        "rerun={rerun} clean={clean} dd={} point={}",
+        cfg.use_dd, cfg.use_point
+    );
+    if open_curve || closed_curve {
+        println!("open_curve={open_curve} closed_curve={closed_curve}");
+    }
     println!("-----");
-    // Reference folder path
-    let refer = (!no_ref).then_some(refer.as_path());
+    // Reference folder paths
+    let refer = if no_ref { &[][..] } else { refer.as_slice() };
     // Load atlas
     let atlas = atlas
         .map(|atlas| std::env::split_paths(&atlas).collect::<Vec<_>>())
@@ -164,15 +253,32 @@ pub(super) fn loader(syn: Syn) {
     const STYLE: &str = "{eta} {wide_bar} {percent}%";
     let pb = ProgressBar::new(0);
     pb.set_style(ProgressStyle::with_template(STYLE).unwrap());
+    if progress == ProgressFmt::Json {
+        pb.set_draw_target(ProgressDrawTarget::hidden());
+    }
     // Load target files & create project folders
     let tasks = files
         .into_iter()
-        .filter_map(|file| file.canonicalize().ok().filter(|f| f.is_file()))
         .filter_map(|file| {
-            let title = file.file_stem().and_then(|p| p.to_str())?;
+            if file == Path::new(STDIN) {
+                return Some(file);
+            }
+            file.canonicalize().ok().filter(|f| f.is_file())
+        })
+        .filter_map(|file| {
+            let is_stdin = file == Path::new(STDIN);
+            let title = if is_stdin {
+                "stdin.auto"
+            } else {
+                file.file_stem().and_then(|p| p.to_str())?
+            };
             // FIXME: Try block
             let info_ret = (|| {
-                let ext = file.extension().and_then(|p| p.to_str());
+                let ext = if is_stdin {
+                    Some("csv")
+                } else {
+                    file.extension().and_then(|p| p.to_str())
+                };
                 macro_rules! check {
                     ($c:expr) => {
                         efd::util::valid_curve($c).ok_or(SynErr::Linkage)?.into()
@@ -192,11 +298,15 @@ pub(super) fn loader(syn: Syn) {
                 }
                 let target = match ext.ok_or(SynErr::Format)? {
                     "csv" | "txt" => {
-                        match io::Curve::from_csv_reader(std::fs::File::open(&file)?)? {
+                        match read_csv_curve(&file)? {
                             io::Curve::P(t) => {
                                 Target::fb(check!(t), None, atlas_ref.map(|a| a.as_fb()))
                             }
-                            io::Curve::M(t) => Target::mfb(check!(@t), None),
+                            io::Curve::M(mut t) => {
+                                // Poses may not be given in unit vectors
+                                io::normalize_uvec(&mut t)?;
+                                Target::mfb(check!(@t), None)
+                            }
                             io::Curve::S(t) => {
                                 Target::sfb(check!(t), None, atlas_ref.map(|a| a.as_sfb()))
                             }
@@ -212,14 +322,27 @@ pub(super) fn loader(syn: Syn) {
                         Err(SynErr::Format)?
                     }
                 };
+                cfg.check(&target)?;
                 let mode = match Path::new(title).extension().and_then(|p| p.to_str()) {
                     Some("closed") => syn::Mode::Closed,
                     Some("partial") => syn::Mode::Partial,
                     Some("open") => syn::Mode::Open,
+                    Some("auto") => target.resolve_mode(syn::Mode::Auto),
                     _ => Err(SynErr::Format)?,
                 };
-                let parent = file.parent().unwrap();
-                let root = if cfg.use_dd {
+                let mode = match mode {
+                    _ if closed_curve => syn::Mode::Closed,
+                    syn::Mode::Closed if open_curve => syn::Mode::Open,
+                    mode => mode,
+                };
+                let parent = match &output_dir {
+                    Some(dir) => dir.clone(),
+                    None if is_stdin => std::env::current_dir()?,
+                    None => file.parent().unwrap().to_path_buf(),
+                };
+                let root = if cfg.use_point {
+                    parent.join(format!("{title}.point"))
+                } else if cfg.use_dd {
                     parent.join(format!("{title}.dd"))
                 } else {
                     parent.join(title)
@@ -245,7 +368,20 @@ pub(super) fn loader(syn: Syn) {
                 }
                 let title = title.to_string();
                 let pb = pb.clone();
-                let info = Info { root, title, mode, refer, legend, rerun, video, pb };
+                let info = Info {
+                    root,
+                    title,
+                    mode,
+                    refer,
+                    legend,
+                    format,
+                    precision,
+                    markers: history_markers,
+                    rerun,
+                    video,
+                    pb,
+                    progress,
+                };
                 Ok((info, target))
             })();
             match info_ret {
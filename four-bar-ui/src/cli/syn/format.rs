@@ -0,0 +1,36 @@
+/// Output format of the plots
+#[derive(clap::ValueEnum, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) enum PlotFormat {
+    /// Scalable vector graphics
+    #[default]
+    Svg,
+    /// Raster image
+    Png,
+}
+
+impl PlotFormat {
+    pub(crate) const fn ext(&self) -> &'static str {
+        match self {
+            Self::Svg => "svg",
+            Self::Png => "png",
+        }
+    }
+}
+
+// Save the plot with the backend of the format, the extension is replaced
+macro_rules! save_plot {
+    ($format:expr, $path:expr, $size:expr, | $b:ident | $plot:expr) => {{
+        let path = $path.with_extension($format.ext());
+        match $format {
+            $crate::cli::syn::format::PlotFormat::Svg => {
+                let $b = four_bar::plot::SVGBackend::new(&path, $size);
+                $plot?;
+            }
+            $crate::cli::syn::format::PlotFormat::Png => {
+                let $b = four_bar::plot::BitMapBackend::new(&path, $size);
+                $plot?;
+            }
+        }
+    }};
+}
+pub(crate) use save_plot;
@@ -37,7 +37,7 @@ impl<'a, W: Write> Logger<'a, W> {
         writeln!(self.writer, "[{title}]")
     }
 
-    pub(crate) fn title(&mut self, title: &'static str) -> Result<(), std::io::Error> {
+    pub(crate) fn title(&mut self, title: &str) -> Result<(), std::io::Error> {
         writeln!(self.writer, "\n[{title}]")
     }
 
@@ -1,9 +1,9 @@
-use super::*;
+use super::{format::save_plot, *};
 use crate::app::GIF_RES;
 use four_bar::plot::Style;
 use plot::{full_palette::*, RGBColor};
 use std::{
-    path::Path,
+    path::{Path, PathBuf},
     sync::{Arc, Mutex},
 };
 
@@ -22,7 +22,8 @@ const CURVE_FIG: &str = "curve.fig.ron";
 const TARGET_COLOR: RGBColor = RED;
 const SYN_COLOR: RGBColor = BLUE_900;
 const ATLAS_COLOR: RGBColor = GREEN_900;
-const REF_COLOR: RGBColor = ORANGE_900;
+const REF_COLORS: [RGBColor; 4] = [ORANGE_900, PURPLE_900, CYAN_900, BROWN_900];
+const REF_STYLES: [Style; 3] = [Style::DashedLine, Style::DashDottedLine, Style::DottedLine];
 
 macro_rules! gif_video {
     ($info:ident, $root:ident, $fig:ident) => {
@@ -54,8 +55,7 @@ where
         const BUF_SIZE: usize = (SIZE * SIZE) as usize * 3;
         let mut frame = vec![0; BUF_SIZE];
         let b = plot::BitMapBackend::with_buffer(&mut frame, (SIZE, SIZE));
-        fig.plot_video(b, curr, GIF_RES)
-            .map_err(|e| format!("{e}"))?;
+        fig.plot_video(b, curr, GIF_RES)?;
         let image = RgbImage::from_vec(SIZE, SIZE, frame).unwrap_or_else(|| unreachable!());
         w.encode_frame(Frame::new(DynamicImage::from(image).into_rgba8()))?;
         pb.inc(1);
@@ -65,122 +65,60 @@ where
     Ok(())
 }
 
-impl<M, const N: usize, const D: usize> PSynData<'_, M::De, syn::PathSyn<M, N, D>, D>
+// Objective-dependent outputs of the path synthesis
+trait PathOutput<const D: usize> {
+    // Harmonic number of the target
+    fn harmonic(&self) -> Option<usize> {
+        None
+    }
+
+    // Write the target data of the objective
+    fn write_target(&self, _root: &Path, _prec: usize) -> Result<(), SynErr> {
+        Ok(())
+    }
+
+    // Cost of a reference curve, `None` if only the distances are compared
+    fn cost(&self, curve: &[[f64; D]], mode: syn::Mode) -> Option<f64>;
+}
+
+impl<M, const N: usize, const D: usize> PathOutput<D> for syn::PathSyn<M, N, D>
 where
-    syn::PathSyn<M, N, D>: mh::ObjFunc<Ys = mh::WithProduct<f64, M::De>>,
-    M: atlas::Code<N, D>,
-    M::De: mech::CurveGen<D>
-        + serde::Serialize
-        + serde::de::DeserializeOwned
-        + Default
-        + Clone
-        + Sync
-        + Send
-        + 'static,
     efd::U<D>: efd::EfdDim<D>,
-    efd::Efd<D>: Sync,
-    for<'f1, 'f2> plot::FigureBase<'f1, 'f2, M::De, [f64; D]>: plot::Plot + serde::Serialize,
 {
-    fn solve_cli(
-        self,
-        cfg: &SynCfg,
-        info: &Info,
-        history: Arc<Mutex<Vec<f64>>>,
-    ) -> Result<(), SynErr> {
-        use four_bar::mech::CurveGen as _;
-        let Self { s, tar_curve, tar_fb, atlas_fb } = self;
-        let Info { root, title, mode, refer, .. } = info;
-        let t0 = std::time::Instant::now();
-        let s = s.solve();
-        let t1 = t0.elapsed();
-        let func = s.func();
-        let harmonic = func.harmonic();
-        let tar_efd = func.tar.clone();
-        let (cost, fb) = s.into_err_result();
-        {
-            let path = root.join(HISTORY_SVG);
-            let svg = plot::SVGBackend::new(&path, (800, 600));
-            plot::fb::history(svg, Arc::into_inner(history).unwrap().into_inner().unwrap())?;
-        }
-        let refer = refer
-            .map(|p| root.join("..").join(p).join(format!("{title}.ron")))
-            .filter(|p| p.is_file());
-        let mut log = std::fs::File::create(root.join(format!("{title}.log")))?;
-        let mut log = super::logger::Logger::new(&mut log);
-        log.top_title(title)?;
-        write_tar_efd(root.join(EFD_CSV), &tar_efd)?;
-        write_ron(root.join(LNK_RON), &fb)?;
-        let curve = fb.curve(cfg.res);
-        let mut fig = plot::FigureBase::new();
-        if let Some(legend) = info.legend {
-            fig.legend = legend;
-        }
-        fig.push_line("Target", &*tar_curve, Style::Circle, TARGET_COLOR);
-        {
-            write_ron(root.join(TAR_FIG), &fig)?;
-            let path = root.join(TAR_SVG);
-            let svg = plot::SVGBackend::new(&path, (1600, 1600));
-            fig.plot(svg)?;
-        }
-        fig.set_fb_ref(&fb);
-        fig.push_line("Optimized", &curve, Style::Line, SYN_COLOR);
-        {
-            write_ron(root.join(LNK_FIG), &fig)?;
-            let path = root.join(LNK_SVG);
-            let svg = plot::SVGBackend::new(&path, (1600, 1600));
-            fig.plot(svg)?;
-        }
-        gif_video!(info, root, fig);
-        if let Some(fb) = tar_fb {
-            log.title("target.fb")?;
-            log.log(fb)?;
-        }
-        if let Some((cost, fb)) = atlas_fb {
-            let curve = fb.curve(cfg.res);
-            log.title("atlas")?;
-            log.log(Performance::cost(cost, &tar_curve, &curve).harmonic(harmonic))?;
-            log.title("atlas.fb")?;
-            log.log(&fb)?;
-            write_ron(root.join("atlas.ron"), &fb)?;
-            fig.push_line("Atlas", curve, Style::Triangle, ATLAS_COLOR);
-        }
-        log.title("optimized")?;
-        log.log(
-            Performance::cost(cost, &tar_curve, &curve)
-                .time(t1)
-                .harmonic(harmonic),
-        )?;
-        log.title("optimized.fb")?;
-        log.log(&fb)?;
-        if let Some(refer) = refer {
-            let fb = ron::de::from_reader::<_, M::De>(std::fs::File::open(refer)?)?;
-            let c = fb.curve(cfg.res);
-            log.title("competitor")?;
-            if matches!(mode, syn::Mode::Partial) {
-                log.log(Performance::dist_err(&tar_curve, &c))?;
-            } else {
-                let efd = efd::Efd::from_curve_harmonic(&c, mode.is_result_open(), harmonic);
-                log.log(Performance::cost(efd.err(&tar_efd), &tar_curve, &c))?;
-            }
-            log.title("competitor.fb")?;
-            log.log(&fb)?;
-            fig.push_line("Ref. [?]", c, Style::DashedLine, REF_COLOR);
-        }
-        fig.remove_fb();
-        write_ron(root.join(CURVE_FIG), &fig)?;
-        let path = root.join(CURVE_SVG);
-        let svg = plot::SVGBackend::new(&path, (1600, 1600));
-        fig.plot(svg)?;
-        log.flush()?;
-        Ok(())
+    fn harmonic(&self) -> Option<usize> {
+        Some(self.harmonic())
+    }
+
+    fn write_target(&self, root: &Path, prec: usize) -> Result<(), SynErr> {
+        write_tar_efd(root.join(EFD_CSV), &self.tar, prec)
+    }
+
+    fn cost(&self, curve: &[[f64; D]], mode: syn::Mode) -> Option<f64> {
+        let efd = efd::Efd::from_curve_harmonic(curve, mode.is_result_open(), self.harmonic());
+        Some(efd.err(&self.tar))
+    }
+}
+
+impl<M, const N: usize, const D: usize> PathOutput<D> for syn::DDPathSyn<M, N, D>
+where
+    efd::U<D>: efd::EfdDim<D>,
+{
+    fn cost(&self, curve: &[[f64; D]], mode: syn::Mode) -> Option<f64> {
+        let efd = efd::Efd::from_curve(curve, mode.is_result_open());
+        Some(efd.err_sig(&self.tar))
     }
 }
 
-impl<M, const N: usize, const D: usize> PSynData<'_, M::De, syn::DDPathSyn<M, N, D>, D>
+impl<M, const N: usize> PathOutput<2> for syn::PointSyn<M, N> {
+    fn cost(&self, _curve: &[[f64; 2]], _mode: syn::Mode) -> Option<f64> {
+        None
+    }
+}
+
+impl<MDe, F, const D: usize> PSynData<'_, MDe, F, D>
 where
-    syn::DDPathSyn<M, N, D>: mh::ObjFunc<Ys = mh::WithProduct<f64, M::De>>,
-    M: atlas::Code<N, D>,
-    M::De: mech::CurveGen<D>
+    F: mh::ObjFunc<Ys = mh::WithProduct<f64, MDe>> + PathOutput<D>,
+    MDe: mech::CurveGen<D>
         + serde::Serialize
         + serde::de::DeserializeOwned
         + Default
@@ -189,8 +127,7 @@ where
         + Send
         + 'static,
     efd::U<D>: efd::EfdDim<D>,
-    efd::Efd<D>: Sync,
-    for<'f1, 'f2> plot::FigureBase<'f1, 'f2, M::De, [f64; D]>: plot::Plot + serde::Serialize,
+    for<'f1, 'f2> plot::FigureBase<'f1, 'f2, MDe, [f64; D]>: plot::Plot + serde::Serialize,
 {
     fn solve_cli(
         self,
@@ -198,25 +135,23 @@ where
         info: &Info,
         history: Arc<Mutex<Vec<f64>>>,
     ) -> Result<(), SynErr> {
-        use four_bar::mech::CurveGen as _;
-        let Self { s, tar_curve, tar_fb, atlas_fb } = self;
-        let Info { root, title, refer, mode, .. } = info;
+        let Self { s, tar_curve, tar_fb, atlas_fb, .. } = self;
+        let Info { root, title, mode, .. } = info;
         let t0 = std::time::Instant::now();
         let s = s.solve();
         let t1 = t0.elapsed();
         let (cost, fb, func) = s.into_err_result_func();
-        let tar_sig = func.tar;
+        let harmonic = func.harmonic();
         {
-            let path = root.join(HISTORY_SVG);
-            let svg = plot::SVGBackend::new(&path, (800, 600));
-            plot::fb::history(svg, Arc::into_inner(history).unwrap().into_inner().unwrap())?;
+            let history = Arc::into_inner(history).unwrap().into_inner().unwrap();
+            save_plot!(info.format, root.join(HISTORY_SVG), (800, 600), |b| {
+                plot::fb::history_best(b, &history, best_so_far(&history), info.markers)
+            });
         }
-        let refer = refer
-            .map(|p| root.join("..").join(p).join(format!("{title}.ron")))
-            .filter(|p| p.is_file());
         let mut log = std::fs::File::create(root.join(format!("{title}.log")))?;
         let mut log = super::logger::Logger::new(&mut log);
         log.top_title(title)?;
+        func.write_target(root, info.precision)?;
         write_ron(root.join(LNK_RON), &fb)?;
         let curve = fb.curve(cfg.res);
         let mut fig = plot::FigureBase::new();
@@ -226,55 +161,62 @@ where
         fig.push_line("Target", &*tar_curve, Style::Circle, TARGET_COLOR);
         {
             write_ron(root.join(TAR_FIG), &fig)?;
-            let path = root.join(TAR_SVG);
-            let svg = plot::SVGBackend::new(&path, (1600, 1600));
-            fig.plot(svg)?;
+            save_plot!(info.format, root.join(TAR_SVG), (1600, 1600), |b| fig
+                .plot(b));
         }
         fig.set_fb_ref(&fb);
         fig.push_line("Optimized", &curve, Style::Line, SYN_COLOR);
         {
             write_ron(root.join(LNK_FIG), &fig)?;
-            let path = root.join(LNK_SVG);
-            let svg = plot::SVGBackend::new(&path, (1600, 1600));
-            fig.plot(svg)?;
+            save_plot!(info.format, root.join(LNK_SVG), (1600, 1600), |b| fig
+                .plot(b));
         }
         gif_video!(info, root, fig);
         if let Some(fb) = tar_fb {
             log.title("target.fb")?;
             log.log(fb)?;
         }
-        if let Some((cost, fb)) = atlas_fb {
+        for (i, (cost, fb)) in atlas_fb.into_iter().enumerate() {
             let curve = fb.curve(cfg.res);
-            log.title("atlas")?;
-            log.log(Performance::cost(cost, &tar_curve, &curve))?;
-            log.title("atlas.fb")?;
+            let name = atlas_name(i);
+            log.title(&name)?;
+            log.log(Performance::cost(cost, &tar_curve, &curve).harmonic(harmonic))?;
+            log.title(&format!("{name}.fb"))?;
             log.log(&fb)?;
-            write_ron(root.join("atlas.ron"), &fb)?;
-            fig.push_line("Atlas", curve, Style::Triangle, ATLAS_COLOR);
+            write_ron(root.join(format!("{name}.ron")), &fb)?;
+            if i == 0 {
+                fig.push_line("Atlas", curve, Style::Triangle, ATLAS_COLOR);
+            }
         }
         log.title("optimized")?;
-        log.log(Performance::cost(cost, &tar_curve, &curve).time(t1))?;
+        log.log(
+            Performance::cost(cost, &tar_curve, &curve)
+                .time(t1)
+                .harmonic(harmonic),
+        )?;
         log.title("optimized.fb")?;
         log.log(&fb)?;
-        if let Some(refer) = refer {
-            let fb = ron::de::from_reader::<_, M::De>(std::fs::File::open(refer)?)?;
+        for (i, refer) in refer_paths(info) {
+            let fb = ron::de::from_reader::<_, MDe>(std::fs::File::open(refer)?)?;
             let c = fb.curve(cfg.res);
-            log.title("competitor")?;
-            if matches!(mode, syn::Mode::Partial) {
-                log.log(Performance::dist_err(&tar_curve, &c))?;
-            } else {
-                let efd = efd::Efd::from_curve(&c, mode.is_result_open());
-                log.log(Performance::cost(efd.err_sig(&tar_sig), &tar_curve, &c))?;
+            let name = refer_name(i);
+            log.title(&name)?;
+            let cost = (!matches!(mode, syn::Mode::Partial))
+                .then(|| func.cost(&c, *mode))
+                .flatten();
+            match cost {
+                Some(cost) => log.log(Performance::cost(cost, &tar_curve, &c))?,
+                None => log.log(Performance::dist_err(&tar_curve, &c))?,
             }
-            log.title("competitor.fb")?;
+            log.title(&format!("{name}.fb"))?;
             log.log(&fb)?;
-            fig.push_line("Ref. [?]", c, Style::DashedLine, REF_COLOR);
+            let (style, color) = refer_style(i);
+            fig.push_line(format!("Ref. [{}]", i + 1), c, style, color);
         }
         fig.remove_fb();
         write_ron(root.join(CURVE_FIG), &fig)?;
-        let path = root.join(CURVE_SVG);
-        let svg = plot::SVGBackend::new(&path, (1600, 1600));
-        fig.plot(svg)?;
+        save_plot!(info.format, root.join(CURVE_SVG), (1600, 1600), |b| fig
+            .plot(b));
         log.flush()?;
         Ok(())
     }
@@ -287,8 +229,8 @@ impl MSynData<'_, syn::MOFit, syn::MFbSyn> {
         info: &Info,
         history: Arc<Mutex<Vec<f64>>>,
     ) -> Result<(), SynErr> {
-        let Self { s, tar_p, tar_v, tar_fb } = self;
-        let Info { root, title, mode, refer, .. } = info;
+        let Self { s, tar_p, tar_v, tar_fb, .. } = self;
+        let Info { root, title, mode, .. } = info;
         let t0 = std::time::Instant::now();
         let s = s.solve();
         let t1 = t0.elapsed();
@@ -297,18 +239,16 @@ impl MSynData<'_, syn::MOFit, syn::MFbSyn> {
         let tar_efd = func.tar.clone();
         let (cost, fb) = s.into_err_result();
         {
-            let path = root.join(HISTORY_SVG);
-            let svg = plot::SVGBackend::new(&path, (800, 600));
-            plot::fb::history(svg, Arc::into_inner(history).unwrap().into_inner().unwrap())?;
+            let history = Arc::into_inner(history).unwrap().into_inner().unwrap();
+            save_plot!(info.format, root.join(HISTORY_SVG), (800, 600), |b| {
+                plot::fb::history_best(b, &history, best_so_far(&history), info.markers)
+            });
         }
-        let refer = refer
-            .map(|p| root.join("..").join(p).join(format!("{title}.ron")))
-            .filter(|p| p.is_file());
         let mut log = std::fs::File::create(root.join(format!("{title}.log")))?;
         let mut log = super::logger::Logger::new(&mut log);
         log.top_title(title)?;
-        write_tar_efd(root.join(EFD_CRUVE_CSV), tar_efd.as_curve())?;
-        write_tar_efd(root.join(EFD_POSE_CSV), tar_efd.as_pose())?;
+        write_tar_efd(root.join(EFD_CRUVE_CSV), tar_efd.as_curve(), info.precision)?;
+        write_tar_efd(root.join(EFD_POSE_CSV), tar_efd.as_pose(), info.precision)?;
         write_ron(root.join(LNK_RON), &fb)?;
         let length = tar_efd.as_curve().as_geo().scale();
         let (curve_p, curve_q) = fb.ext_curve(length, cfg.res);
@@ -331,9 +271,8 @@ impl MSynData<'_, syn::MOFit, syn::MFbSyn> {
                 false,
             );
             write_ron(root.join(TAR_FIG), &fig)?;
-            let path = root.join(TAR_SVG);
-            let svg = plot::SVGBackend::new(&path, (1600, 1600));
-            fig.plot(svg)?;
+            save_plot!(info.format, root.join(TAR_SVG), (1600, 1600), |b| fig
+                .plot(b));
             fig.lines.pop();
         }
         fig.set_fb_ref(fb.as_fb());
@@ -346,9 +285,8 @@ impl MSynData<'_, syn::MOFit, syn::MFbSyn> {
         );
         {
             write_ron(root.join(LNK_FIG), &fig)?;
-            let path = root.join(LNK_SVG);
-            let svg = plot::SVGBackend::new(&path, (1600, 1600));
-            fig.plot(svg)?;
+            save_plot!(info.format, root.join(LNK_SVG), (1600, 1600), |b| fig
+                .plot(b));
         }
         gif_video!(info, root, fig);
         if let Some(fb) = tar_fb {
@@ -363,11 +301,12 @@ impl MSynData<'_, syn::MOFit, syn::MFbSyn> {
         )?;
         log.title("optimized.fb")?;
         log.log(&fb)?;
-        if let Some(refer) = refer {
+        for (i, refer) in refer_paths(info) {
             let fb = ron::de::from_reader::<_, MFourBar>(std::fs::File::open(refer)?)?;
             let (c, v) = fb.pose(cfg.res);
             let c_q = efd::posed::guide_from_curve(&c, &v, length);
-            log.title("competitor")?;
+            let name = refer_name(i);
+            log.title(&name)?;
             if matches!(mode, syn::Mode::Partial) {
                 log.log(Performance::dist_err_m(&tar_p, &tar_q, &c, &c_q))?;
             } else {
@@ -376,21 +315,15 @@ impl MSynData<'_, syn::MOFit, syn::MFbSyn> {
                 let cost = efd.err(&tar_efd);
                 log.log(Performance::cost_m(cost, &tar_p, &tar_q, &c, &c_q))?;
             }
-            log.title("competitor.fb")?;
+            log.title(&format!("{name}.fb"))?;
             log.log(&fb)?;
-            fig.push_series(
-                "Ref. [?]",
-                (c, c_q),
-                Style::DashDottedLine,
-                ATLAS_COLOR,
-                true,
-            );
+            let (style, color) = refer_style(i);
+            fig.push_series(format!("Ref. [{}]", i + 1), (c, c_q), style, color, true);
         }
         fig.remove_fb();
         write_ron(root.join(CURVE_FIG), &fig)?;
-        let path = root.join(CURVE_SVG);
-        let svg = plot::SVGBackend::new(&path, (1600, 1600));
-        fig.plot(svg)?;
+        save_plot!(info.format, root.join(CURVE_SVG), (1600, 1600), |b| fig
+            .plot(b));
         log.flush()?;
         Ok(())
     }
@@ -403,21 +336,19 @@ impl MSynData<'_, f64, syn::MFbDDSyn> {
         info: &Info,
         history: Arc<Mutex<Vec<f64>>>,
     ) -> Result<(), SynErr> {
-        let Self { s, tar_p, tar_v, tar_fb } = self;
-        let Info { root, title, refer, mode, .. } = info;
+        let Self { s, tar_p, tar_v, tar_fb, .. } = self;
+        let Info { root, title, mode, .. } = info;
         let t0 = std::time::Instant::now();
         let s = s.solve();
         let t1 = t0.elapsed();
         let (cost, fb, func) = s.into_err_result_func();
         let tar_sig = func.tar;
         {
-            let path = root.join(HISTORY_SVG);
-            let svg = plot::SVGBackend::new(&path, (800, 600));
-            plot::fb::history(svg, Arc::into_inner(history).unwrap().into_inner().unwrap())?;
+            let history = Arc::into_inner(history).unwrap().into_inner().unwrap();
+            save_plot!(info.format, root.join(HISTORY_SVG), (800, 600), |b| {
+                plot::fb::history_best(b, &history, best_so_far(&history), info.markers)
+            });
         }
-        let refer = refer
-            .map(|p| root.join("..").join(p).join(format!("{title}.ron")))
-            .filter(|p| p.is_file());
         let mut log = std::fs::File::create(root.join(format!("{title}.log")))?;
         let mut log = super::logger::Logger::new(&mut log);
         log.top_title(title)?;
@@ -444,9 +375,8 @@ impl MSynData<'_, f64, syn::MFbDDSyn> {
                 false,
             );
             write_ron(root.join(TAR_FIG), &fig)?;
-            let path = root.join(TAR_SVG);
-            let svg = plot::SVGBackend::new(&path, (1600, 1600));
-            fig.plot(svg)?;
+            save_plot!(info.format, root.join(TAR_SVG), (1600, 1600), |b| fig
+                .plot(b));
             fig.lines.pop();
         }
         fig.set_fb_ref(fb.as_fb());
@@ -459,9 +389,8 @@ impl MSynData<'_, f64, syn::MFbDDSyn> {
         );
         {
             write_ron(root.join(LNK_FIG), &fig)?;
-            let path = root.join(LNK_SVG);
-            let svg = plot::SVGBackend::new(&path, (1600, 1600));
-            fig.plot(svg)?;
+            save_plot!(info.format, root.join(LNK_SVG), (1600, 1600), |b| fig
+                .plot(b));
         }
         gif_video!(info, root, fig);
         if let Some(fb) = tar_fb {
@@ -472,11 +401,12 @@ impl MSynData<'_, f64, syn::MFbDDSyn> {
         log.log(Performance::cost_m(cost, &tar_p, &tar_q, &curve_p, &curve_q).time(t1))?;
         log.title("optimized.fb")?;
         log.log(&fb)?;
-        if let Some(refer) = refer {
+        for (i, refer) in refer_paths(info) {
             let fb = ron::de::from_reader::<_, MFourBar>(std::fs::File::open(refer)?)?;
             let (c, v) = fb.pose(cfg.res);
             let c_q = efd::posed::guide_from_curve(&c, &v, length);
-            log.title("competitor")?;
+            let name = refer_name(i);
+            log.title(&name)?;
             if matches!(mode, syn::Mode::Partial) {
                 log.log(Performance::dist_err_m(&tar_p, &tar_q, &c, &c_q))?;
             } else {
@@ -484,21 +414,15 @@ impl MSynData<'_, f64, syn::MFbDDSyn> {
                 let cost = efd.err_sig(&tar_sig);
                 log.log(Performance::cost_m(cost, &tar_p, &tar_q, &c, &c_q))?;
             }
-            log.title("competitor.fb")?;
+            log.title(&format!("{name}.fb"))?;
             log.log(&fb)?;
-            fig.push_series(
-                "Ref. [?]",
-                (c, c_q),
-                Style::DashDottedLine,
-                ATLAS_COLOR,
-                true,
-            );
+            let (style, color) = refer_style(i);
+            fig.push_series(format!("Ref. [{}]", i + 1), (c, c_q), style, color, true);
         }
         fig.remove_fb();
         write_ron(root.join(CURVE_FIG), &fig)?;
-        let path = root.join(CURVE_SVG);
-        let svg = plot::SVGBackend::new(&path, (1600, 1600));
-        fig.plot(svg)?;
+        save_plot!(info.format, root.join(CURVE_SVG), (1600, 1600), |b| fig
+            .plot(b));
         log.flush()?;
         Ok(())
     }
@@ -512,22 +436,46 @@ pub(crate) fn run(alg: SynAlg, info: Info, target: Target, cfg: &SynCfg) {
         info.pb.inc_length(cfg.gen);
         from_runtime(alg, target, cfg, &info)
     };
-    match ret {
-        Ok(()) => info.pb.println(format!("Finished: {}", info.title)),
-        Err(e) => info.pb.println(format!("Error in {}: {e}", info.title)),
+    let msg = match ret {
+        Ok(()) => format!("Finished: {}", info.title),
+        Err(e) => format!("Error in {}: {e}", info.title),
+    };
+    match info.progress {
+        ProgressFmt::Bar => info.pb.println(msg),
+        // The hidden progress bar prints nothing
+        ProgressFmt::Json => println!("{msg}"),
     }
 }
 
+// A line of the JSON progress output
+fn progress_json(title: &str, gen: u64, total: u64) -> String {
+    let pct = if total == 0 {
+        100.
+    } else {
+        (gen as f64 / total as f64 * 1000.).round() / 10.
+    };
+    serde_json::json!({"title": title, "gen": gen, "pct": pct}).to_string()
+}
+
 fn from_runtime(alg: SynAlg, target: Target, cfg: &SynCfg, info: &Info) -> Result<(), SynErr> {
     let history = Arc::new(Mutex::new(Vec::with_capacity(cfg.gen as usize)));
     let s = {
         let pb = info.pb.clone();
         let history = history.clone();
+        let (progress, title, total) = (info.progress, info.title.clone(), cfg.gen);
+        let mut last_pct = None;
         let cfg = SynCfg { mode: info.mode, ..cfg.clone() };
         let stop = || false;
-        Solver::new(alg, target, cfg, stop, move |best_f, _| {
+        Solver::new(alg, target, cfg, stop, move |best_f, gen| {
             history.lock().unwrap().push(best_f);
             pb.inc(1);
+            if progress == ProgressFmt::Json {
+                // Print once per percent
+                let pct = (gen * 100).checked_div(total).unwrap_or(100);
+                if last_pct.replace(pct) != Some(pct) {
+                    eprintln!("{}", progress_json(&title, gen, total));
+                }
+            }
         })
     };
     match s {
@@ -537,6 +485,7 @@ fn from_runtime(alg: SynAlg, target: Target, cfg: &SynCfg, info: &Info) -> Resul
         Solver::DDFb(s) => s.solve_cli(cfg, info, history),
         Solver::DDSFb(s) => s.solve_cli(cfg, info, history),
         Solver::DDMFb(s) => s.solve_cli(cfg, info, history),
+        Solver::PointFb(s) => s.solve_cli(cfg, info, history),
     }
 }
 
@@ -555,7 +504,8 @@ fn from_exist(target: &Target, info: &Info) -> Result<(), SynErr> {
                 if let Some(legend) = info.legend {
                     fig.legend = legend;
                 }
-                fig.plot(plot::SVGBackend::new(&root.join(svg_path), (1600, 1600)))?;
+                save_plot!(info.format, root.join(svg_path), (1600, 1600), |b| fig
+                    .plot(b));
                 if path == LNK_FIG {
                     gif_video!(info, root, fig);
                 }
@@ -574,6 +524,8 @@ fn from_exist(target: &Target, info: &Info) -> Result<(), SynErr> {
 struct Performance {
     #[serde(rename = "dist-err")]
     dist_err: f64,
+    #[serde(rename = "hausdorff-err")]
+    hausdorff_err: f64,
     cost: Option<f64>,
     #[serde(serialize_with = "ser_time")]
     time: Option<std::time::Duration>,
@@ -590,30 +542,43 @@ where
     }
 }
 
+// (dist_err, hausdorff_err)
+fn curve_err<const D: usize>(tar: impl efd::Curve<D>, cur: impl efd::Curve<D>) -> (f64, f64) {
+    let (tar, cur) = (tar.as_curve(), cur.as_curve());
+    (efd::util::dist_err(tar, cur), curve::hausdorff(tar, cur))
+}
+
 impl Performance {
     fn dist_err<const D: usize>(tar: impl efd::Curve<D>, cur: impl efd::Curve<D>) -> Self {
-        let dist_err = efd::util::dist_err(tar, cur);
-        Self { dist_err, cost: None, time: None, harmonic: None }
-    }
-
-    fn cost<const D: usize>(cost: f64, tar: impl efd::Curve<D>, cur: impl efd::Curve<D>) -> Self {
-        let dist_err = efd::util::dist_err(tar, cur);
+        let (dist_err, hausdorff_err) = curve_err(tar, cur);
         Self {
-            cost: Some(cost),
             dist_err,
+            hausdorff_err,
+            cost: None,
             time: None,
             harmonic: None,
         }
     }
 
+    fn cost<const D: usize>(cost: f64, tar: impl efd::Curve<D>, cur: impl efd::Curve<D>) -> Self {
+        Self { cost: Some(cost), ..Self::dist_err(tar, cur) }
+    }
+
     fn dist_err_m<const D: usize>(
         tar_p: impl efd::Curve<D>,
         tar_q: impl efd::Curve<D>,
         cur_p: impl efd::Curve<D>,
         cur_q: impl efd::Curve<D>,
     ) -> Self {
-        let dist_err = efd::util::dist_err(tar_p, cur_p) + efd::util::dist_err(tar_q, cur_q);
-        Self { dist_err, cost: None, time: None, harmonic: None }
+        let (dist_p, hausdorff_p) = curve_err(tar_p, cur_p);
+        let (dist_q, hausdorff_q) = curve_err(tar_q, cur_q);
+        Self {
+            dist_err: dist_p + dist_q,
+            hausdorff_err: hausdorff_p + hausdorff_q,
+            cost: None,
+            time: None,
+            harmonic: None,
+        }
     }
 
     fn cost_m<const D: usize>(
@@ -623,12 +588,9 @@ impl Performance {
         cur_p: impl efd::Curve<D>,
         cur_q: impl efd::Curve<D>,
     ) -> Self {
-        let dist_err = efd::util::dist_err(tar_p, cur_p) + efd::util::dist_err(tar_q, cur_q);
         Self {
             cost: Some(cost),
-            dist_err,
-            time: None,
-            harmonic: None,
+            ..Self::dist_err_m(tar_p, tar_q, cur_p, cur_q)
         }
     }
 
@@ -636,11 +598,46 @@ impl Performance {
         Self { time: Some(time), ..self }
     }
 
-    fn harmonic(self, harmonic: usize) -> Self {
-        Self { harmonic: Some(harmonic), ..self }
+    fn harmonic(self, harmonic: impl Into<Option<usize>>) -> Self {
+        Self { harmonic: harmonic.into(), ..self }
+    }
+}
+
+// The best candidate keeps the original name "atlas"
+fn atlas_name(i: usize) -> String {
+    if i == 0 {
+        "atlas".to_string()
+    } else {
+        format!("atlas{i}")
     }
 }
 
+// Existing reference linkage files with their indexes in the given order
+fn refer_paths<'a>(info: &'a Info) -> impl Iterator<Item = (usize, PathBuf)> + 'a {
+    let Info { root, title, refer, .. } = info;
+    (refer.iter().enumerate())
+        .map(move |(i, p)| (i, root.join("..").join(p).join(format!("{title}.ron"))))
+        .filter(|(_, p)| p.is_file())
+}
+
+// The first reference keeps the original name "competitor", the others are
+// numbered as their labels
+fn refer_name(i: usize) -> String {
+    if i == 0 {
+        "competitor".to_string()
+    } else {
+        format!("competitor{}", i + 1)
+    }
+}
+
+// Cycle through the styles and colors of the references
+fn refer_style(i: usize) -> (Style, RGBColor) {
+    (
+        REF_STYLES[i % REF_STYLES.len()],
+        REF_COLORS[i % REF_COLORS.len()],
+    )
+}
+
 fn write_ron<S>(path: impl AsRef<Path>, s: &S) -> Result<(), SynErr>
 where
     S: serde::Serialize,
@@ -649,7 +646,11 @@ where
     Ok(())
 }
 
-fn write_tar_efd<const D: usize>(path: impl AsRef<Path>, efd: &efd::Efd<D>) -> Result<(), SynErr>
+fn write_tar_efd<const D: usize>(
+    path: impl AsRef<Path>,
+    efd: &efd::Efd<D>,
+    prec: usize,
+) -> Result<(), SynErr>
 where
     efd::U<D>: efd::EfdDim<D>,
 {
@@ -658,9 +659,9 @@ where
     for m in efd.coeffs_iter() {
         for (i, c) in m.iter().enumerate() {
             if i == m.len() - 1 {
-                write!(w, "{c:.4}")?;
+                write!(w, "{c:.prec$}")?;
             } else {
-                write!(w, "{c:.4},")?;
+                write!(w, "{c:.prec$},")?;
             }
         }
         writeln!(w)?;
@@ -668,3 +669,15 @@ where
     w.flush()?;
     Ok(())
 }
+
+#[test]
+fn progress_json_valid() {
+    for title in ["crunode.closed", "a \"quoted\" \\ name\n"] {
+        let line = progress_json(title, 25, 40);
+        let obj = serde_json::from_str::<serde_json::Value>(&line).unwrap();
+        assert_eq!(obj["title"], title);
+        assert_eq!(obj["gen"], 25);
+        assert_eq!(obj["pct"], 62.5);
+        assert!(!line.contains('\n'));
+    }
+}
@@ -0,0 +1,165 @@
+use super::syn::{
+    format::{save_plot, PlotFormat},
+    read_csv_curve, SynErr,
+};
+use crate::{io, syn_cmd::*};
+use four_bar::{efd, plot};
+use std::{
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+#[derive(clap::Args)]
+pub(super) struct TournamentCfg {
+    /// Target file path of the curve in CSV format, use "-" to read from the
+    /// standard input
+    file: PathBuf,
+    /// Save the combined convergence plot of the algorithms
+    #[clap(long)]
+    plot: Option<PathBuf>,
+    /// The output format of the plot, the file extension is replaced by the
+    /// format
+    #[clap(long, value_enum, default_value_t)]
+    format: PlotFormat,
+    #[clap(flatten)]
+    cfg: SynCfg,
+}
+
+// Result of an algorithm
+struct Record {
+    abbr: &'static str,
+    cost: f64,
+    time: Duration,
+    history: Vec<f64>,
+}
+
+pub(super) fn tournament(cfg: TournamentCfg) {
+    let TournamentCfg { file, plot, format, cfg } = cfg;
+    let target = load_target(&file).unwrap_or_else(|e| {
+        eprintln!("{}: {e}", file.display());
+        std::process::exit(1);
+    });
+    // All algorithms use the same seed
+    let seed = cfg.seed.unwrap_or_else(|| {
+        use std::time::{SystemTime, UNIX_EPOCH};
+        let t = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        t.as_nanos() as u64
+    });
+    let cfg = SynCfg { seed: Some(seed), ..cfg };
+    if let Err(e) = cfg.check(&target) {
+        eprintln!("{}: {e}", file.display());
+        std::process::exit(1);
+    }
+    println!(
+        "seed={seed} gen={} pop={} res={}",
+        cfg.gen, cfg.pop, cfg.res
+    );
+    let mut records = SynAlg::LIST
+        .iter()
+        .map(|(_, abbr, f)| {
+            let history = Arc::new(Mutex::new(Vec::with_capacity(cfg.gen as usize)));
+            let s = {
+                let history = history.clone();
+                Solver::new(
+                    f(),
+                    target.clone(),
+                    cfg.clone(),
+                    || false,
+                    move |best_f, _| history.lock().unwrap().push(best_f),
+                )
+            };
+            let t0 = Instant::now();
+            s.solve();
+            let time = t0.elapsed();
+            let history = Arc::into_inner(history).unwrap().into_inner().unwrap();
+            let cost = history.last().copied().unwrap_or(f64::INFINITY);
+            println!("Finished: {abbr}");
+            Record { abbr, cost, time, history }
+        })
+        .collect::<Vec<_>>();
+    records.sort_by(|a, b| a.cost.total_cmp(&b.cost));
+    println!("-----");
+    println!("{:<6} {:<6} {:>12} {:>12}", "rank", "alg", "cost", "time");
+    for (i, Record { abbr, cost, time, .. }) in records.iter().enumerate() {
+        println!("{:<6} {abbr:<6} {cost:>12.06} {time:>12.02?}", i + 1);
+    }
+    if let Some(path) = plot {
+        if let Err(e) = plot_convergence(&path, format, &records) {
+            eprintln!("{}: {e}", path.display());
+            std::process::exit(1);
+        }
+    }
+}
+
+// Load the target curve of the synthesis
+fn load_target(file: &Path) -> Result<Target<'static, 'static>, SynErr> {
+    macro_rules! valid {
+        ($c:expr) => {
+            efd::util::valid_curve($c).ok_or(SynErr::Linkage)?.into()
+        };
+    }
+    Ok(match read_csv_curve(file)? {
+        io::Curve::P(t) => Target::fb(valid!(t), None, None),
+        io::Curve::M(mut t) => {
+            // Poses may not be given in unit vectors
+            io::normalize_uvec(&mut t)?;
+            if t.len() < 3 {
+                return Err(SynErr::Linkage);
+            }
+            Target::mfb(t.into(), None)
+        }
+        io::Curve::S(t) => Target::sfb(valid!(t), None, None),
+    })
+}
+
+// Plot the best-so-far fitness of all algorithms in a chart
+fn plot_convergence(path: &Path, format: PlotFormat, records: &[Record]) -> Result<(), SynErr> {
+    save_plot!(format, path, (800, 600), |b| draw_convergence(b, records));
+    Ok(())
+}
+
+fn draw_convergence<B>(
+    b: B,
+    records: &[Record],
+) -> Result<(), plot::DrawingAreaErrorKind<B::ErrorType>>
+where
+    B: plot::DrawingBackend,
+{
+    use plot::*;
+    let root = b.into_drawing_area();
+    root.fill(&WHITE)?;
+    let gen = records.iter().map(|r| r.history.len()).max().unwrap_or(0);
+    let max = (records.iter())
+        .flat_map(|r| r.history.iter().copied())
+        .fold(0., f64::max);
+    let mut chart = ChartBuilder::on(&root)
+        .set_label_area_size(LabelAreaPosition::Left, (10).percent())
+        .set_label_area_size(LabelAreaPosition::Bottom, (10).percent())
+        .margin((4).percent())
+        .build_cartesian_2d(0..gen.max(2) - 1, 0.0..max)?;
+    chart
+        .configure_mesh()
+        .disable_x_mesh()
+        .disable_y_mesh()
+        .x_desc("Generation")
+        .y_desc("Fitness")
+        .draw()?;
+    for (i, Record { abbr, history, .. }) in records.iter().enumerate() {
+        let color = Palette99::pick(i).to_rgba();
+        let line = best_so_far(history).into_iter().enumerate();
+        chart
+            .draw_series(LineSeries::new(line, color.stroke_width(2)))?
+            .label(*abbr)
+            .legend(move |(x, y)| PathElement::new([(x, y), (x + 20, y)], color));
+    }
+    chart
+        .configure_series_labels()
+        .background_style(WHITE)
+        .border_style(BLACK)
+        .draw()?;
+    root.present()?;
+    Ok(())
+}
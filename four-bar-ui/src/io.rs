@@ -12,8 +12,12 @@ const CSV_FMT: &str = "Delimiter-Separated Values (CSV)";
 const CSV_EXT: &[&str] = &["csv", "txt"];
 const ATLAS_FMT: &str = "Numpy Array Zip (NPZ)";
 const ATLAS_EXT: &[&str] = &["npz"];
+const ATLAS_ANY_FMT: &str = "Atlas Data (NPZ, CSV & RON)";
+const ATLAS_ANY_EXT: &[&str] = &["npz", "csv", "ron"];
 const SVG_FMT: &str = "Scalable Vector Graphics (SVG)";
 const SVG_EXT: &[&str] = &["svg"];
+const HTML_FMT: &str = "HyperText Markup Language (HTML)";
+const HTML_EXT: &[&str] = &["html"];
 const GIF_FMT: &str = "Graphics Interchange Format (GIF)";
 const GIF_EXT: &[&str] = &["gif"];
 const IMG_FMT: &str = "Supported Image Format (PNG & JPEG)";
@@ -76,10 +80,10 @@ mod impl_io {
 
     pub(super) fn open_bin<C>(_fmt: &str, ext: &[&str], done: C)
     where
-        C: Fn(Cursor<Vec<u8>>) + 'static,
+        C: Fn(PathBuf, Cursor<Vec<u8>>) + 'static,
     {
-        let done = move |buf| done(Cursor::new(buf));
-        let done = Closure::<dyn Fn(Vec<u8>)>::wrap(Box::new(done)).into_js_value();
+        let done = move |path, buf| done(PathBuf::from(path), Cursor::new(buf));
+        let done = Closure::<dyn Fn(String, Vec<u8>)>::wrap(Box::new(done)).into_js_value();
         open_file(&js_ext(ext), done, true, true);
     }
 
@@ -141,11 +145,11 @@ mod impl_io {
 
     pub(super) fn open_bin<C>(fmt: &str, ext: &[&str], done: C)
     where
-        C: Fn(File) + 'static,
+        C: Fn(PathBuf, File) + 'static,
     {
         if let Some(paths) = rfd::FileDialog::new().add_filter(fmt, ext).pick_files() {
             for path in paths {
-                alert!(("Open File", File::open(path)), ("*", done));
+                alert!(("Open File", File::open(&path)), ("*", |r| done(path, r)));
             }
         }
     }
@@ -322,12 +326,17 @@ where
     });
 }
 
-pub(crate) fn open_atlas<C>(done: C)
+pub(crate) fn open_atlas<C>(cfg: atlas::Cfg, done: C)
 where
     C: Fn(Atlas) + 'static,
 {
-    let done = move |b| alert!(("Parse File", Atlas::from_reader(b)), ("*", done));
-    open_bin(ATLAS_FMT, ATLAS_EXT, done);
+    let done = move |path: PathBuf, r| {
+        alert!(
+            ("Parse File", Atlas::from_path_reader(&path, r, &cfg)),
+            ("*", |atlas| done(atlas))
+        );
+    };
+    open_bin(ATLAS_ANY_FMT, ATLAS_ANY_EXT, done);
 }
 
 pub(crate) fn open_img<C>(done: C)
@@ -342,7 +351,7 @@ where
     });
 }
 
-pub(crate) fn save_csv_ask<S>(c: &[S])
+pub(crate) fn save_csv_ask<S>(c: &[S], precision: Option<usize>)
 where
     S: serde::Serialize,
 {
@@ -350,7 +359,20 @@ where
         "curve.csv",
         CSV_FMT,
         CSV_EXT,
-        |w| csv::to_writer(w, c),
+        |w| csv::to_writer(w, c, precision),
+        |_| (),
+    );
+}
+
+pub(crate) fn save_joints_ask<const D: usize>(
+    data: &[(f64, [[f64; D]; 3])],
+    precision: Option<usize>,
+) {
+    save_ask(
+        "joints.csv",
+        CSV_FMT,
+        CSV_EXT,
+        |w| csv::joints_to_writer(w, data, precision),
         |_| (),
     );
 }
@@ -399,15 +421,26 @@ pub(crate) fn save_svg_ask(buf: &str, name: &str) {
     );
 }
 
+pub(crate) fn save_html_ask(buf: &str, name: &str) {
+    use std::io::Write as _;
+    save_ask(
+        name,
+        HTML_FMT,
+        HTML_EXT,
+        |mut w| w.write_all(buf.as_bytes()),
+        |_| (),
+    );
+}
+
 pub(crate) fn save_gif_ask(buf: Vec<u8>, name: &str) {
     use std::io::Write as _;
     save_ask(name, GIF_FMT, GIF_EXT, |mut w| w.write_all(&buf), |_| ());
 }
 
-pub(crate) fn save_history_ask(history: &[f64], name: &str) {
+pub(crate) fn save_history_ask(history: &[f64], best: &[f64], name: &str) {
     let mut buf = String::new();
     let svg = plot::SVGBackend::with_string(&mut buf, (800, 600));
-    plot::fb::history(svg, history).unwrap();
+    plot::fb::history_best(svg, history, best, 0).unwrap();
     save_svg_ask(&buf, name);
 }
 
@@ -433,7 +466,7 @@ impl Default for Curve {
 }
 
 impl Curve {
-    pub(crate) fn from_csv_reader<R>(mut r: R) -> Result<Self, csv::Error>
+    pub(crate) fn from_csv_reader<R>(mut r: R) -> Result<Self, four_bar::Error>
     where
         R: std::io::Read + std::io::Seek,
     {
@@ -450,6 +483,36 @@ impl Curve {
             })
     }
 
+    /// Parse the pasted text, in CSV or RON format (array of tuple or array of
+    /// array, as the copy buttons).
+    pub(crate) fn from_text(s: &str) -> Result<Self, ron::error::SpannedError> {
+        if let Ok(c) = Self::from_csv_reader(std::io::Cursor::new(s)) {
+            return Ok(c);
+        }
+        // Array of tuple, in descending order as the CSV
+        let err = match (ron::from_str(s).map(Self::M))
+            .or_else(|_| ron::from_str(s).map(Self::S))
+            .or_else(|_| ron::from_str(s).map(Self::P))
+        {
+            Ok(c) => return Ok(c),
+            Err(e) => e,
+        };
+        // Array of array, the motion is concatenated as `[x, y, u, v]`
+        let c = ron::from_str::<Vec<Vec<f64>>>(s)?;
+        let dim = c.first().map(Vec::len);
+        if c.iter().any(|r| Some(r.len()) != dim) {
+            return Err(err);
+        }
+        match dim {
+            Some(2) => Ok(Self::P(c.iter().map(|r| [r[0], r[1]]).collect())),
+            Some(3) => Ok(Self::S(c.iter().map(|r| [r[0], r[1], r[2]]).collect())),
+            Some(4) => Ok(Self::M(
+                c.iter().map(|r| ([r[0], r[1]], [r[2], r[3]])).collect(),
+            )),
+            _ => Err(err),
+        }
+    }
+
     pub(crate) fn len(&self) -> usize {
         match self {
             Curve::P(c) => c.len(),
@@ -462,6 +525,14 @@ impl Curve {
         self.len() == 0
     }
 
+    pub(crate) fn resolve_mode(&self, mode: syn::Mode) -> syn::Mode {
+        match self {
+            Self::P(c) => mode.resolve(c),
+            Self::M(c) => mode.resolve(&c.iter().map(|(c, _)| *c).collect::<Vec<_>>()),
+            Self::S(c) => mode.resolve(c),
+        }
+    }
+
     pub(crate) fn convert_to_planar(&mut self) {
         match self {
             Self::S(c) => *self = Self::P(c.iter().map(|&[x, y, _]| [x, y]).collect()),
@@ -487,23 +558,72 @@ impl Curve {
     }
 }
 
+/// Normalize the unit vectors of the motion target.
+///
+/// Return an error if any vector is zero or not finite, which has no
+/// direction, and the target is unchanged in this case.
+pub(crate) fn normalize_uvec(c: &mut [([f64; 2], [f64; 2])]) -> Result<(), std::io::Error> {
+    let norms = c.iter().map(|(_, v)| v[0].hypot(v[1])).collect::<Vec<_>>();
+    if let Some(i) = norms.iter().position(|n| !n.is_normal()) {
+        let msg = format!("the pose vector of row {} has no direction", i + 1);
+        return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, msg));
+    }
+    for ((_, v), norm) in std::iter::zip(c, norms) {
+        v[0] /= norm;
+        v[1] /= norm;
+    }
+    Ok(())
+}
+
 pub(crate) enum Atlas {
     P(atlas::FbAtlas),
     S(atlas::SFbAtlas),
 }
 
 impl Atlas {
-    pub(crate) fn from_reader<R>(mut r: R) -> Result<Self, atlas::ReadNpzError>
+    pub(crate) fn from_reader<R>(mut r: R) -> Result<Self, four_bar::Error>
     where
         R: std::io::Read + std::io::Seek,
     {
         if let Ok(atlas) = atlas::SFbAtlas::read(&mut r) {
             Ok(Self::S(atlas))
         } else {
-            r.rewind().map_err(|e| atlas::ReadNpzError::Zip(e.into()))?;
+            r.rewind()?;
             Ok(Self::P(atlas::FbAtlas::read(r)?))
         }
     }
+
+    /// Detect the format by the file extension, the EFD coefficients of the
+    /// CSV and RON formats are computed by the `cfg`.
+    pub(crate) fn from_path_reader<R>(
+        path: &Path,
+        mut r: R,
+        cfg: &atlas::Cfg,
+    ) -> Result<Self, four_bar::Error>
+    where
+        R: std::io::Read + std::io::Seek,
+    {
+        let ext = path.extension().and_then(|s| s.to_str()).unwrap_or("npz");
+        match ext.to_lowercase().as_str() {
+            "csv" => {
+                let mut s = String::new();
+                r.read_to_string(&mut s)?;
+                // The number of the columns is checked by the parser
+                atlas::FbAtlas::read_csv(s.as_bytes(), cfg)
+                    .map(Self::P)
+                    .or_else(|_| atlas::SFbAtlas::read_csv(s.as_bytes(), cfg).map(Self::S))
+            }
+            "ron" => {
+                let mut s = String::new();
+                r.read_to_string(&mut s)?;
+                // The planar linkages have no field `l2` of the spherical linkages
+                atlas::SFbAtlas::read_ron(s.as_bytes(), cfg)
+                    .map(Self::S)
+                    .or_else(|_| atlas::FbAtlas::read_ron(s.as_bytes(), cfg).map(Self::P))
+            }
+            _ => Self::from_reader(r),
+        }
+    }
 }
 
 #[derive(Default)]
@@ -581,3 +701,36 @@ where
     let size = [img.width(), img.height()].map(|s| s as _);
     Ok(ColorImage::from_rgba_unmultiplied(size, img.as_raw()))
 }
+
+#[test]
+fn parse_pasted_curve() {
+    let p = |s| match Curve::from_text(s) {
+        Ok(Curve::P(c)) => c,
+        _ => panic!("{s}"),
+    };
+    let c = vec![[0., 1.], [2., 3.5]];
+    assert_eq!(p("0,1\n2,3.5\n"), c);
+    assert_eq!(p("[(0.0, 1.0), (2.0, 3.5)]"), c);
+    assert_eq!(p("[[0.0, 1.0], [2.0, 3.5]]"), c);
+    let m = Curve::from_text("[[0.0, 1.0, 2.0, 3.0]]");
+    assert!(matches!(m, Ok(Curve::M(..))));
+    assert!(Curve::from_text("[[0.0, 1.0], [2.0]]").is_err());
+    assert!(Curve::from_text("hello").is_err());
+}
+
+#[test]
+fn parse_pose_csv() {
+    let s = include_str!("../../test-mfb/pose.closed.csv");
+    let Ok(Curve::M(mut c)) = Curve::from_csv_reader(std::io::Cursor::new(s)) else {
+        panic!("not a motion target");
+    };
+    assert_eq!(c.len(), 24);
+    normalize_uvec(&mut c).unwrap();
+    for (_, [ux, uy]) in &c {
+        assert!((ux.hypot(*uy) - 1.).abs() < 1e-12);
+    }
+    // The zero vectors have no direction
+    let mut zero = vec![([0., 0.], [1., 1.]), ([1., 0.], [0., 0.])];
+    assert!(normalize_uvec(&mut zero).is_err());
+    assert_eq!(zero[0].1, [1., 1.]);
+}
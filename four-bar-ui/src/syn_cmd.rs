@@ -1,7 +1,47 @@
 use crate::io;
 use four_bar::{mh::SolverBox, *};
 use serde::{Deserialize, Serialize};
-use std::borrow::Cow;
+use std::{
+    any::Any,
+    borrow::Cow,
+    sync::{Arc, Mutex},
+};
+
+/// Number of the generations solved in a chunk in the web version.
+///
+/// The web version has no threads, so the solver returns the control to the
+/// event loop between the chunks to update the progress. A larger chunk is
+/// faster but makes the UI less responsive.
+#[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
+pub(crate) const CHUNK_GEN: u64 = 20;
+
+/// The population of the latest generation, used to resume the solving from
+/// the previous chunk.
+pub(crate) type Resume = Arc<Mutex<Option<(Vec<Vec<f64>>, Box<dyn Any + Send>)>>>;
+
+// Save the population to the resume slot, only the chunked solving of the
+// web version needs it
+macro_rules! save_pool {
+    ($pool:ident, $ctx:ident) => {
+        #[cfg(target_arch = "wasm32")]
+        {
+            *$pool.lock().unwrap() = Some(($ctx.pool.clone(), Box::new($ctx.pool_y.clone())));
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        let _ = (&$pool, &$ctx);
+    };
+}
+
+// Move the population of the resume slot to the initial pool of the solver
+macro_rules! resume_pool {
+    ($self:ident, $pool:ident) => {
+        if let Some((pool, pool_y)) = $pool.lock().unwrap().take() {
+            if let Ok(pool_y) = pool_y.downcast() {
+                $self.s = $self.s.init_pool(mh::Pool::Ready { pool, pool_y: *pool_y });
+            }
+        }
+    };
+}
 
 macro_rules! impl_method {
     ($(fn $method:ident, $sym:ident, $name:literal, $full_name:literal, $link:literal)+) => {
@@ -47,6 +87,11 @@ impl SynAlg {
         fn tlbo, Tlbo, "TLBO", "Teaching Learning Based Optimization", "https://doi.org/10.1016/j.cad.2010.12.015"
     }
 
+    pub(crate) fn presets(&self) -> impl Iterator<Item = &'static (&'static str, Self)> {
+        let abbr = self.abbr();
+        PRESETS.iter().filter(move |(_, s)| s.abbr() == abbr)
+    }
+
     pub(crate) fn build_solver<F: mh::ObjFunc>(self, f: F) -> SolverBox<'static, F> {
         match self {
             Self::De(s) => mh::Solver::build_boxed(s, f),
@@ -58,6 +103,46 @@ impl SynAlg {
     }
 }
 
+// Set the parameters of the algorithm in the const context
+macro_rules! preset {
+    ($sym:ident { $($field:ident: $v:expr),+ $(,)? }) => {{
+        let mut s = mh::$sym::new();
+        $(s.$field = $v;)+
+        SynAlg::$sym(s)
+    }};
+}
+
+/// Named parameter presets of the algorithms, the unmatched parameters are
+/// shown as "Custom".
+pub(crate) const PRESETS: &[(&str, SynAlg)] = &[
+    ("Default", SynAlg::de()),
+    ("Aggressive", preset!(De { f: 0.9, cross: 0.9 })),
+    ("Robust", preset!(De { f: 0.5, cross: 0.3 })),
+    ("Default", SynAlg::fa()),
+    (
+        "Exploration",
+        preset!(Fa { alpha: 0.8, beta_min: 0.2, gamma: 0.5 }),
+    ),
+    ("Default", SynAlg::pso()),
+    (
+        "Constriction",
+        preset!(Pso {
+            cognition: 1.49445,
+            social: 1.49445,
+            velocity: 0.729
+        }),
+    ),
+    (
+        "Exploration",
+        preset!(Pso { cognition: 2., social: 1., velocity: 0.9 }),
+    ),
+    ("Default", SynAlg::rga()),
+    (
+        "Exploration",
+        preset!(Rga { cross: 0.8, mutate: 0.2, win: 0.9, delta: 5. }),
+    ),
+];
+
 #[derive(Deserialize, Serialize, Clone, PartialEq)]
 #[serde(default)]
 #[cfg_attr(not(target_arch = "wasm32"), derive(clap::Args))]
@@ -75,27 +160,101 @@ pub(crate) struct SynCfg {
     /// Number of population (the fetch number in atlas)
     #[cfg_attr(not(target_arch = "wasm32"), clap(short, long, default_value_t = CFG_DEF.pop))]
     pub(crate) pop: usize,
+    /// Stop early if the best fitness is not improved for this number of
+    /// generations, the number of generation is still the upper limit
+    #[cfg_attr(not(target_arch = "wasm32"), clap(long))]
+    pub(crate) stagnation: Option<u32>,
     /// Number of the points (resolution) in curve production
     #[cfg_attr(not(target_arch = "wasm32"), clap(long, default_value_t = CFG_DEF.res))]
     pub(crate) res: usize,
+    /// The joint that traces the target curve in the path synthesis
+    #[cfg_attr(
+        not(target_arch = "wasm32"),
+        clap(long, value_enum, default_value_t = CFG_DEF.pivot)
+    )]
+    pub(crate) pivot: syn::Pivot,
+    /// Match the mirror image of the target curve in the path synthesis, by
+    /// also comparing the mirror images of the planar linkages
+    #[cfg_attr(not(target_arch = "wasm32"), clap(long))]
+    pub(crate) mirror: bool,
+    /// Also compare the other starting point of the first harmonic in the
+    /// path synthesis of the closed planar curves
+    #[cfg_attr(not(target_arch = "wasm32"), clap(long))]
+    pub(crate) flip: bool,
+    /// Normalize the target curve and the candidate curves of the planar
+    /// linkages to counter-clockwise in the path synthesis
+    #[cfg_attr(not(target_arch = "wasm32"), clap(long))]
+    pub(crate) ccw: bool,
+    /// Exploit the mirror symmetry of the target curve in the path synthesis
+    /// if it is detected, by searching the planar linkages that generate the
+    /// symmetric coupler curves only
+    #[cfg_attr(not(target_arch = "wasm32"), clap(long))]
+    pub(crate) symmetry: bool,
     /// Specify the mechanism on the origin and unit scale
     #[cfg_attr(not(target_arch = "wasm32"), clap(long))]
     pub(crate) on_unit: bool,
     /// Use the distance-discrepancy method
     #[cfg_attr(not(target_arch = "wasm32"), clap(long = "dd"))]
     pub(crate) use_dd: bool,
+    /// Treat the target curve as the precision points of the planar
+    /// linkages, which are matched with the nearest positions of the coupler
+    /// curve instead of the EFD coefficients
+    #[cfg_attr(
+        not(target_arch = "wasm32"),
+        clap(long = "point", conflicts_with = "use_dd")
+    )]
+    pub(crate) use_point: bool,
+    /// Number of the atlas candidates to be shown and compared, which are
+    /// evaluated to seed the population
+    #[cfg_attr(
+        not(target_arch = "wasm32"),
+        clap(long, default_value_t = CFG_DEF.atlas_candidates)
+    )]
+    pub(crate) atlas_candidates: usize,
+    /// Distance metric of the atlas search
+    #[cfg_attr(
+        not(target_arch = "wasm32"),
+        clap(long, value_enum, default_value_t = CFG_DEF.atlas_metric)
+    )]
+    pub(crate) atlas_metric: atlas::EfdMetric,
     #[cfg_attr(not(target_arch = "wasm32"), clap(skip = CFG_DEF.mode))]
     pub(crate) mode: syn::Mode,
+    /// Lock the normalized parameters `[l1, l3, l4, l5, g]` of the planar
+    /// linkages, the atlas is disabled if any parameter is locked
+    #[cfg_attr(not(target_arch = "wasm32"), clap(skip = CFG_DEF.fixed))]
+    pub(crate) fixed: [Option<f64>; 5],
+    /// Generate the candidate curves only over the input angle range
+    /// `[start, end]` in radians, default to the whole range of motion, the
+    /// atlas is disabled if the range is set
+    #[cfg_attr(not(target_arch = "wasm32"), clap(skip = CFG_DEF.input_range))]
+    pub(crate) input_range: Option<[f64; 2]>,
+    /// Lock the extended link `l5` and its angle `g` of the planar linkages
+    /// to the target linkage, or to the locked parameters if the target
+    /// linkage is not provided
+    #[cfg_attr(not(target_arch = "wasm32"), clap(long))]
+    pub(crate) lock_ext: bool,
 }
 
 const CFG_DEF: SynCfg = SynCfg {
     seed: None,
     gen: 50,
     pop: 200,
+    stagnation: None,
     res: 180,
+    pivot: syn::Pivot::Coupler,
+    mirror: false,
+    flip: false,
+    ccw: false,
+    symmetry: false,
     on_unit: false,
     use_dd: false,
-    mode: syn::Mode::Closed,
+    use_point: false,
+    atlas_candidates: 1,
+    atlas_metric: atlas::EfdMetric::Manhattan,
+    mode: syn::Mode::Auto,
+    fixed: [None; 5],
+    input_range: None,
+    lock_ext: false,
 };
 
 impl Default for SynCfg {
@@ -104,6 +263,72 @@ impl Default for SynCfg {
     }
 }
 
+impl SynCfg {
+    /// Check the options that are not applicable to the target.
+    pub(crate) fn check(&self, target: &Target) -> Result<(), CfgErr> {
+        if self.use_point && self.use_dd {
+            Err(CfgErr(
+                "the precision points cannot be matched by the distance discrepancy",
+            ))
+        } else if self.use_point && !matches!(target, Target::Fb { .. }) {
+            Err(CfgErr(
+                "the precision points are for the planar path synthesis only",
+            ))
+        } else if self.pivot != syn::Pivot::Coupler && matches!(target, Target::MFb { .. }) {
+            Err(CfgErr("the pivot is for the path synthesis only"))
+        } else if self.input_range.is_some() && !target.resolve_mode(self.mode).is_target_open() {
+            Err(CfgErr("the input range is for the open targets only"))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Error of the options that are not applicable to the target.
+#[derive(Debug)]
+pub(crate) struct CfgErr(&'static str);
+
+impl std::fmt::Display for CfgErr {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for CfgErr {}
+
+/// The cumulative minimum (best-so-far) of the fitness history.
+pub(crate) fn best_so_far(history: &[f64]) -> Vec<f64> {
+    let best = |best: &mut f64, f: &f64| {
+        *best = best.min(*f);
+        Some(*best)
+    };
+    history.iter().scan(f64::INFINITY, best).collect()
+}
+
+/// Stop condition of the stagnation, which is met if the best fitness is not
+/// improved for `k` generations.
+pub(crate) struct Stagnation {
+    k: Option<u32>,
+    best: f64,
+    last: u64,
+}
+
+impl Stagnation {
+    pub(crate) fn new(k: Option<u32>) -> Self {
+        Self { k, best: f64::INFINITY, last: 0 }
+    }
+
+    /// Record the best fitness of the generation `gen`, return true if the
+    /// stop condition is met.
+    pub(crate) fn update(&mut self, best_f: f64, gen: u64) -> bool {
+        if best_f < self.best {
+            (self.best, self.last) = (best_f, gen);
+        }
+        // Zero generation is treated as disabled
+        (self.k).is_some_and(|k| k > 0 && gen.saturating_sub(self.last) >= k as u64)
+    }
+}
+
 #[derive(Clone)]
 pub(crate) enum Target<'a, 'b> {
     Fb {
@@ -142,6 +367,17 @@ impl<'a, 'b> Target<'a, 'b> {
     ) -> Self {
         Self::SFb { tar_curve, tar_fb, atlas }
     }
+
+    // Resolve the automatic mode by the target curve
+    pub(crate) fn resolve_mode(&self, mode: syn::Mode) -> syn::Mode {
+        match self {
+            Self::Fb { tar_curve, .. } => mode.resolve(tar_curve),
+            Self::MFb { target, .. } => {
+                mode.resolve(&target.iter().map(|(c, _)| *c).collect::<Vec<_>>())
+            }
+            Self::SFb { tar_curve, .. } => mode.resolve(tar_curve),
+        }
+    }
 }
 
 #[cfg_attr(target_arch = "wasm32", allow(dead_code))]
@@ -149,7 +385,8 @@ pub(crate) struct PSynData<'a, MDe, F: mh::ObjFunc, const D: usize> {
     pub(crate) s: SolverBox<'a, F>,
     pub(crate) tar_curve: Cow<'a, [[f64; D]]>,
     pub(crate) tar_fb: Option<MDe>,
-    pub(crate) atlas_fb: Option<(f64, MDe)>,
+    pub(crate) atlas_fb: Vec<(f64, MDe)>,
+    pool: Resume,
 }
 
 impl<'a, MDe, F, const D: usize> PSynData<'a, MDe, F, D>
@@ -158,7 +395,8 @@ where
     MDe: Default + Clone + Sync + Send + 'static,
 {
     fn new<M, S, C, const N: usize>(
-        s: SolverBox<'a, F>,
+        alg: SynAlg,
+        obj: F,
         tar_curve: Cow<'a, [[f64; D]]>,
         tar_fb: Option<M::De>,
         atlas: Option<&atlas::Atlas<M, N, D>>,
@@ -173,31 +411,59 @@ where
         efd::U<D>: efd::EfdDim<D>,
         efd::Efd<D>: Sync,
     {
-        let mut s = s
+        let pool = Resume::default();
+        let pool_w = pool.clone();
+        let mut stag = Stagnation::new(cfg.stagnation);
+        // FIXME: Try block
+        let atlas_init = (|| {
+            let atlas =
+                atlas.filter(|_| !cfg.mode.is_partial() && cfg.pivot == syn::Pivot::Coupler)?;
+            // The atlas linkages don't follow the locked parameters and the
+            // input range
+            cfg.fixed.iter().all(Option::is_none).then_some(())?;
+            cfg.input_range.is_none().then_some(())?;
+            let is_open = cfg.mode.is_target_open();
+            let (k, metric) = (cfg.atlas_candidates.max(1), cfg.atlas_metric);
+            let (candi, pool) = atlas.fetch_k(&tar_curve, is_open, k, cfg.pop, metric)?;
+            // The top-k candidates are evaluated by the objective function, the
+            // others take the atlas distances as their fitness
+            let (pool, pool_y): (Vec<_>, Vec<_>) = (pool.into_iter().enumerate())
+                .map(|(i, (f, fb))| {
+                    let de = fb.clone().denormalize();
+                    let xs = fb.into_vectorized().0;
+                    if i < k {
+                        let ys = obj.fitness(&xs);
+                        (xs, ys)
+                    } else {
+                        (xs, mh::WithProduct::new(f, de))
+                    }
+                })
+                .unzip();
+            Some((candi, pool, pool_y))
+        })();
+        let mut s = alg
+            .build_solver(obj)
             .seed(cfg.seed)
             .pop_num(cfg.pop)
-            .task(move |ctx| !stop() && ctx.gen >= cfg.gen)
-            .callback(move |ctx| callback(ctx.best.get_eval(), ctx.gen));
-        // FIXME: Try block
-        let atlas_fb = if let Some((fb, pool, pool_y)) = (|| {
-            let atlas = atlas.filter(|_| !cfg.mode.is_partial())?;
-            let (best, candi) = atlas.fetch_raw(&tar_curve, cfg.mode.is_target_open(), cfg.pop)?;
-            let pool_y = candi
-                .iter()
-                .map(|(f, fb)| mh::WithProduct::new(*f, fb.clone().denormalize()))
-                .collect();
-            let pool = candi
-                .into_iter()
-                .map(|(_, fb)| fb.into_vectorized().0)
-                .collect();
-            Some((best, pool, pool_y))
-        })() {
+            .task(move |ctx| {
+                (!stop() && ctx.gen >= cfg.gen) || stag.update(ctx.best.get_eval(), ctx.gen)
+            })
+            .callback(move |ctx| {
+                callback(ctx.best.get_eval(), ctx.gen);
+                save_pool!(pool_w, ctx);
+            });
+        let atlas_fb = if let Some((candi, pool, pool_y)) = atlas_init {
             s = s.init_pool(mh::Pool::Ready { pool, pool_y });
-            Some(fb)
+            candi
         } else {
-            None
+            Vec::new()
         };
-        Self { s, tar_curve, tar_fb, atlas_fb }
+        Self { s, tar_curve, tar_fb, atlas_fb, pool }
+    }
+
+    fn resume(mut self, pool: &Resume) -> Self {
+        resume_pool!(self, pool);
+        self
     }
 
     fn solve(self) -> MDe {
@@ -215,6 +481,7 @@ where
     pub(crate) tar_p: Vec<[f64; 2]>,
     pub(crate) tar_v: Vec<[f64; 2]>,
     pub(crate) tar_fb: Option<MFourBar>,
+    pool: Resume,
 }
 
 impl<'a, Y, F: mh::ObjFunc> MSynData<'a, Y, F>
@@ -236,12 +503,25 @@ where
         C: FnMut(f64, u64) + Send + 'a,
     {
         use mh::pareto::Best as _;
+        let pool = Resume::default();
+        let pool_w = pool.clone();
+        let mut stag = Stagnation::new(cfg.stagnation);
         let s = s
             .seed(cfg.seed)
             .pop_num(cfg.pop)
-            .task(move |ctx| !stop() && ctx.gen >= cfg.gen)
-            .callback(move |ctx| callback(ctx.best.get_eval(), ctx.gen));
-        Self { s, tar_p: tar_curve, tar_v: tar_pose, tar_fb }
+            .task(move |ctx| {
+                (!stop() && ctx.gen >= cfg.gen) || stag.update(ctx.best.get_eval(), ctx.gen)
+            })
+            .callback(move |ctx| {
+                callback(ctx.best.get_eval(), ctx.gen);
+                save_pool!(pool_w, ctx);
+            });
+        Self { s, tar_p: tar_curve, tar_v: tar_pose, tar_fb, pool }
+    }
+
+    fn resume(mut self, pool: &Resume) -> Self {
+        resume_pool!(self, pool);
+        self
     }
 
     fn solve(self) -> MFourBar {
@@ -256,6 +536,7 @@ pub(crate) enum Solver<'a> {
     DDFb(PSynData<'a, FourBar, syn::FbDDSyn, 2>),
     DDSFb(PSynData<'a, SFourBar, syn::SFbDDSyn, 3>),
     DDMFb(MSynData<'a, f64, syn::MFbDDSyn>),
+    PointFb(PSynData<'a, FourBar, syn::FbPointSyn, 2>),
 }
 
 impl<'a> Solver<'a> {
@@ -270,67 +551,165 @@ impl<'a> Solver<'a> {
         S: Fn() -> bool + Send + 'a,
         C: FnMut(f64, u64) + Send + 'a,
     {
-        macro_rules! build_solver {
-            ($ty:ident, $tar_curve:ident) => {{
-                let mut obj = syn::$ty::from_curve(&$tar_curve, cfg.mode).res(cfg.res);
+        let mut cfg = SynCfg { mode: target.resolve_mode(cfg.mode), ..cfg };
+        if cfg.lock_ext {
+            use mech::Normalized as _;
+            let norm = match &target {
+                Target::Fb { tar_fb: Some(fb), .. } => Some(NormFourBar::normalize(fb.clone())),
+                Target::MFb { tar_fb: Some(fb), .. } => {
+                    Some(NormFourBar::normalize(fb.as_fb().clone()))
+                }
+                _ => None,
+            };
+            if let Some(norm) = norm {
+                cfg.fixed[3..].copy_from_slice(&[Some(norm.l5), Some(norm.g)]);
+            }
+        }
+        macro_rules! build_obj {
+            ($ty:ident, $tar_curve:ident $(, $fixed:expr)?) => {{
+                let mut obj = syn::$ty::from_curve(&$tar_curve, cfg.mode)
+                    .res(cfg.res)
+                    .pivot(cfg.pivot)
+                    .norm_opt(syn::NormOpt { mirror: cfg.mirror, flip: cfg.flip, ccw: cfg.ccw });
                 if cfg.on_unit {
                     obj = obj.on_unit();
                 }
-                alg.build_solver(obj)
+                if cfg.symmetry {
+                    obj = obj.exploit_symmetry(&$tar_curve);
+                }
+                if let Some(range) = cfg.input_range {
+                    obj = obj.input_range(range).expect("checked by SynCfg::check()");
+                }
+                $(obj = obj.fixed($fixed);)?
+                obj
             }};
             (@ $ty:ident, $target:ident) => {{
                 let (tar_curve, tar_pose): (Vec<_>, Vec<_>) =
                     $target.into_owned().into_iter().unzip();
-                let mut obj = syn::$ty::from_uvec(&tar_curve, &tar_pose, cfg.mode).res(cfg.res);
+                let mut obj = syn::$ty::from_uvec(&tar_curve, &tar_pose, cfg.mode)
+                    .res(cfg.res)
+                    .fixed(&cfg.fixed);
                 if cfg.on_unit {
                     obj = obj.on_unit();
                 }
-                (alg.build_solver(obj), tar_curve, tar_pose)
+                if let Some(range) = cfg.input_range {
+                    obj = obj.input_range(range).expect("checked by SynCfg::check()");
+                }
+                (obj, tar_curve, tar_pose)
             }};
         }
         match target {
             Target::Fb { tar_curve, tar_fb, atlas } => {
-                if cfg.use_dd {
-                    let s = build_solver!(FbDDSyn, tar_curve);
+                // The options are checked by `SynCfg::check()`
+                if cfg.use_point {
+                    // The atlas is searched by the EFD, which is not for the points
+                    let obj = build_obj!(FbPointSyn, tar_curve, &cfg.fixed);
+                    let atlas = None::<&atlas::FbAtlas>;
+                    Self::PointFb(PSynData::new(
+                        alg, obj, tar_curve, tar_fb, atlas, cfg, stop, callback,
+                    ))
+                } else if cfg.use_dd {
+                    let obj = build_obj!(FbDDSyn, tar_curve, &cfg.fixed);
                     Self::DDFb(PSynData::new(
-                        s, tar_curve, tar_fb, atlas, cfg, stop, callback,
+                        alg, obj, tar_curve, tar_fb, atlas, cfg, stop, callback,
                     ))
                 } else {
-                    let s = build_solver!(FbSyn, tar_curve);
+                    let obj = build_obj!(FbSyn, tar_curve, &cfg.fixed);
                     Self::Fb(PSynData::new(
-                        s, tar_curve, tar_fb, atlas, cfg, stop, callback,
+                        alg, obj, tar_curve, tar_fb, atlas, cfg, stop, callback,
                     ))
                 }
             }
             Target::MFb { target, tar_fb } => {
                 if cfg.use_dd {
-                    let (s, tar_curve, tar_pose) = build_solver!(@MFbDDSyn, target);
+                    let (obj, tar_curve, tar_pose) = build_obj!(@MFbDDSyn, target);
                     Self::DDMFb(MSynData::new(
-                        s, tar_curve, tar_pose, tar_fb, cfg, stop, callback,
+                        alg.build_solver(obj),
+                        tar_curve,
+                        tar_pose,
+                        tar_fb,
+                        cfg,
+                        stop,
+                        callback,
                     ))
                 } else {
-                    let (s, tar_curve, tar_pose) = build_solver!(@MFbSyn, target);
+                    let (obj, tar_curve, tar_pose) = build_obj!(@MFbSyn, target);
                     Self::MFb(MSynData::new(
-                        s, tar_curve, tar_pose, tar_fb, cfg, stop, callback,
+                        alg.build_solver(obj),
+                        tar_curve,
+                        tar_pose,
+                        tar_fb,
+                        cfg,
+                        stop,
+                        callback,
                     ))
                 }
             }
             Target::SFb { tar_curve, tar_fb, atlas } => {
                 if cfg.use_dd {
-                    let s = build_solver!(SFbDDSyn, tar_curve);
+                    let obj = build_obj!(SFbDDSyn, tar_curve);
                     Self::DDSFb(PSynData::new(
-                        s, tar_curve, tar_fb, atlas, cfg, stop, callback,
+                        alg, obj, tar_curve, tar_fb, atlas, cfg, stop, callback,
                     ))
                 } else {
-                    let s = build_solver!(SFbSyn, tar_curve);
+                    let obj = build_obj!(SFbSyn, tar_curve);
                     Self::SFb(PSynData::new(
-                        s, tar_curve, tar_fb, atlas, cfg, stop, callback,
+                        alg, obj, tar_curve, tar_fb, atlas, cfg, stop, callback,
                     ))
                 }
             }
         }
     }
 
+    pub(crate) fn atlas_candidates(&self) -> Vec<io::Fb> {
+        macro_rules! candi {
+            ($s:ident, $ty:ident) => {
+                $s.atlas_fb
+                    .iter()
+                    .map(|(_, fb)| io::Fb::$ty(fb.clone()))
+                    .collect()
+            };
+        }
+        match self {
+            Self::Fb(s) => candi!(s, P),
+            Self::SFb(s) => candi!(s, S),
+            Self::DDFb(s) => candi!(s, P),
+            Self::DDSFb(s) => candi!(s, S),
+            Self::PointFb(s) => candi!(s, P),
+            Self::MFb(_) | Self::DDMFb(_) => Vec::new(),
+        }
+    }
+
+    /// The slot of the latest population, which is updated during the solving.
+    #[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
+    pub(crate) fn pool(&self) -> Resume {
+        match self {
+            Self::Fb(s) => s.pool.clone(),
+            Self::MFb(s) => s.pool.clone(),
+            Self::SFb(s) => s.pool.clone(),
+            Self::DDFb(s) => s.pool.clone(),
+            Self::DDSFb(s) => s.pool.clone(),
+            Self::DDMFb(s) => s.pool.clone(),
+            Self::PointFb(s) => s.pool.clone(),
+        }
+    }
+
+    /// Start from the population of the previous chunk instead of the random
+    /// or atlas initialization. The population is ignored if the solver type
+    /// is different.
+    #[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
+    pub(crate) fn resume(self, pool: &Resume) -> Self {
+        match self {
+            Self::Fb(s) => Self::Fb(s.resume(pool)),
+            Self::MFb(s) => Self::MFb(s.resume(pool)),
+            Self::SFb(s) => Self::SFb(s.resume(pool)),
+            Self::DDFb(s) => Self::DDFb(s.resume(pool)),
+            Self::DDSFb(s) => Self::DDSFb(s.resume(pool)),
+            Self::DDMFb(s) => Self::DDMFb(s.resume(pool)),
+            Self::PointFb(s) => Self::PointFb(s.resume(pool)),
+        }
+    }
+
     pub(crate) fn solve(self) -> io::Fb {
         match self {
             Self::Fb(s) => io::Fb::P(s.solve()),
@@ -339,6 +718,133 @@ impl<'a> Solver<'a> {
             Self::DDFb(s) => io::Fb::P(s.solve()),
             Self::DDSFb(s) => io::Fb::S(s.solve()),
             Self::DDMFb(s) => io::Fb::M(s.solve()),
+            Self::PointFb(s) => io::Fb::P(s.solve()),
         }
     }
 }
+
+#[test]
+fn preset_ranges() {
+    // The lower bound of the percentage widgets
+    const MIN: f64 = 1e-2;
+    for (name, alg) in PRESETS {
+        let (params, prob) = match alg {
+            SynAlg::De(s) => (vec![s.f, s.cross], vec![s.cross]),
+            SynAlg::Fa(s) => (vec![s.alpha, s.beta_min, s.gamma], vec![]),
+            SynAlg::Pso(s) => (vec![s.cognition, s.social, s.velocity], vec![]),
+            SynAlg::Rga(s) => (
+                vec![s.cross, s.mutate, s.win, s.delta],
+                vec![s.cross, s.mutate, s.win],
+            ),
+            SynAlg::Tlbo(_) => (vec![], vec![]),
+        };
+        assert!(params.iter().all(|v| *v >= MIN), "{} {name}", alg.abbr());
+        assert!(prob.iter().all(|v| *v <= 1.), "{} {name}", alg.abbr());
+        assert_eq!(alg.presets().filter(|(n, _)| n == name).count(), 1);
+    }
+}
+
+#[test]
+fn stagnation_stop_early() {
+    use four_bar::mech::CurveGen as _;
+    const GEN: u64 = 1000;
+    let curve = FourBar::example().curve(90);
+    let target = Target::fb(curve.into(), None, None);
+    let cfg = SynCfg {
+        seed: Some(0),
+        gen: GEN,
+        pop: 20,
+        res: 90,
+        stagnation: Some(10),
+        ..SynCfg::default()
+    };
+    let last = Arc::new(Mutex::new(0));
+    let last_w = last.clone();
+    let s = Solver::new(
+        SynAlg::rga(),
+        target,
+        cfg,
+        || false,
+        move |_, gen| {
+            *last_w.lock().unwrap() = gen;
+        },
+    );
+    s.solve();
+    let last = *last.lock().unwrap();
+    assert!(last < GEN, "{last}");
+}
+
+#[test]
+fn point_solver_selected() {
+    use four_bar::mech::{CurveGen as _, IntoVectorized as _, Normalized as _};
+    use mh::{Fitness as _, ObjFunc as _};
+    let fb = FourBar::example();
+    let pts = [0.3, 1.5, 2.7, 3.9, 5.1].map(|t| fb.pos(t).unwrap()[4]);
+    let target = Target::fb(pts.to_vec().into(), None, None);
+    let cfg = SynCfg {
+        seed: Some(0),
+        gen: 1,
+        pop: 4,
+        res: 90,
+        mode: syn::Mode::Closed,
+        use_point: true,
+        ..SynCfg::default()
+    };
+    assert!(cfg.check(&target).is_ok());
+    let Solver::PointFb(s) = Solver::new(
+        SynAlg::de(),
+        target.clone(),
+        cfg.clone(),
+        || false,
+        |_, _| (),
+    ) else {
+        panic!("the precision point solver is not selected");
+    };
+    // The points are matched by the point fitness, not the EFD
+    let s = s.s.solve();
+    let (xs, _) = NormFourBar::normalize(fb.clone()).into_vectorized();
+    let err = s.func().fitness(&xs).eval();
+    assert!(err < 1e-6, "{err}");
+    // Not applicable options
+    let dd = SynCfg { use_dd: true, ..cfg.clone() };
+    assert!(dd.check(&target).is_err());
+    let range = SynCfg {
+        use_point: false,
+        input_range: Some([0.5, 2.5]),
+        ..cfg.clone()
+    };
+    assert!(range.check(&target).is_err());
+    let target = Target::sfb(SFourBar::example().curve(90).into(), None, None);
+    assert!(cfg.check(&target).is_err());
+    let target = Target::mfb(MFourBar::example().pose_zipped(90).into(), None);
+    assert!(cfg.check(&target).is_err());
+    let pivot = SynCfg { use_point: false, pivot: syn::Pivot::Driver, ..cfg };
+    assert!(pivot.check(&target).is_err());
+}
+
+#[test]
+fn atlas_candidates_not_worse() {
+    use four_bar::mech::CurveGen as _;
+    let atlas = atlas::FbAtlas::make(atlas::Cfg::new().size(200).res(90).seed(Some(0u64).into()));
+    let (_, fb) = &FourBar::benchmarks()[1];
+    let curve = fb.curve(90);
+    let cost = |k| {
+        let target = Target::fb(Cow::Borrowed(&curve), None, Some(&atlas));
+        let cfg = SynCfg {
+            seed: Some(0),
+            gen: 10,
+            pop: 20,
+            res: 90,
+            atlas_candidates: k,
+            ..SynCfg::default()
+        };
+        let Solver::Fb(s) = Solver::new(SynAlg::de(), target, cfg, || false, |_, _| ()) else {
+            unreachable!()
+        };
+        s.s.solve().into_err_result().0
+    };
+    let best = cost(1);
+    for k in [3, 10] {
+        assert!(cost(k) <= best, "{k}");
+    }
+}
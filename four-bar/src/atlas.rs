@@ -1,9 +1,12 @@
 //! Create a atlas database for four-bar linkages.
-pub use self::distr::{Code, Distr};
+pub use self::distr::{Code, Distr, Offset, Rearrange};
 use super::{NormFourBar, SNormFourBar};
-use mh::random::{Rng, SeedOpt};
 #[cfg(feature = "rayon")]
 use mh::rayon::prelude::*;
+use mh::{
+    rand::distributions::Uniform,
+    random::{Rng, SeedOpt},
+};
 use ndarray::*;
 pub use ndarray_npy::{ReadNpzError, WriteNpzError};
 use std::{marker::PhantomData, sync::Mutex};
@@ -15,6 +18,11 @@ pub type FbAtlas = Atlas<NormFourBar, 5, 2>;
 /// Spherical four-bar atlas type.
 pub type SFbAtlas = Atlas<SNormFourBar, 6, 3>;
 
+/// Maximum number of the failed samples in a row when making an atlas.
+///
+/// See [`Atlas::try_make_with()`].
+pub const MAX_STALL: usize = 10000;
+
 fn to_arr<A, S, D>(stack: Mutex<Vec<ArrayBase<S, D>>>, n: usize) -> Array<A, D::Larger>
 where
     A: Clone,
@@ -48,6 +56,61 @@ where
     efd::Efd::from_parts_unchecked(coeffs, efd::GeoVar::identity())
 }
 
+/// Distance metric between the EFD coefficients.
+///
+/// The Manhattan distance is robust for the closed curves, which is the
+/// default choice. The Euclidean distance penalizes the large differences
+/// more, and the cosine distance ignores the magnitude of the coefficients,
+/// which is more tolerant for the open curves with uncertain lengths.
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub enum EfdMetric {
+    /// Manhattan distance (L1 norm)
+    #[default]
+    Manhattan,
+    /// Euclidean distance (L2 norm)
+    Euclidean,
+    /// Cosine distance
+    Cosine,
+}
+
+impl EfdMetric {
+    /// Metric list.
+    pub const LIST: [Self; 3] = [Self::Manhattan, Self::Euclidean, Self::Cosine];
+
+    /// Get the metric names.
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::Manhattan => "Manhattan",
+            Self::Euclidean => "Euclidean",
+            Self::Cosine => "Cosine",
+        }
+    }
+
+    /// Distance between two coefficient arrays in the same shape.
+    pub fn dist(&self, a: ArrayView2<f64>, b: ArrayView2<f64>) -> f64 {
+        match self {
+            Self::Manhattan => Zip::from(a)
+                .and(b)
+                .fold(0., |acc, a, b| acc + (a - b).abs()),
+            Self::Euclidean => Zip::from(a)
+                .and(b)
+                .fold(0., |acc, a, b| acc + (a - b).powi(2))
+                .sqrt(),
+            Self::Cosine => {
+                let dot = Zip::from(a).and(b).fold(0., |acc, a, b| acc + a * b);
+                let norm = a.mapv(|x| x * x).sum().sqrt() * b.mapv(|x| x * x).sum().sqrt();
+                if norm == 0. {
+                    1.
+                } else {
+                    1. - dot / norm
+                }
+            }
+        }
+    }
+}
+
 /// Atlas generation config.
 #[derive(Clone)]
 pub struct Cfg {
@@ -61,6 +124,16 @@ pub struct Cfg {
     pub harmonic: usize,
     /// Random seed
     pub seed: SeedOpt,
+    /// Store the sampled offset (unnormalized) linkages
+    ///
+    /// Only supported by the planar atlas.
+    pub with_offset: bool,
+    /// Sampling range of the normalized code, default to the bound of the
+    /// synthesis (see [`FromVectorized::BOUND`](crate::mech::FromVectorized))
+    ///
+    /// The length should be the same as the code, each range is `[min, max)`.
+    /// The range is checked by [`Atlas::try_make_with()`].
+    pub range: Option<Vec<[f64; 2]>>,
 }
 
 impl Default for Cfg {
@@ -78,9 +151,26 @@ impl Cfg {
             res: 720,
             harmonic: 20,
             seed: SeedOpt::Entropy,
+            with_offset: false,
+            range: None,
         }
     }
 
+    /// Sampling range of the normalized code.
+    ///
+    /// The code of the planar linkages is `[l1, l3, l4, l5, g]`, and the
+    /// spherical linkages is `[l1, l2, l3, l4, l5, g]`.
+    ///
+    /// ```
+    /// use four_bar::atlas::*;
+    ///
+    /// let cfg = Cfg::new().range([[1., 2.], [1., 2.], [1., 2.], [0.5, 1.], [0., 1.]]);
+    /// assert_eq!(cfg.range.unwrap().len(), 5);
+    /// ```
+    pub fn range(self, range: impl Into<Vec<[f64; 2]>>) -> Self {
+        Self { range: Some(range.into()), ..self }
+    }
+
     mh::impl_builders! {
         /// Open curve
         fn is_open(bool)
@@ -92,6 +182,8 @@ impl Cfg {
         fn harmonic(usize)
         /// Random seed
         fn seed(SeedOpt)
+        /// Store the sampled offset (unnormalized) linkages
+        fn with_offset(bool)
     }
 }
 
@@ -100,6 +192,7 @@ pub struct Atlas<M, const N: usize, const D: usize> {
     fb: Array2<f64>,
     stat: Array1<u8>,
     efd: Array3<f64>,
+    offset: Option<Array2<f64>>,
     _marker: PhantomData<M>,
 }
 
@@ -109,6 +202,7 @@ impl<M, const N: usize, const D: usize> Clone for Atlas<M, N, D> {
             fb: self.fb.clone(),
             stat: self.stat.clone(),
             efd: self.efd.clone(),
+            offset: self.offset.clone(),
             _marker: PhantomData,
         }
     }
@@ -124,6 +218,7 @@ where
             fb: Array2::default([0, N]),
             stat: Array1::default(0),
             efd: Array3::default([0, 0, D * 2]),
+            offset: None,
             _marker: PhantomData,
         }
     }
@@ -135,6 +230,10 @@ where
     efd::U<D>: efd::EfdDim<D>,
 {
     /// Takes time to generate atlas data.
+    ///
+    /// # Panics
+    ///
+    /// Panic if the sampling range is invalid, see [`Self::try_make_with()`].
     pub fn make(cfg: Cfg) -> Self
     where
         M: Send,
@@ -144,48 +243,237 @@ where
     }
 
     /// Takes time to generate atlas data with a callback function.
+    ///
+    /// # Panics
+    ///
+    /// Panic if the sampling range is invalid, see [`Self::try_make_with()`].
     pub fn make_with<CB>(cfg: Cfg, callback: CB) -> Self
     where
         M: Send,
         CB: Fn(usize) + Sync + Send,
         [f64; D]: Sync + Send,
     {
-        let Cfg { is_open, size, res, harmonic, seed } = cfg;
+        Self::try_make_with(cfg, callback).expect("invalid sampling range")
+    }
+
+    /// Takes time to generate atlas data with a callback function.
+    ///
+    /// The sampled links are rearranged to generate the curve type of the
+    /// atlas if possible (see [`Rearrange`]), unless the code is out of the
+    /// sampling range.
+    ///
+    /// Return [`Error::ParamLength`](crate::Error::ParamLength) if the length
+    /// of the sampling range is different from the code, and
+    /// [`Error::InvalidRange`](crate::Error::InvalidRange) if any range is
+    /// empty or not finite, or no valid linkage is found in the range after
+    /// [`MAX_STALL`] failed samples.
+    ///
+    /// ```
+    /// use four_bar::{atlas::*, Error};
+    ///
+    /// let cfg = Cfg::new().size(10).res(90).range([[1., 2.]; 4]);
+    /// let e = FbAtlas::try_make_with(cfg, |_| ()).err();
+    /// assert_eq!(e, Some(Error::ParamLength { expected: 5, found: 4 }));
+    /// let cfg = Cfg::new().size(10).res(90).range([[1., 1.]; 5]);
+    /// let e = FbAtlas::try_make_with(cfg, |_| ()).err();
+    /// assert_eq!(e, Some(Error::InvalidRange));
+    /// // The ground link is too long to be assembled
+    /// let range = [[100., 200.], [0.1, 0.2], [0.1, 0.2], [0.1, 1.], [0., 1.]];
+    /// let cfg = Cfg::new().size(10).res(90).range(range);
+    /// let e = FbAtlas::try_make_with(cfg, |_| ()).err();
+    /// assert_eq!(e, Some(Error::InvalidRange));
+    /// ```
+    pub fn try_make_with<CB>(cfg: Cfg, callback: CB) -> Result<Self, crate::Error>
+    where
+        M: Send,
+        CB: Fn(usize) + Sync + Send,
+        [f64; D]: Sync + Send,
+    {
+        let Cfg {
+            is_open,
+            size,
+            res,
+            harmonic,
+            seed,
+            with_offset,
+            range,
+        } = cfg;
+        let with_offset = with_offset && !M::OFFSET_BOUND.is_empty();
+        let distr = match range {
+            Some(range) => {
+                let found = range.len();
+                let range = <[[f64; 2]; N]>::try_from(range)
+                    .map_err(|_| crate::Error::ParamLength { expected: N, found })?;
+                if !range
+                    .iter()
+                    .all(|[min, max]| min.is_finite() && max.is_finite() && min < max)
+                {
+                    return Err(crate::Error::InvalidRange);
+                }
+                Distr::<M, N>::with_bound(range)
+            }
+            None => Distr::new(),
+        };
         let mut rng = Rng::new(seed);
         let fb_stack = Mutex::new(Vec::with_capacity(size));
         let stat_stack = Mutex::new(Vec::with_capacity(size));
         let efd_stack = Mutex::new(Vec::with_capacity(size));
+        let offset_stack = Mutex::new(Vec::with_capacity(if with_offset { size } else { 0 }));
+        // Number of the samples without any valid linkage in a row
+        let mut stall = 0;
         loop {
             let len = efd_stack.lock().unwrap().len();
-            let n = (size - len) / 2;
+            let n = ((size - len) / 2).max(1);
             #[cfg(not(feature = "rayon"))]
             let iter = rng.stream(n).into_iter();
             #[cfg(feature = "rayon")]
             let iter = rng.stream(n).into_par_iter();
-            iter.flat_map(|mut rng| rng.sample(Distr::<M, N>::new()))
-                .filter_map(|fb| fb.get_curve(res, is_open).map(|c| (c, fb)))
-                .filter(|(c, _)| c.len() > 1)
-                .for_each(|(curve, fb)| {
-                    let efd = efd::Efd::from_curve_harmonic(curve, is_open, harmonic);
-                    efd_stack.lock().unwrap().push(efd_to_arr(efd));
-                    let (code, stat) = fb.to_code();
-                    let mut stack = fb_stack.lock().unwrap();
-                    stack.push(arr1(&code));
-                    callback(stack.len());
-                    stat_stack.lock().unwrap().push(arr0(stat));
-                });
-            if efd_stack.lock().unwrap().len() >= size {
+            iter.flat_map(|mut rng| {
+                let fbs = rng.sample(distr.curve_type(is_open));
+                // Each state has its own pose
+                fbs.into_iter()
+                    .map(|fb| {
+                        let offset = if with_offset {
+                            (M::OFFSET_BOUND.iter())
+                                .map(|&[u, l]| rng.sample(Uniform::new(u, l)))
+                                .collect()
+                        } else {
+                            Vec::new()
+                        };
+                        (fb, offset)
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .filter_map(|(fb, offset)| fb.get_curve(res, is_open).map(|c| (c, fb, offset)))
+            .filter(|(c, ..)| c.len() > 1)
+            .for_each(|(curve, fb, offset)| {
+                let efd = efd::Efd::from_curve_harmonic(curve, is_open, harmonic);
+                let (code, stat) = fb.to_code();
+                // Keep the rows of the arrays in the same order
+                let mut efd_stack = efd_stack.lock().unwrap();
+                efd_stack.push(efd_to_arr(efd));
+                fb_stack.lock().unwrap().push(arr1(&code));
+                stat_stack.lock().unwrap().push(arr0(stat));
+                if with_offset {
+                    offset_stack.lock().unwrap().push(arr1(&offset));
+                }
+                callback(efd_stack.len());
+            });
+            let found = efd_stack.lock().unwrap().len();
+            if found >= size {
                 break;
+            } else if found == len {
+                stall += n;
+                if stall >= MAX_STALL {
+                    return Err(crate::Error::InvalidRange);
+                }
+            } else {
+                stall = 0;
             }
         }
         let fb = to_arr(fb_stack, size);
         let stat = to_arr(stat_stack, size);
         let efd = to_arr(efd_stack, size);
-        Self { fb, stat, efd, _marker: PhantomData }
+        let offset = with_offset.then(|| to_arr(offset_stack, size));
+        Ok(Self { fb, stat, efd, offset, _marker: PhantomData })
+    }
+
+    /// Create atlas from a list of normalized linkages.
+    ///
+    /// The EFD coefficients are computed with the `is_open`, `res` and
+    /// `harmonic` options of the `cfg`, and the linkages that cannot generate
+    /// the curve are skipped.
+    ///
+    /// ```
+    /// use four_bar::{atlas::*, mech::Normalized as _, FourBar, NormFourBar};
+    ///
+    /// let fb = NormFourBar::normalize(FourBar::example());
+    /// let atlas = FbAtlas::from_norm_slice(&[fb.clone(), fb], &Cfg::new().harmonic(10));
+    /// assert_eq!(atlas.len(), 2);
+    /// assert_eq!(atlas.harmonic(), 10);
+    /// ```
+    pub fn from_norm_slice(fbs: &[M], cfg: &Cfg) -> Self
+    where
+        M: Clone + Sync,
+    {
+        let Cfg { is_open, res, harmonic, .. } = *cfg;
+        #[cfg(not(feature = "rayon"))]
+        let iter = fbs.iter();
+        #[cfg(feature = "rayon")]
+        let iter = fbs.par_iter();
+        let rows = iter
+            .filter_map(|fb| {
+                let curve = fb.get_curve(res, is_open).filter(|c| c.len() > 1)?;
+                let efd = efd::Efd::from_curve_harmonic(curve, is_open, harmonic);
+                let (code, stat) = fb.clone().to_code();
+                Some((arr1(&code), arr0(stat), efd_to_arr(efd)))
+            })
+            .collect::<Vec<_>>();
+        let n = rows.len();
+        if n == 0 {
+            return Self::default();
+        }
+        let mut fb_stack = Vec::with_capacity(n);
+        let mut stat_stack = Vec::with_capacity(n);
+        let mut efd_stack = Vec::with_capacity(n);
+        for (fb, stat, efd) in rows {
+            fb_stack.push(fb);
+            stat_stack.push(stat);
+            efd_stack.push(efd);
+        }
+        let fb = to_arr(Mutex::new(fb_stack), n);
+        let stat = to_arr(Mutex::new(stat_stack), n);
+        let efd = to_arr(Mutex::new(efd_stack), n);
+        Self { fb, stat, efd, offset: None, _marker: PhantomData }
+    }
+
+    /// Read atlas from CSV file.
+    ///
+    /// Each row is a linkage code followed by its state number, the same as
+    /// [`Self::write_csv()`]. The EFD coefficients are recomputed, see
+    /// [`Self::from_norm_slice()`].
+    #[cfg(feature = "csv")]
+    pub fn read_csv<R>(r: R, cfg: &Cfg) -> Result<Self, crate::Error>
+    where
+        R: std::io::Read,
+        M: Clone + Sync,
+    {
+        let fbs = crate::csv::from_reader::<_, Vec<f64>>(r)?
+            .into_iter()
+            .map(|row| match row.as_slice() {
+                [code @ .., stat] if code.len() == N => {
+                    if stat.fract() != 0. || crate::mech::Stat::try_from(*stat as u8).is_err() {
+                        return Err(crate::Error::InvalidStat);
+                    }
+                    Ok(M::from_code(code, *stat as u8))
+                }
+                _ => Err(crate::Error::ParamLength { expected: N + 1, found: row.len() }),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self::from_norm_slice(&fbs, cfg))
+    }
+
+    /// Read atlas from RON file.
+    ///
+    /// The file is a list of the normalized linkages, the EFD coefficients are
+    /// computed, see [`Self::from_norm_slice()`].
+    #[cfg(feature = "ron")]
+    pub fn read_ron<R>(r: R, cfg: &Cfg) -> Result<Self, crate::Error>
+    where
+        R: std::io::Read,
+        M: Clone + Sync + serde::de::DeserializeOwned,
+    {
+        let fbs = ron::de::from_reader::<_, Vec<M>>(r)?;
+        if fbs.is_empty() {
+            return Err(crate::Error::EmptyData);
+        }
+        Ok(Self::from_norm_slice(&fbs, cfg))
     }
 
     /// Read atlas from NPZ file.
-    pub fn read<R>(r: R) -> Result<Self, ReadNpzError>
+    ///
+    /// The atlas without the offset data is loaded as normalized-only.
+    pub fn read<R>(r: R) -> Result<Self, crate::Error>
     where
         R: std::io::Read + std::io::Seek,
     {
@@ -193,7 +481,7 @@ where
         macro_rules! impl_read {
             ($r:ident, $($field:ident),+) => {{
                 $(let $field = $r.by_name(stringify!($field))?;)+
-                Self { $($field),+, _marker: PhantomData }
+                Self { $($field),+, offset: None, _marker: PhantomData }
             }};
         }
         macro_rules! impl_check {
@@ -201,16 +489,20 @@ where
                 let actual = $actual;
                 let expect = $expect;
                 if actual != expect {
-                    return Err(ReadNpzError::Npy(ndarray_npy::ReadNpyError::WrongNdim(
-                        Some(expect),
-                        actual,
-                    )));
+                    let e = ndarray_npy::ReadNpyError::WrongNdim(Some(expect), actual);
+                    return Err(ReadNpzError::Npy(e).into());
                 }
             };
         }
-        let atlas = impl_read!(r, fb, stat, efd);
+        let mut atlas = impl_read!(r, fb, stat, efd);
         impl_check!(atlas.fb.len_of(Axis(1)), N);
         impl_check!(atlas.efd.len_of(Axis(2)), D * 2);
+        if (r.names()?.iter()).any(|name| name.trim_end_matches(".npy") == "offset") {
+            let offset = r.by_name::<OwnedRepr<f64>, Ix2>("offset")?;
+            impl_check!(offset.len_of(Axis(1)), M::OFFSET_BOUND.len());
+            impl_check!(offset.nrows(), atlas.len());
+            atlas.offset = Some(offset);
+        }
         Ok(atlas)
     }
 
@@ -228,6 +520,8 @@ where
     /// Get the n-nearest four-bar linkages from a target curve.
     ///
     /// This method will keep the dimensional variables without transform.
+    /// The distance is measured by the default metric, see
+    /// [`Self::fetch_raw_with_metric()`].
     #[allow(clippy::type_complexity)]
     pub fn fetch_raw(
         &self,
@@ -235,6 +529,48 @@ where
         is_open: bool,
         size: usize,
     ) -> Option<((f64, M::De), Vec<(f64, M)>)>
+    where
+        efd::Efd<D>: Sync,
+    {
+        self.fetch_raw_with_metric(target, is_open, size, EfdMetric::default())
+    }
+
+    /// Get the n-nearest four-bar linkages from a target curve, measured by
+    /// the `metric`.
+    ///
+    /// See also [`Self::fetch_raw()`].
+    #[allow(clippy::type_complexity)]
+    pub fn fetch_raw_with_metric(
+        &self,
+        target: &[[f64; D]],
+        is_open: bool,
+        size: usize,
+        metric: EfdMetric,
+    ) -> Option<((f64, M::De), Vec<(f64, M)>)>
+    where
+        efd::Efd<D>: Sync,
+    {
+        let (mut first, pool) = self.fetch_k(target, is_open, 1, size, metric)?;
+        Some((first.swap_remove(0), pool))
+    }
+
+    /// Get the `k` nearest transformed candidates and the `size` nearest
+    /// normalized linkages from a target curve, measured by the `metric`.
+    ///
+    /// The candidates are sorted by their distance, and the number of them is
+    /// at least one if the atlas is not empty.
+    ///
+    /// If the atlas has the offset data, the candidates are placed at their
+    /// stored positions instead of being transformed to the target.
+    #[allow(clippy::type_complexity)]
+    pub fn fetch_k(
+        &self,
+        target: &[[f64; D]],
+        is_open: bool,
+        k: usize,
+        size: usize,
+        metric: EfdMetric,
+    ) -> Option<(Vec<(f64, M::De)>, Vec<(f64, M)>)>
     where
         efd::Efd<D>: Sync,
     {
@@ -242,32 +578,29 @@ where
         let res = target.len();
         let target = efd::Efd::from_curve_harmonic(target, is_open, self.harmonic());
         let geo = target.as_geo();
-        #[cfg(not(feature = "rayon"))]
-        let iter = self.efd.axis_iter(Axis(0));
-        #[cfg(feature = "rayon")]
-        let iter = self.efd.axis_iter(Axis(0)).into_par_iter();
-        let dis = iter
-            .map(|arr| target.err(&arr_to_efd(arr)))
-            .collect::<Vec<_>>();
-        if size == 1 {
+        let dis = self.distances(&target, metric);
+        if k <= 1 && size == 1 {
             let (first_i, err) = dis
                 .into_iter()
                 .enumerate()
                 .min_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap())?;
-            let first = (err, self.pick(first_i, geo, is_open, res));
+            let first = vec![(err, self.pick(first_i, geo, is_open, res))];
             let pool = vec![(err, self.pick_norm(first_i))];
             return Some((first, pool));
         }
         let mut ind = (0..dis.len()).collect::<Vec<_>>();
         ind.sort_by(|&a, &b| dis[a].partial_cmp(&dis[b]).unwrap());
-        let first_i = *ind.first()?;
-        let first = (dis[first_i], self.pick(first_i, geo, is_open, res));
+        let candi = ind
+            .iter()
+            .take(k.max(1))
+            .map(|&i| (dis[i], self.pick(i, geo, is_open, res)))
+            .collect();
         let pool = ind
             .into_iter()
             .take(size)
             .map(|i| (dis[i], self.pick_norm(i)))
             .collect();
-        Some((first, pool))
+        Some((candi, pool))
     }
 
     /// Get the nearest four-bar linkage from a target curve.
@@ -275,30 +608,59 @@ where
     where
         efd::Efd<D>: Sync,
     {
-        self.fetch_raw(target, is_open, 1).map(|(first, _)| first)
+        self.fetch_1st_with_metric(target, is_open, EfdMetric::default())
+    }
+
+    /// Get the nearest four-bar linkage from a target curve, measured by the
+    /// `metric`.
+    pub fn fetch_1st_with_metric(
+        &self,
+        target: &[[f64; D]],
+        is_open: bool,
+        metric: EfdMetric,
+    ) -> Option<(f64, M::De)>
+    where
+        efd::Efd<D>: Sync,
+    {
+        self.fetch_raw_with_metric(target, is_open, 1, metric)
+            .map(|(first, _)| first)
     }
 
     /// Get the n-nearest four-bar linkages from a target curve.
     ///
     /// Slower than [`Self::fetch_1st()`].
+    ///
+    /// If the atlas has the offset data, the linkages are placed at their
+    /// stored positions instead of being transformed to the target.
     pub fn fetch(&self, target: &[[f64; D]], is_open: bool, size: usize) -> Vec<(f64, M::De)>
+    where
+        efd::Efd<D>: Sync,
+    {
+        self.fetch_with_metric(target, is_open, size, EfdMetric::default())
+    }
+
+    /// Get the n-nearest four-bar linkages from a target curve, measured by
+    /// the `metric`.
+    ///
+    /// See also [`Self::fetch()`].
+    pub fn fetch_with_metric(
+        &self,
+        target: &[[f64; D]],
+        is_open: bool,
+        size: usize,
+        metric: EfdMetric,
+    ) -> Vec<(f64, M::De)>
     where
         efd::Efd<D>: Sync,
     {
         if self.is_empty() {
             return Vec::new();
         } else if size == 1 {
-            return Vec::from_iter(self.fetch_1st(target, is_open));
+            return Vec::from_iter(self.fetch_1st_with_metric(target, is_open, metric));
         }
         let res = target.len();
         let target = efd::Efd::from_curve_harmonic(target, is_open, self.harmonic());
-        #[cfg(not(feature = "rayon"))]
-        let iter = self.efd.axis_iter(Axis(0));
-        #[cfg(feature = "rayon")]
-        let iter = self.efd.axis_iter(Axis(0)).into_par_iter();
-        let dis = iter
-            .map(|arr| target.err(&arr_to_efd(arr)))
-            .collect::<Vec<_>>();
+        let dis = self.distances(&target, metric);
         let mut ind = (0..self.len()).collect::<Vec<_>>();
         ind.sort_by(|&a, &b| dis[a].partial_cmp(&dis[b]).unwrap());
         ind.into_iter()
@@ -307,6 +669,15 @@ where
             .collect()
     }
 
+    fn distances(&self, target: &efd::Efd<D>, metric: EfdMetric) -> Vec<f64> {
+        let target = efd_to_arr(target.clone());
+        #[cfg(not(feature = "rayon"))]
+        let iter = self.efd.axis_iter(Axis(0));
+        #[cfg(feature = "rayon")]
+        let iter = self.efd.axis_iter(Axis(0)).into_par_iter();
+        iter.map(|arr| metric.dist(target.view(), arr)).collect()
+    }
+
     fn pick_norm(&self, i: usize) -> M {
         M::from_code(self.fb.row(i).as_slice().unwrap(), self.stat[i])
     }
@@ -319,6 +690,9 @@ where
         res: usize,
     ) -> M::De {
         let fb = self.pick_norm(i);
+        if let Some(offset) = &self.offset {
+            return fb.denorm_offset(offset.row(i).as_slice().unwrap());
+        }
         let curve = fb.get_curve(res, is_open).unwrap();
         let efd = efd::Efd::from_curve(curve, is_open);
         fb.trans_denorm(&efd.as_geo().to(geo))
@@ -327,22 +701,40 @@ where
 
 impl<M, const N: usize, const D: usize> Atlas<M, N, D> {
     /// Write atlas to NPZ file.
-    pub fn write<W>(&self, w: W) -> Result<(), WriteNpzError>
+    pub fn write<W>(&self, w: W) -> Result<(), crate::Error>
     where
         W: std::io::Write + std::io::Seek,
     {
         let mut w = ndarray_npy::NpzWriter::new_compressed(w);
         macro_rules! impl_write {
             ($w:ident, $($field:ident),+) => {
-                let Self { $($field),+, _marker: _ } = self;
+                let Self { $($field),+, offset: _, _marker: _ } = self;
                 $($w.add_array(stringify!($field), $field)?;)+
             };
         }
         impl_write!(w, fb, stat, efd);
+        if let Some(offset) = &self.offset {
+            w.add_array("offset", offset)?;
+        }
         w.finish()?;
         Ok(())
     }
 
+    /// Write atlas to CSV file.
+    ///
+    /// Each row is a linkage code followed by its state number. The EFD
+    /// coefficients and the offset data are not included.
+    #[cfg(feature = "csv")]
+    pub fn write_csv<W>(&self, w: W) -> Result<(), crate::Error>
+    where
+        W: std::io::Write,
+    {
+        let rows = std::iter::zip(self.fb.rows(), &self.stat)
+            .map(|(code, stat)| code.iter().copied().chain([*stat as f64]).collect())
+            .collect::<Vec<Vec<f64>>>();
+        crate::csv::to_writer(w, rows, None)
+    }
+
     /// Clear the atlas.
     pub fn clear(&mut self)
     where
@@ -369,6 +761,44 @@ impl<M, const N: usize, const D: usize> Atlas<M, N, D> {
         self.efd.len_of(Axis(1))
     }
 
+    /// Recompute the EFD coefficients of the stored linkages with a new
+    /// harmonic number.
+    ///
+    /// The curves are regenerated with `res` points, and the open/closed
+    /// type of each linkage is kept. This is much cheaper than
+    /// regenerating the atlas, since the linkages are not sampled again.
+    ///
+    /// ```
+    /// use four_bar::atlas::*;
+    ///
+    /// let mut atlas = FbAtlas::make(Cfg::new().size(10).res(90).harmonic(10));
+    /// atlas.recompute_efd(5, 90);
+    /// assert_eq!(atlas.harmonic(), 5);
+    /// ```
+    pub fn recompute_efd(&mut self, harmonic: usize, res: usize) {
+        let rows = self
+            .fb_norm_iter()
+            .zip(self.is_open_iter())
+            .collect::<Vec<_>>();
+        if rows.is_empty() {
+            self.efd = Array3::zeros([0, harmonic, D * 2]);
+            return;
+        }
+        #[cfg(not(feature = "rayon"))]
+        let iter = rows.iter();
+        #[cfg(feature = "rayon")]
+        let iter = rows.par_iter();
+        let efd_stack = iter
+            .map(
+                |(fb, is_open)| match fb.get_curve(res, *is_open).filter(|c| c.len() > 1) {
+                    Some(c) => efd_to_arr(efd::Efd::from_curve_harmonic(c, *is_open, harmonic)),
+                    None => Array2::zeros([harmonic, D * 2]),
+                },
+            )
+            .collect::<Vec<_>>();
+        self.efd = to_arr(Mutex::new(efd_stack), rows.len());
+    }
+
     /// Get a reference to the data.
     ///
     /// Data is stored in a 2D array, each row is a linkage code.
@@ -376,6 +806,13 @@ impl<M, const N: usize, const D: usize> Atlas<M, N, D> {
         &self.fb
     }
 
+    /// Get a reference to the offset data if exists.
+    ///
+    /// Each row is an offset code of the linkage, see [`Offset`].
+    pub fn offset_data(&self) -> Option<&Array2<f64>> {
+        self.offset.as_ref()
+    }
+
     /// Iterate over with an "open state" of the linkages.
     pub fn is_open_iter(&self) -> impl Iterator<Item = bool> + '_ {
         (self.efd.axis_iter(Axis(0))).map(|efd| efd.slice(s![.., D..]).sum() == 0.)
@@ -389,6 +826,12 @@ impl<M, const N: usize, const D: usize> Atlas<M, N, D> {
 
     /// Merge two data to one atlas inplace.
     pub fn merge_inplace(&mut self, mut rhs: Self) -> Result<(), ndarray::ShapeError> {
+        // Keep the offset data only if both of them have it
+        match (&mut self.offset, rhs.offset.take()) {
+            (Some(lhs), Some(rhs)) => lhs.append(Axis(0), rhs.view())?,
+            (lhs @ None, rhs) if self.fb.nrows() == 0 => *lhs = rhs,
+            (lhs, _) => *lhs = None,
+        }
         self.fb.append(Axis(0), rhs.fb.view())?;
         self.stat.append(Axis(0), rhs.stat.view())?;
         // Extend the harmonic number (zero padding) if needed
@@ -424,3 +867,155 @@ where
             .unwrap_or_default()
     }
 }
+
+#[test]
+fn offset_npz_round_trip() {
+    let cfg = Cfg::new()
+        .size(20)
+        .res(90)
+        .seed(Some(0u64).into())
+        .with_offset(true);
+    let atlas = FbAtlas::make(cfg);
+    assert_eq!(atlas.offset_data().map(|a| a.dim()), Some((20, 4)));
+    // The states of a sample have their own poses
+    let offset = atlas.offset_data().unwrap();
+    for (i, a) in offset.rows().into_iter().enumerate() {
+        assert!(offset.rows().into_iter().skip(i + 1).all(|b| a != b));
+    }
+    let mut buf = std::io::Cursor::new(Vec::new());
+    atlas.write(&mut buf).unwrap();
+    buf.set_position(0);
+    let read = FbAtlas::read(buf).unwrap();
+    assert_eq!(read.fb_data(), atlas.fb_data());
+    assert_eq!(read.offset_data(), atlas.offset_data());
+    // Backward compatible with the normalized-only atlas
+    let atlas = FbAtlas::make(Cfg::new().size(20).res(90).seed(Some(0u64).into()));
+    let mut buf = std::io::Cursor::new(Vec::new());
+    atlas.write(&mut buf).unwrap();
+    buf.set_position(0);
+    assert!(FbAtlas::read(buf).unwrap().offset_data().is_none());
+}
+
+#[test]
+fn sampling_range_respected() {
+    let range = [[1., 2.], [0.5, 1.5], [1., 3.], [0.2, 0.4], [0., 1.]];
+    let cfg = Cfg::new().size(40).res(90).seed(Some(0u64).into());
+    let atlas = FbAtlas::make(cfg.range(range));
+    assert_eq!(atlas.len(), 40);
+    for code in atlas.fb_data().rows() {
+        for (x, [min, max]) in std::iter::zip(code, range) {
+            assert!((min..max).contains(x), "{x} not in {min}..{max}");
+        }
+    }
+}
+
+#[test]
+fn recompute_efd_harmonic() {
+    let cfg = Cfg::new().size(20).res(90).seed(Some(0u64).into());
+    let atlas = FbAtlas::make(cfg.clone());
+    let mut lower = atlas.clone();
+    lower.recompute_efd(10, 90);
+    assert_eq!(lower.harmonic(), 10);
+    assert_eq!(lower.len(), atlas.len());
+    assert_eq!(lower.fb_data(), atlas.fb_data());
+    // Same as generating with the new harmonic
+    let expect =
+        FbAtlas::from_norm_slice(&atlas.fb_norm_iter().collect::<Vec<_>>(), &cfg.harmonic(10));
+    for (a, b) in std::iter::zip(lower.efd_iter(), expect.efd_iter()) {
+        assert!(a.err(&b) < 1e-12);
+    }
+}
+
+#[test]
+fn load_formats_equivalent() {
+    let cfg = Cfg::new().size(20).res(90).seed(Some(0u64).into());
+    let atlas = FbAtlas::make(cfg.clone());
+    let assert_eq_atlas = |read: FbAtlas| {
+        assert_eq!(read.fb_data(), atlas.fb_data());
+        assert_eq!(read.harmonic(), atlas.harmonic());
+        for (a, b) in std::iter::zip(read.efd_iter(), atlas.efd_iter()) {
+            assert!(a.err(&b) < 1e-12);
+        }
+    };
+    // NPZ
+    let mut buf = std::io::Cursor::new(Vec::new());
+    atlas.write(&mut buf).unwrap();
+    buf.set_position(0);
+    assert_eq_atlas(FbAtlas::read(buf).unwrap());
+    // Linkage list
+    let fbs = atlas.fb_norm_iter().collect::<Vec<_>>();
+    assert_eq_atlas(FbAtlas::from_norm_slice(&fbs, &cfg));
+    // RON
+    #[cfg(feature = "ron")]
+    {
+        let s = ron::to_string(&fbs).unwrap();
+        assert_eq_atlas(FbAtlas::read_ron(s.as_bytes(), &cfg).unwrap());
+        let e = FbAtlas::read_ron("[]".as_bytes(), &cfg).unwrap_err();
+        assert_eq!(e, crate::Error::EmptyData);
+        // The planar linkages are not the spherical linkages
+        assert!(SFbAtlas::read_ron(s.as_bytes(), &cfg).is_err());
+    }
+    // CSV
+    #[cfg(feature = "csv")]
+    {
+        let mut buf = Vec::new();
+        atlas.write_csv(&mut buf).unwrap();
+        assert_eq_atlas(FbAtlas::read_csv(buf.as_slice(), &cfg).unwrap());
+        let e = FbAtlas::read_csv("1,2,3\n".as_bytes(), &cfg).unwrap_err();
+        assert_eq!(e, crate::Error::ParamLength { expected: 6, found: 3 });
+        let e = FbAtlas::read_csv("1,2,3,4,5,0.5\n".as_bytes(), &cfg).unwrap_err();
+        assert_eq!(e, crate::Error::InvalidStat);
+    }
+}
+
+#[test]
+fn efd_metric_ranking() {
+    let target = arr2(&[[1., 0., 0., 1.], [0.2, 0., 0., 0.1]]);
+    // Same shape in a larger magnitude
+    let scaled = target.mapv(|x| x * 1.5);
+    // Close magnitude but a different shape
+    let skewed = arr2(&[[1., 0.3, 0., 1.], [0.2, 0., 0., 0.1]]);
+    for metric in EfdMetric::LIST {
+        assert!(metric.dist(target.view(), target.view()) < 1e-12);
+    }
+    let rank = |metric: EfdMetric| {
+        let d1 = metric.dist(target.view(), scaled.view());
+        let d2 = metric.dist(target.view(), skewed.view());
+        d1 < d2
+    };
+    // The magnitude matters for Manhattan, but not for cosine
+    assert!(!rank(EfdMetric::Manhattan));
+    assert!(!rank(EfdMetric::Euclidean));
+    assert!(rank(EfdMetric::Cosine));
+}
+
+#[test]
+fn fetch_with_metric() {
+    let cfg = Cfg::new().size(40).res(90).seed(Some(0u64).into());
+    let atlas = FbAtlas::make(cfg);
+    let curve = atlas.pick_norm(0).get_curve(90, false).unwrap();
+    for metric in EfdMetric::LIST {
+        let (candi, _) = atlas.fetch_k(&curve, false, 3, 3, metric).unwrap();
+        let fetched = atlas.fetch_with_metric(&curve, false, 3, metric);
+        let dist = |v: &[(f64, crate::FourBar)]| v.iter().map(|(d, _)| *d).collect::<Vec<_>>();
+        assert_eq!(dist(&fetched), dist(&candi));
+        let (d, _) = atlas.fetch_1st_with_metric(&curve, false, metric).unwrap();
+        assert_eq!(d, candi[0].0);
+    }
+}
+
+#[test]
+fn spherical_fetch_stored() {
+    let cfg = Cfg::new().size(40).res(90).seed(Some(0u64).into());
+    let atlas = SFbAtlas::make(cfg);
+    for i in [0, 20] {
+        let fb = atlas.pick_norm(i);
+        let curve = fb.get_curve(90, false).unwrap();
+        let (candi, pool) = atlas
+            .fetch_k(&curve, false, 3, 3, EfdMetric::default())
+            .unwrap();
+        assert_eq!(candi.len(), 3);
+        assert!(pool.iter().any(|(_, m)| *m == fb), "{i}");
+        assert!(pool[0].0 < 1e-9, "{}", pool[0].0);
+    }
+}
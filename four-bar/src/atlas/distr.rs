@@ -3,6 +3,7 @@ use mh::rand::{distributions::Distribution, Rng};
 
 /// Uniform distribution for mechinism types.
 pub struct Distr<M, const N: usize> {
+    bound: Option<[[f64; 2]; N]>,
     _marker: std::marker::PhantomData<M>,
 }
 
@@ -10,7 +11,27 @@ impl<M, const N: usize> Distr<M, N> {
     /// Create a new instance.
     #[allow(clippy::new_without_default)]
     pub fn new() -> Self {
-        Self { _marker: std::marker::PhantomData }
+        Self { bound: None, _marker: std::marker::PhantomData }
+    }
+
+    /// Create a new instance with a custom bound instead of
+    /// [`FromVectorized::BOUND`].
+    pub fn with_bound(bound: [[f64; 2]; N]) -> Self {
+        Self {
+            bound: Some(bound),
+            _marker: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<M: syn::SynBound<N>, const N: usize> Distr<M, N> {
+    fn bound(&self) -> [[f64; 2]; N] {
+        self.bound.unwrap_or(M::BOUND)
+    }
+
+    // The distribution of the linkages in the curve type
+    pub(crate) fn curve_type(&self, is_open: bool) -> CurveTypeDistr<M, N> {
+        CurveTypeDistr { distr: self, is_open }
     }
 }
 
@@ -19,14 +40,97 @@ where
     M: syn::SynBound<N>,
 {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec<M> {
-        let v = M::BOUND.map(|[u, l]| rng.gen_range(u..l));
+        let v = self.bound().map(|[u, l]| rng.gen_range(u..l));
         M::from_vectorized_s1(v).to_states()
     }
 }
 
+// Sample the states of a linkage in the curve type, the links are rearranged
+// if the code is still in the bound
+pub(crate) struct CurveTypeDistr<'a, M, const N: usize> {
+    distr: &'a Distr<M, N>,
+    is_open: bool,
+}
+
+impl<M, const N: usize> Distribution<Vec<M>> for CurveTypeDistr<'_, M, N>
+where
+    M: syn::SynBound<N> + IntoVectorized + Rearrange,
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec<M> {
+        let bound = self.distr.bound();
+        let fb = M::from_vectorized_s1(bound.map(|[u, l]| rng.gen_range(u..l)));
+        let in_bound = |fb: &M| {
+            let (code, _) = fb.clone().into_vectorized();
+            std::iter::zip(code, bound).all(|(x, [min, max])| (min..max).contains(&x))
+        };
+        match fb.clone().rearrange(self.is_open) {
+            Some(fb) if in_bound(&fb) => fb.to_states(),
+            _ => fb.to_states(),
+        }
+    }
+}
+
+/// Rearrangement of the links to generate the open or closed curves.
+pub trait Rearrange: Sized {
+    /// Rearrange the links to generate an open or a closed curve, return
+    /// `None` if not supported.
+    fn rearrange(self, is_open: bool) -> Option<Self>;
+}
+
+impl Rearrange for NormFourBar {
+    /// See [`NormFourBar::to_open_curve()`] and
+    /// [`NormFourBar::to_closed_curve()`].
+    fn rearrange(self, is_open: bool) -> Option<Self> {
+        if is_open {
+            Some(self.to_open_curve())
+        } else {
+            self.to_closed_curve()
+        }
+    }
+}
+
+impl Rearrange for SNormFourBar {
+    fn rearrange(self, _is_open: bool) -> Option<Self> {
+        None
+    }
+}
+
+/// Offset (unnormalized) code of the atlas linkages.
+pub trait Offset<const D: usize>: Normalized<D>
+where
+    efd::U<D>: efd::EfdDim<D>,
+{
+    /// Sampling bound of the offset code. Empty if the offset is not
+    /// supported.
+    const OFFSET_BOUND: &'static [[f64; 2]];
+
+    /// Denormalize the linkage with the offset code.
+    fn denorm_offset(self, offset: &[f64]) -> Self::De;
+}
+
+impl Offset<2> for NormFourBar {
+    /// Code `[p1x, p1y, a, l2]`, placed in the unit workspace.
+    const OFFSET_BOUND: &'static [[f64; 2]] =
+        &[[-1., 1.], [-1., 1.], [0., std::f64::consts::TAU], [0.1, 1.]];
+
+    fn denorm_offset(mut self, offset: &[f64]) -> Self::De {
+        let [p1x, p1y, a, l2]: [f64; 4] = offset.try_into().unwrap();
+        self.scale_inplace(l2);
+        FourBar::new(fb::UnNorm { p1x, p1y, a, l2 }, self)
+    }
+}
+
+impl Offset<3> for SNormFourBar {
+    const OFFSET_BOUND: &'static [[f64; 2]] = &[];
+
+    fn denorm_offset(self, _offset: &[f64]) -> Self::De {
+        self.denormalize()
+    }
+}
+
 /// Implement this trait to support atlas functions.
 pub trait Code<const N: usize, const D: usize>:
-    Normalized<D> + CurveGen<D> + syn::SynBound<N> + IntoVectorized + 'static
+    Normalized<D> + CurveGen<D> + syn::SynBound<N> + IntoVectorized + Offset<D> + Rearrange + 'static
 where
     efd::U<D>: efd::EfdDim<D>,
 {
@@ -52,7 +156,13 @@ where
 
 impl<M, const N: usize, const D: usize> Code<N, D> for M
 where
-    M: Normalized<D> + CurveGen<D> + syn::SynBound<N> + IntoVectorized + 'static,
+    M: Normalized<D>
+        + CurveGen<D>
+        + syn::SynBound<N>
+        + IntoVectorized
+        + Offset<D>
+        + Rearrange
+        + 'static,
     efd::U<D>: efd::EfdDim<D>,
 {
 }
@@ -1,10 +1,12 @@
 //! Functions for reading/writing CSV format.
-pub use csv::Error;
+use crate::FourBar;
 use csv::{ReaderBuilder, Writer};
-use std::io::ErrorKind::InvalidData;
+use std::io::Write as _;
 
 /// Parse CSV from a reader.
-pub fn from_reader<R, D>(r: R) -> Result<Vec<D>, Error>
+///
+/// Return [`crate::Error::EmptyData`] if there is no record.
+pub fn from_reader<R, D>(r: R) -> Result<Vec<D>, crate::Error>
 where
     R: std::io::Read,
     D: serde::de::DeserializeOwned,
@@ -15,14 +17,15 @@ where
         .from_reader(r)
         .deserialize()
         .collect::<Result<Vec<_>, _>>()
+        .map_err(crate::Error::from)
         .and_then(|data| match data.is_empty() {
-            true => Err(std::io::Error::new(InvalidData, "Empty data"))?,
+            true => Err(crate::Error::EmptyData),
             false => Ok(data),
         })
 }
 
 /// Parse CSV from string.
-pub fn from_string<D>(s: &str) -> Result<Vec<D>, Error>
+pub fn from_string<D>(s: &str) -> Result<Vec<D>, crate::Error>
 where
     D: serde::de::DeserializeOwned,
 {
@@ -30,25 +33,157 @@ where
 }
 
 /// Dump CSV to a writer.
-pub fn to_writer<W, C, S>(w: W, c: C) -> Result<(), csv::Error>
+///
+/// The floating point numbers are written with `precision` decimal places,
+/// or the shortest representation that round-trips if `None`.
+pub fn to_writer<W, C, S>(w: W, c: C, precision: Option<usize>) -> Result<(), crate::Error>
 where
     W: std::io::Write,
     C: AsRef<[S]>,
     S: serde::Serialize,
 {
+    let Some(prec) = precision else {
+        let mut w = Writer::from_writer(w);
+        c.as_ref().iter().try_for_each(|c| w.serialize(c))?;
+        w.flush()?;
+        return Ok(());
+    };
+    // The shortest representation is parsed back to the same value
+    let mut buf = Vec::new();
+    to_writer(&mut buf, c, None)?;
     let mut w = Writer::from_writer(w);
-    c.as_ref().iter().try_for_each(|c| w.serialize(c))?;
+    for record in ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(buf.as_slice())
+        .into_records()
+    {
+        let record = record?;
+        let record = record.iter().map(|s| match s.parse::<f64>() {
+            Ok(v) => format!("{v:.prec$}"),
+            Err(_) => s.to_string(),
+        });
+        w.write_record(record)?;
+    }
     w.flush()?;
     Ok(())
 }
 
 /// Dump CSV to string.
-pub fn to_string<C, S>(c: C) -> Result<String, csv::Error>
+///
+/// See also [`to_writer()`].
+pub fn to_string<C, S>(c: C, precision: Option<usize>) -> Result<String, crate::Error>
 where
     C: AsRef<[S]>,
     S: serde::Serialize,
 {
     let mut w = Vec::new();
-    to_writer(&mut w, c)?;
+    to_writer(&mut w, c, precision)?;
     Ok(String::from_utf8(w).unwrap())
 }
+
+/// Read a table of planar four-bar linkages, one linkage per row.
+///
+/// Each row is parsed by [`FourBar::from_csv_row()`], see it for the column
+/// order. This is different from the curve data.
+pub fn fb_from_reader<R>(r: R) -> Result<Vec<FourBar>, crate::Error>
+where
+    R: std::io::Read,
+{
+    from_reader::<_, [f64; 10]>(r)?
+        .into_iter()
+        .map(FourBar::from_csv_row)
+        .collect()
+}
+
+/// Write a table of planar four-bar linkages, one linkage per row.
+///
+/// The column names are written as a comment line at the beginning, and the
+/// rows are generated by [`FourBar::to_csv_row()`].
+pub fn fb_to_writer<W>(
+    mut w: W,
+    fbs: &[FourBar],
+    precision: Option<usize>,
+) -> Result<(), crate::Error>
+where
+    W: std::io::Write,
+{
+    writeln!(w, "# {}", FourBar::CSV_COLUMNS.join(","))?;
+    let rows = fbs.iter().map(FourBar::to_csv_row).collect::<Vec<_>>();
+    to_writer(w, rows, precision)
+}
+
+/// Write the joint paths paired with the input angles into a table.
+///
+/// The data is generated by [`crate::mech::CurveGen::curves_angle()`]. The
+/// column names are written as a comment line at the beginning, which are the
+/// input angle `theta` followed by the coordinates of the driver (`p3`),
+/// follower (`p4`) and coupler (`p5`) joints.
+pub fn joints_to_writer<W, const D: usize>(
+    mut w: W,
+    data: &[(f64, [[f64; D]; 3])],
+    precision: Option<usize>,
+) -> Result<(), crate::Error>
+where
+    W: std::io::Write,
+{
+    let mut header = vec!["theta".to_string()];
+    for p in ["p3", "p4", "p5"] {
+        header.extend(["x", "y", "z"][..D].iter().map(|c| format!("{p}{c}")));
+    }
+    writeln!(w, "# {}", header.join(","))?;
+    let rows = data.iter().map(|(t, ps)| {
+        let mut row = vec![*t];
+        ps.iter().for_each(|p| row.extend(p));
+        row
+    });
+    to_writer(w, rows.collect::<Vec<_>>(), precision)
+}
+
+#[test]
+fn output_precision() {
+    let curve = [[1. / 3., 2.], [-0.5, 1e-7]];
+    for prec in [0, 2, 8] {
+        let s = to_string(curve, Some(prec)).unwrap();
+        for v in s.lines().flat_map(|l| l.split(',')) {
+            let decimals = v.split_once('.').map(|(_, d)| d.len()).unwrap_or(0);
+            assert_eq!(decimals, prec, "{v}");
+        }
+    }
+    let s = to_string(curve, None).unwrap();
+    assert_eq!(from_string::<[f64; 2]>(&s).unwrap(), curve);
+}
+
+#[test]
+fn fb_table_round_trip() {
+    let fbs = FourBar::benchmarks().into_iter().map(|(_, fb)| fb);
+    let mut fbs = fbs.collect::<Vec<_>>();
+    let mut fb = FourBar::example();
+    fb.unnorm.set_origin(1.5, -2.);
+    fb.unnorm.set_rotation(0.3);
+    fb.stat = crate::mech::Stat::C2B1;
+    fbs.push(fb);
+    let mut buf = Vec::new();
+    fb_to_writer(&mut buf, &fbs, None).unwrap();
+    assert_eq!(fb_from_reader(buf.as_slice()).unwrap(), fbs);
+    // Invalid state number
+    let e = fb_from_reader("0,0,0,90,35,70,70,45,0.5,5".as_bytes());
+    assert_eq!(e, Err(crate::Error::InvalidStat));
+}
+
+#[test]
+fn joint_paths_table() {
+    use crate::mech::CurveGen as _;
+    let fb = FourBar::example();
+    let data = fb.curves_angle(90);
+    let mut buf = Vec::new();
+    joints_to_writer(&mut buf, &data, None).unwrap();
+    let s = String::from_utf8(buf).unwrap();
+    assert!(s.starts_with("# theta,p3x,p3y,p4x,p4y,p5x,p5y\n"));
+    let rows = from_string::<[f64; 7]>(&s).unwrap();
+    let curves = fb.curves(90);
+    assert_eq!(rows.len(), curves.len());
+    for (row, c) in std::iter::zip(&rows, &curves) {
+        assert_eq!(row[1..], c.concat());
+    }
+}
@@ -0,0 +1,423 @@
+//! Utilities for the curve data.
+//!
+//! The curves are represented as slices of coordinates `&[[f64; D]]`.
+
+fn dist<const D: usize>(a: &[f64; D], b: &[f64; D]) -> f64 {
+    std::iter::zip(a, b)
+        .map(|(a, b)| (a - b).powi(2))
+        .sum::<f64>()
+        .sqrt()
+}
+
+/// Cumulative arc length of the curve.
+///
+/// The first value is always zero, and the last value is the total length.
+/// If the curve is closed, the segment from the last point to the first point
+/// is appended, so the length of the returned vector is one more than the
+/// number of the points.
+pub fn cumulative_length<const D: usize>(curve: &[[f64; D]], closed: bool) -> Vec<f64> {
+    let mut len = Vec::with_capacity(curve.len() + 1);
+    if curve.is_empty() {
+        return len;
+    }
+    let last = closed.then(|| [curve[curve.len() - 1], curve[0]]);
+    let mut acc = 0.;
+    len.push(acc);
+    for [a, b] in curve.windows(2).map(|w| [w[0], w[1]]).chain(last) {
+        acc += dist(&a, &b);
+        len.push(acc);
+    }
+    len
+}
+
+/// Total arc length of the curve.
+///
+/// See also [`cumulative_length()`].
+///
+/// ```
+/// use four_bar::curve::arc_length;
+///
+/// let line = [[0., 0.], [3., 4.]];
+/// assert_eq!(arc_length(&line, false), 5.);
+/// assert_eq!(arc_length(&line, true), 10.);
+/// ```
+pub fn arc_length<const D: usize>(curve: &[[f64; D]], closed: bool) -> f64 {
+    cumulative_length(curve, closed)
+        .last()
+        .copied()
+        .unwrap_or(0.)
+}
+
+/// Smooth the curve with a centered moving average.
+///
+/// The `window` is the number of the points to be averaged, which is rounded
+/// up to an odd number. The window wraps around the ends of the closed curve,
+/// and is shrunk at the ends of the open curve.
+pub fn smooth<const D: usize>(curve: &[[f64; D]], window: usize, closed: bool) -> Vec<[f64; D]> {
+    let n = curve.len();
+    let half = (window / 2).min(n.saturating_sub(1) / 2);
+    if half == 0 {
+        return curve.to_vec();
+    }
+    (0..n)
+        .map(|i| {
+            let rng = if closed {
+                (i + n - half)..(i + n + half + 1)
+            } else {
+                i.saturating_sub(half) + n..(i + half + 1).min(n) + n
+            };
+            let len = rng.len() as f64;
+            let mut p = [0.; D];
+            for c in rng.map(|j| &curve[j % n]) {
+                p.iter_mut().zip(c).for_each(|(p, c)| *p += c);
+            }
+            p.map(|p| p / len)
+        })
+        .collect()
+}
+
+/// Symmetric Hausdorff distance between two curves.
+///
+/// The largest distance from a point of one curve to the nearest point of
+/// another curve. The comparison only depends on the shape of the point sets,
+/// so the curves can have different numbers of points and parameterizations.
+/// Return infinity if only one of the curves is empty.
+///
+/// ```
+/// use four_bar::curve::hausdorff;
+///
+/// let a = [[0., 0.], [1., 0.], [2., 0.]];
+/// let b = [[0., 1.], [2., 1.]];
+/// assert_eq!(hausdorff(&a, &b), 2f64.sqrt());
+/// ```
+pub fn hausdorff<const D: usize>(a: &[[f64; D]], b: &[[f64; D]]) -> f64 {
+    let directed = |a: &[[f64; D]], b: &[[f64; D]]| {
+        a.iter()
+            .map(|p| b.iter().map(|q| dist(p, q)).fold(f64::INFINITY, f64::min))
+            .fold(0., f64::max)
+    };
+    directed(a, b).max(directed(b, a))
+}
+
+/// Return true if the curve crosses itself.
+///
+/// The curve is treated as an open polyline, so use [`close_loop()`] to test
+/// a closed curve. Only the proper crossings are counted, the
+/// segments that touch at their endpoints are ignored. This function returns
+/// on the first crossing without counting all of them.
+///
+/// ```
+/// use four_bar::curve::is_self_intersecting;
+///
+/// let z = [[0., 0.], [1., 0.], [0., 1.], [1., 1.]];
+/// assert!(!is_self_intersecting(&z));
+/// let bow = [[0., 0.], [1., 1.], [1., 0.], [0., 1.]];
+/// assert!(is_self_intersecting(&bow));
+/// ```
+pub fn is_self_intersecting(curve: &[[f64; 2]]) -> bool {
+    let seg = curve.windows(2).map(|w| [w[0], w[1]]).collect::<Vec<_>>();
+    // Skip the adjacent segments, which always share an endpoint
+    (0..seg.len()).any(|i| {
+        let [a, b] = &seg[i];
+        seg.iter().skip(i + 2).any(|[c, d]| seg_cross(a, b, c, d))
+    })
+}
+
+// Return true if the segment `ab` properly crosses the segment `cd`
+fn seg_cross(a: &[f64; 2], b: &[f64; 2], c: &[f64; 2], d: &[f64; 2]) -> bool {
+    fn cross(o: &[f64; 2], p: &[f64; 2], q: &[f64; 2]) -> f64 {
+        (p[0] - o[0]) * (q[1] - o[1]) - (p[1] - o[1]) * (q[0] - o[0])
+    }
+    // Bounding box rejection
+    if c[0].max(d[0]) < a[0].min(b[0])
+        || c[0].min(d[0]) > a[0].max(b[0])
+        || c[1].max(d[1]) < a[1].min(b[1])
+        || c[1].min(d[1]) > a[1].max(b[1])
+    {
+        return false;
+    }
+    cross(c, d, a) * cross(c, d, b) < 0. && cross(a, b, c) * cross(a, b, d) < 0.
+}
+
+/// Return true if the curve is likely to be a closed loop.
+///
+/// The gap between the endpoints is compared with the average length of the
+/// segments. For the planar curves, a larger gap (up to 10 segments) is also
+/// accepted if closing it doesn't make the curve cross itself.
+///
+/// ```
+/// use four_bar::curve::is_closed;
+///
+/// let square = [[0., 0.], [1., 0.], [1., 1.], [0., 1.]];
+/// assert!(is_closed(&square));
+/// let line = [[0., 0.], [1., 0.], [2., 0.], [3., 0.]];
+/// assert!(!is_closed(&line));
+/// ```
+pub fn is_closed<const D: usize>(curve: &[[f64; D]]) -> bool {
+    if curve.len() < 3 {
+        return false;
+    }
+    let step = arc_length(curve, false) / (curve.len() - 1) as f64;
+    let gap = dist(&curve[0], &curve[curve.len() - 1]);
+    if gap <= 2. * step {
+        return true;
+    } else if D != 2 || gap > 10. * step {
+        return false;
+    }
+    // Only the closing segment is checked, the crossings of the open curve
+    // are not caused by it
+    let c = curve.iter().map(|p| [p[0], p[1]]).collect::<Vec<_>>();
+    let (first, last) = (&c[0], &c[c.len() - 1]);
+    // Skip the first and the last segments, which share an endpoint
+    !c[1..c.len() - 1]
+        .windows(2)
+        .any(|w| seg_cross(last, first, &w[0], &w[1]))
+}
+
+/// Close the curve by appending the first point to the end.
+///
+/// The first point is appended only if the endpoints are not overlapped, where
+/// the tolerance is relative to the average length of the segments.
+///
+/// ```
+/// use four_bar::curve::close_loop;
+///
+/// let c = [[0., 0.], [1., 0.], [1., 1.]];
+/// assert_eq!(close_loop(&c).last(), Some(&[0., 0.]));
+/// assert_eq!(close_loop(&close_loop(&c)).len(), 4);
+/// ```
+pub fn close_loop<const D: usize>(curve: &[[f64; D]]) -> Vec<[f64; D]> {
+    let mut c = curve.to_vec();
+    if let (Some(first), Some(last)) = (curve.first(), curve.last()) {
+        let step = arc_length(curve, false) / (curve.len() - 1).max(1) as f64;
+        if dist(first, last) > 1e-9 * step {
+            c.push(*first);
+        }
+    }
+    c
+}
+
+/// Center and scale the curve into the unit bounding box.
+///
+/// The longer side of the bounding box is scaled to one, and its center is
+/// moved to the origin, so the points are in `[-0.5, 0.5]^2`. The returned
+/// transformation maps the normalized points back to the original curve. A
+/// single point or a repeated point is only centered.
+///
+/// ```
+/// use four_bar::curve::normalize_to_unit;
+///
+/// let (c, geo) = normalize_to_unit(&[[1., 1.], [5., 3.]]);
+/// assert_eq!(c, [[-0.5, -0.25], [0.5, 0.25]]);
+/// assert_eq!((geo.trans()[0], geo.trans()[1]), (3., 2.));
+/// assert_eq!(geo.scale(), 4.);
+/// ```
+pub fn normalize_to_unit(curve: &[[f64; 2]]) -> (Vec<[f64; 2]>, efd::GeoVar2) {
+    let Some(first) = curve.first() else {
+        return (Vec::new(), efd::GeoVar2::identity());
+    };
+    let (min, max) = curve.iter().fold((*first, *first), |(min, max), p| {
+        let min = [min[0].min(p[0]), min[1].min(p[1])];
+        let max = [max[0].max(p[0]), max[1].max(p[1])];
+        (min, max)
+    });
+    let center = [(min[0] + max[0]) * 0.5, (min[1] + max[1]) * 0.5];
+    let size = (max[0] - min[0]).max(max[1] - min[1]);
+    let scale = if size > 0. { size } else { 1. };
+    let c = curve
+        .iter()
+        .map(|[x, y]| [(x - center[0]) / scale, (y - center[1]) / scale])
+        .collect();
+    let geo = efd::GeoVar2::new(center, efd::na::UnitComplex::identity(), scale);
+    (c, geo)
+}
+
+/// Signed area of the planar curve, positive if the curve is
+/// counter-clockwise.
+///
+/// The curve is treated as closed by the shoelace formula, so an open curve
+/// is measured with the segment from the last point to the first point.
+///
+/// ```
+/// use four_bar::curve::signed_area;
+///
+/// let square = [[0., 0.], [1., 0.], [1., 1.], [0., 1.]];
+/// assert_eq!(signed_area(&square), 1.);
+/// ```
+pub fn signed_area(curve: &[[f64; 2]]) -> f64 {
+    let next = curve.iter().cycle().skip(1);
+    std::iter::zip(curve, next)
+        .map(|(a, b)| a[0] * b[1] - b[0] * a[1])
+        .sum::<f64>()
+        * 0.5
+}
+
+/// Reverse the planar curve in-placed if it is clockwise, so it is always
+/// counter-clockwise.
+///
+/// The linkages may trace the same shape in the opposite directions, this
+/// function removes the direction difference before the comparison. See also
+/// [`signed_area()`].
+pub fn ensure_ccw(curve: &mut [[f64; 2]]) {
+    if signed_area(curve) < 0. {
+        curve.reverse();
+    }
+}
+
+/// Error of the mirror symmetry of the curve, zero if the curve is mirror
+/// symmetric about any line (or plane in 3D).
+///
+/// The curve is reflected and reversed to keep the traversal direction, then
+/// compared with the original curve by the normalized EFD coefficients, so
+/// the error is independent of the position, orientation, scale and starting
+/// point.
+pub fn mirror_err<const D: usize>(curve: &[[f64; D]], is_open: bool) -> f64
+where
+    efd::U<D>: efd::EfdDim<D>,
+{
+    let efd = efd::Efd::from_curve(curve, is_open);
+    let mirror = (curve.iter().rev())
+        .map(|&c| {
+            let mut c = c;
+            c[D - 1] = -c[D - 1];
+            c
+        })
+        .collect::<Vec<_>>();
+    efd.err(&efd::Efd::from_curve_harmonic(
+        mirror,
+        is_open,
+        efd.harmonic(),
+    ))
+}
+
+#[test]
+fn unit_circle_length() {
+    use approx::assert_abs_diff_eq;
+    const N: usize = 3600;
+    let circle = (0..N)
+        .map(|i| i as f64 / N as f64 * std::f64::consts::TAU)
+        .map(|t| [t.cos(), t.sin()])
+        .collect::<Vec<_>>();
+    assert_abs_diff_eq!(
+        arc_length(&circle, true),
+        std::f64::consts::TAU,
+        epsilon = 1e-5
+    );
+    let len = cumulative_length(&circle, true);
+    assert_eq!(len.len(), N + 1);
+    assert_eq!(len[0], 0.);
+}
+
+#[test]
+fn smooth_noisy_circle() {
+    const N: usize = 360;
+    let circle = (0..N)
+        .map(|i| i as f64 / N as f64 * std::f64::consts::TAU)
+        .map(|t| {
+            // Deterministic noise
+            let r = 1. + 0.05 * (37. * t).sin();
+            [r * t.cos(), r * t.sin()]
+        })
+        .collect::<Vec<_>>();
+    let var = |c: &[[f64; 2]]| {
+        let r = c.iter().map(|[x, y]| x.hypot(*y)).collect::<Vec<_>>();
+        let mean = r.iter().sum::<f64>() / r.len() as f64;
+        r.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / r.len() as f64
+    };
+    let smoothed = smooth(&circle, 9, true);
+    assert_eq!(smoothed.len(), N);
+    assert!(var(&smoothed) < var(&circle));
+}
+
+#[test]
+fn hausdorff_shifted() {
+    let curve = (0..90)
+        .map(|i| i as f64 / 90. * std::f64::consts::TAU)
+        .map(|t| [t.cos(), 2. * t.sin()])
+        .collect::<Vec<_>>();
+    assert_eq!(hausdorff(&curve, &curve), 0.);
+    let shifted = curve
+        .iter()
+        .map(|[x, y]| [x + 0.3, y + 0.4])
+        .collect::<Vec<_>>();
+    approx::assert_abs_diff_eq!(hausdorff(&curve, &shifted), 0.5, epsilon = 1e-12);
+}
+
+#[test]
+fn close_loop_once() {
+    let square = [[0., 0.], [1., 0.], [1., 1.], [0., 1.]];
+    let closed = close_loop(&square);
+    assert_eq!(closed.len(), 5);
+    assert_eq!(closed[4], square[0]);
+    // Already closed, including the rounding errors
+    assert_eq!(close_loop(&closed), closed);
+    let mut almost = closed.clone();
+    almost[4][0] += 1e-12;
+    assert_eq!(close_loop(&almost).len(), 5);
+    // Degenerated curves
+    assert!(close_loop::<2>(&[]).is_empty());
+    assert_eq!(close_loop(&[[1., 2.]]), [[1., 2.]]);
+}
+
+#[test]
+fn self_intersecting_curves() {
+    use std::f64::consts::TAU;
+    const N: usize = 90;
+    // Start away from the crossing point
+    let t = (0..=N).map(|i| i as f64 / N as f64 * TAU + 1.);
+    let figure_eight = t.clone().map(|t| [t.sin(), t.sin() * t.cos()]);
+    assert!(is_self_intersecting(&figure_eight.collect::<Vec<_>>()));
+    let ellipse = t.map(|t| [2. * t.cos(), t.sin()]).collect::<Vec<_>>();
+    assert!(!is_self_intersecting(&ellipse));
+}
+
+#[test]
+fn closed_by_closing_segment() {
+    // An open curve crossing itself, the closing segment crosses it again
+    let open = [
+        [0., 0.],
+        [1., 0.],
+        [2., 0.],
+        [3., 0.],
+        [3., 1.],
+        [2., 1.],
+        [1.5, 0.5],
+        [1.5, -0.5],
+        [1.5, -1.5],
+        [2.5, -1.5],
+    ];
+    assert!(is_self_intersecting(&open));
+    assert!(!is_closed(&open));
+    // Closing the gap doesn't cross anything
+    assert!(is_closed(&open[..9]));
+}
+
+#[test]
+fn ensure_ccw_both_directions() {
+    use std::f64::consts::TAU;
+    let t = (0..90).map(|i| i as f64 / 90. * TAU);
+    let ccw = t.map(|t| [2. * t.cos(), t.sin()]).collect::<Vec<_>>();
+    approx::assert_abs_diff_eq!(signed_area(&ccw), TAU, epsilon = 1e-2);
+    let mut cw = ccw.iter().rev().copied().collect::<Vec<_>>();
+    ensure_ccw(&mut cw);
+    assert_eq!(cw, ccw);
+    let mut ccw2 = ccw.clone();
+    ensure_ccw(&mut ccw2);
+    assert_eq!(ccw2, ccw);
+}
+
+#[test]
+fn normalize_to_unit_inverse() {
+    let curve = (0..90)
+        .map(|i| i as f64 / 90. * std::f64::consts::TAU)
+        .map(|t| [30. + 8. * t.cos(), -12. + 3. * t.sin()])
+        .collect::<Vec<_>>();
+    let (c, geo) = normalize_to_unit(&curve);
+    assert!(c.iter().flatten().all(|x| (-0.5..=0.5).contains(x)));
+    let [tx, ty] = geo.trans();
+    let s = geo.scale();
+    for ([x, y], p) in std::iter::zip(c, &curve) {
+        approx::assert_abs_diff_eq!(tx + s * x, p[0], epsilon = 1e-12);
+        approx::assert_abs_diff_eq!(ty + s * y, p[1], epsilon = 1e-12);
+    }
+}
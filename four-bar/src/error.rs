@@ -0,0 +1,183 @@
+use crate::mech::StatError;
+
+/// Error type of the fallible functions in this crate.
+///
+/// ```
+/// use four_bar::{Error, FourBar};
+///
+/// let mut row = FourBar::example().to_csv_row();
+/// row[9] = 5.;
+/// assert_eq!(FourBar::from_csv_row(row), Err(Error::InvalidStat));
+/// ```
+///
+/// The errors of the underlying libraries are wrapped, and compared by their
+/// kinds or messages.
+#[derive(Debug)]
+pub enum Error {
+    /// The state number is invalid, see [`crate::mech::Stat`].
+    InvalidStat,
+    /// The linkage parameters are invalid, e.g., not finite.
+    InvalidLinkage,
+    /// The linkage cannot be assembled at the given input angle.
+    Unassemblable,
+    /// The curve has too few points.
+    CurveTooShort,
+    /// The option requires an open target, but the target is closed.
+    ClosedTarget,
+    /// The encoded string is malformed.
+    InvalidCode,
+    /// The range is empty or not finite, the lower bound should be less than
+    /// the upper bound. Or no valid sample is found in the range.
+    InvalidRange,
+    /// The number of the parameters does not match the bounds.
+    ParamLength {
+        /// Expected number of the parameters
+        expected: usize,
+        /// Number of the given parameters
+        found: usize,
+    },
+    /// The input data is empty.
+    EmptyData,
+    /// Input/output error.
+    Io(std::io::Error),
+    /// CSV parsing or writing error.
+    #[cfg(feature = "csv")]
+    Csv(::csv::Error),
+    /// NPZ reading error of the atlas.
+    #[cfg(feature = "atlas")]
+    NpzRead(ndarray_npy::ReadNpzError),
+    /// NPZ writing error of the atlas.
+    #[cfg(feature = "atlas")]
+    NpzWrite(ndarray_npy::WriteNpzError),
+    /// RON parsing error.
+    #[cfg(feature = "ron")]
+    Ron(::ron::error::SpannedError),
+}
+
+impl PartialEq for Error {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Io(a), Self::Io(b)) => a.kind() == b.kind(),
+            #[cfg(feature = "csv")]
+            (Self::Csv(a), Self::Csv(b)) => a.to_string() == b.to_string(),
+            #[cfg(feature = "atlas")]
+            (Self::NpzRead(a), Self::NpzRead(b)) => a.to_string() == b.to_string(),
+            #[cfg(feature = "atlas")]
+            (Self::NpzWrite(a), Self::NpzWrite(b)) => a.to_string() == b.to_string(),
+            #[cfg(feature = "ron")]
+            (Self::Ron(a), Self::Ron(b)) => a.to_string() == b.to_string(),
+            (
+                Self::ParamLength { expected: e1, found: f1 },
+                Self::ParamLength { expected: e2, found: f2 },
+            ) => e1 == e2 && f1 == f2,
+            (a, b) => !a.is_wrapped() && std::mem::discriminant(a) == std::mem::discriminant(b),
+        }
+    }
+}
+
+impl Error {
+    // Return true if the error is from the underlying libraries
+    fn is_wrapped(&self) -> bool {
+        match self {
+            Self::Io(_) => true,
+            #[cfg(feature = "csv")]
+            Self::Csv(_) => true,
+            #[cfg(feature = "atlas")]
+            Self::NpzRead(_) | Self::NpzWrite(_) => true,
+            #[cfg(feature = "ron")]
+            Self::Ron(_) => true,
+            _ => false,
+        }
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::InvalidStat => write!(f, "invalid state"),
+            Self::InvalidLinkage => write!(f, "invalid linkage parameters"),
+            Self::Unassemblable => write!(f, "the linkage cannot be assembled"),
+            Self::CurveTooShort => write!(f, "too few points of the curve"),
+            Self::ClosedTarget => write!(f, "the option requires an open target"),
+            Self::InvalidCode => write!(f, "malformed code"),
+            Self::InvalidRange => write!(f, "invalid range"),
+            Self::ParamLength { expected, found } => {
+                write!(f, "expected {expected} parameters, found {found}")
+            }
+            Self::EmptyData => write!(f, "empty data"),
+            Self::Io(e) => write!(f, "[IO] {e}"),
+            #[cfg(feature = "csv")]
+            Self::Csv(e) => write!(f, "[CSV] {e}"),
+            #[cfg(feature = "atlas")]
+            Self::NpzRead(e) => write!(f, "[NPZ] {e}"),
+            #[cfg(feature = "atlas")]
+            Self::NpzWrite(e) => write!(f, "[NPZ] {e}"),
+            #[cfg(feature = "ron")]
+            Self::Ron(e) => write!(f, "[RON] {e}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            #[cfg(feature = "csv")]
+            Self::Csv(e) => Some(e),
+            #[cfg(feature = "atlas")]
+            Self::NpzRead(e) => Some(e),
+            #[cfg(feature = "atlas")]
+            Self::NpzWrite(e) => Some(e),
+            #[cfg(feature = "ron")]
+            Self::Ron(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<StatError> for Error {
+    fn from(_: StatError) -> Self {
+        Self::InvalidStat
+    }
+}
+
+macro_rules! impl_from {
+    ($($(#[$meta:meta])* ($ty:ty, $kind:ident)),+ $(,)?) => {$(
+        $(#[$meta])*
+        impl From<$ty> for Error {
+            fn from(e: $ty) -> Self {
+                Self::$kind(e)
+            }
+        }
+    )+};
+}
+
+impl_from!(
+    (std::io::Error, Io),
+    #[cfg(feature = "csv")]
+    (::csv::Error, Csv),
+    #[cfg(feature = "atlas")]
+    (ndarray_npy::ReadNpzError, NpzRead),
+    #[cfg(feature = "atlas")]
+    (ndarray_npy::WriteNpzError, NpzWrite),
+    #[cfg(feature = "ron")]
+    (::ron::error::SpannedError, Ron),
+);
+
+#[test]
+fn wrapped_errors() {
+    use std::io::ErrorKind;
+    let io = |kind| Error::from(std::io::Error::from(kind));
+    assert_eq!(io(ErrorKind::NotFound), io(ErrorKind::NotFound));
+    assert_ne!(io(ErrorKind::NotFound), io(ErrorKind::InvalidData));
+    assert_ne!(io(ErrorKind::NotFound), Error::EmptyData);
+    assert!(std::error::Error::source(&io(ErrorKind::NotFound)).is_some());
+    assert!(std::error::Error::source(&Error::EmptyData).is_none());
+    #[cfg(feature = "csv")]
+    {
+        let e = crate::csv::from_string::<[f64; 2]>("").unwrap_err();
+        assert_eq!(e, Error::EmptyData);
+        let e = crate::csv::from_string::<[f64; 2]>("a,b\n").unwrap_err();
+        assert!(matches!(e, Error::Csv(_)), "{e}");
+    }
+}
@@ -0,0 +1,118 @@
+//! Functions for writing URDF (Unified Robot Description Format), which can
+//! be imported by the robotics tools such as ROS.
+use crate::{
+    mech::{fb::UnNorm, CurveGen as _},
+    FourBar, NormFourBar,
+};
+use std::{f64::consts::PI, fmt::Write as _};
+
+/// Export the planar four-bar linkage to a minimal URDF string.
+///
+/// The linkage is posed at the start of its range of motion. The links
+/// `ground`, `driver`, `coupler` and `follower` are connected by four
+/// revolute joints around the Z axis, and the coupler point is a `tracer`
+/// link fixed on the coupler. The lengths are written as is, without any unit
+/// conversion.
+///
+/// Since URDF only supports the tree structure, the follower is a child of
+/// the ground, and the joint between the coupler and the follower is attached
+/// to a virtual `closure` link instead, which coincides with the tip of the
+/// follower. If the linkage cannot be assembled, the links are written in a
+/// straight line.
+///
+/// ```
+/// use four_bar::{io_urdf::to_urdf, FourBar};
+///
+/// let urdf = to_urdf(&FourBar::example());
+/// assert!(urdf.contains(r#"<joint name="closure_joint" type="revolute">"#));
+/// ```
+pub fn to_urdf(fb: &FourBar) -> String {
+    let mut s = String::new();
+    // Writing to a string never fails
+    write_urdf(&mut s, fb).unwrap();
+    s
+}
+
+fn write_urdf(s: &mut String, fb: &FourBar) -> std::fmt::Result {
+    let UnNorm { p1x, p1y, a, l2 } = fb.unnorm;
+    let NormFourBar { l1, l3, l4, l5, g, .. } = fb.norm;
+    let start = fb.angle_bound().to_value().map_or(0., |[start, _]| start);
+    let angle = |p: [f64; 2], q: [f64; 2]| (q[1] - p[1]).atan2(q[0] - p[0]);
+    let (p1, p2, driver, coupler, follower) = match fb.pos(start) {
+        Some([p1, p2, p3, p4, _]) => (p1, p2, angle(p1, p3), angle(p3, p4), angle(p2, p4)),
+        None => {
+            let p2 = [p1x + l1 * a.cos(), p1y + l1 * a.sin()];
+            ([p1x, p1y], p2, a, a, a)
+        }
+    };
+    let width = l2 * 0.1;
+    writeln!(s, r#"<?xml version="1.0"?>"#)?;
+    writeln!(s, r#"<robot name="four_bar">"#)?;
+    writeln!(s, r#"  <link name="ground"/>"#)?;
+    for (name, len) in [("driver", l2), ("coupler", l3), ("follower", l4)] {
+        writeln!(s, r#"  <link name="{name}">"#)?;
+        writeln!(s, r#"    <visual>"#)?;
+        writeln!(s, r#"      <origin xyz="{} 0 0"/>"#, len * 0.5)?;
+        writeln!(s, r#"      <geometry>"#)?;
+        writeln!(s, r#"        <box size="{len} {width} {width}"/>"#)?;
+        writeln!(s, r#"      </geometry>"#)?;
+        writeln!(s, r#"    </visual>"#)?;
+        writeln!(s, r#"  </link>"#)?;
+    }
+    writeln!(s, r#"  <link name="closure"/>"#)?;
+    writeln!(s, r#"  <link name="tracer"/>"#)?;
+    const NOTE: &str = "the closure link coincides with the tip of the follower link, \
+        since URDF only supports the tree structure";
+    writeln!(s, "  <!-- Loop closure: {NOTE} -->")?;
+    let p5 = [l5 * g.cos(), l5 * g.sin()];
+    let (yaw3, yaw4) = (coupler - driver, follower - coupler);
+    let joints = [
+        ("ground", "driver", p1, driver, true),
+        ("driver", "coupler", [l2, 0.], yaw3, true),
+        ("ground", "follower", p2, follower, true),
+        ("coupler", "closure", [l3, 0.], yaw4, true),
+        ("coupler", "tracer", p5, 0., false),
+    ];
+    for (parent, child, [x, y], yaw, revolute) in joints {
+        let ty = if revolute { "revolute" } else { "fixed" };
+        writeln!(s, r#"  <joint name="{child}_joint" type="{ty}">"#)?;
+        writeln!(s, r#"    <parent link="{parent}"/>"#)?;
+        writeln!(s, r#"    <child link="{child}"/>"#)?;
+        writeln!(s, r#"    <origin xyz="{x} {y} 0" rpy="0 0 {yaw}"/>"#)?;
+        if revolute {
+            writeln!(s, r#"    <axis xyz="0 0 1"/>"#)?;
+            let limit = format!(r#"lower="{}" upper="{PI}" effort="0" velocity="0""#, -PI);
+            writeln!(s, r#"    <limit {limit}/>"#)?;
+        }
+        writeln!(s, r#"  </joint>"#)?;
+    }
+    writeln!(s, "</robot>")
+}
+
+#[test]
+fn urdf_well_formed() {
+    let urdf = to_urdf(&FourBar::example());
+    // Check the tags are balanced, skip the declaration and the comments
+    let mut stack = Vec::new();
+    let mut rest = urdf.as_str();
+    while let Some(i) = rest.find('<') {
+        rest = &rest[i..];
+        if rest.starts_with("<!--") {
+            let end = rest.find("-->").expect("unclosed comment");
+            rest = &rest[end + 3..];
+            continue;
+        }
+        let end = rest.find('>').expect("unclosed tag");
+        let tag = &rest[1..end];
+        rest = &rest[end + 1..];
+        if tag.starts_with('?') {
+            assert!(tag.ends_with('?'));
+        } else if let Some(name) = tag.strip_prefix('/') {
+            assert_eq!(stack.pop(), Some(name));
+        } else if !tag.ends_with('/') {
+            stack.push(tag.split_whitespace().next().unwrap());
+        }
+    }
+    assert!(stack.is_empty(), "{stack:?}");
+    assert_eq!(urdf.matches(r#"type="revolute""#).count(), 4);
+}
@@ -2,7 +2,10 @@
 #![cfg_attr(docsrs, feature(doc_auto_cfg))]
 #![warn(missing_docs)]
 
-pub use crate::mech::{FourBar, MFourBar, MNormFourBar, NormFourBar, SFourBar, SNormFourBar};
+pub use crate::{
+    error::Error,
+    mech::{FourBar, MFourBar, MNormFourBar, NormFourBar, SFourBar, SNormFourBar},
+};
 pub use efd;
 pub use mh;
 #[cfg(feature = "atlas")]
@@ -12,6 +15,10 @@ pub use ndarray;
 pub mod atlas;
 #[cfg(feature = "csv")]
 pub mod csv;
+pub mod curve;
+mod error;
+#[cfg(feature = "urdf")]
+pub mod io_urdf;
 pub mod mech;
 #[cfg(feature = "plot")]
 pub mod plot;
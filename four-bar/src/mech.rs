@@ -10,6 +10,21 @@ pub use self::{
 #[cfg(feature = "serde")]
 mod impl_serde;
 
+/// Version of the serialized format.
+///
+/// The linkages and the other persisted types (with a [`Version`] field) are
+/// serialized with this version, and the unversioned files are treated as
+/// version 0. Loading a file in a newer version is an error.
+#[cfg(feature = "serde")]
+pub const FORMAT_VERSION: u32 = 1;
+
+/// Version field of the persisted types.
+///
+/// Always serialized as [`FORMAT_VERSION`], and the deserialization fails if
+/// the version is newer.
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub struct Version;
+
 pub mod fb;
 pub mod mfb;
 pub mod sfb;
@@ -265,6 +280,55 @@ pub trait CurveGen<const D: usize>: Statable {
             .unwrap_or_default()
     }
 
+    /// Generator for coupler curve paired with the input angles.
+    ///
+    /// The angles are sampled uniformly in the range of motion, the angle
+    /// might be greater than 2π if the range wraps around.
+    ///
+    /// ```
+    /// use four_bar::{mech::CurveGen as _, FourBar};
+    ///
+    /// let fb = FourBar::example();
+    /// let curve = fb.curve_angle(90);
+    /// assert_eq!(curve.len(), 90);
+    /// assert_eq!(curve[0], (0., fb.curve(90)[0]));
+    /// ```
+    fn curve_angle(&self, res: usize) -> Vec<(f64, [f64; D])> {
+        let Some([start, end]) = self.angle_bound().to_value() else {
+            return Vec::new();
+        };
+        let inv = self.inv();
+        linspace(start, end, res)
+            .filter_map(|t| Some((t, self.pos_s(t, inv)?[4])))
+            .collect()
+    }
+
+    /// Generator for all curves paired with the input angles.
+    ///
+    /// The curves are the same as [`CurveGen::curves()`], see also
+    /// [`CurveGen::curve_angle()`].
+    ///
+    /// ```
+    /// use four_bar::{mech::CurveGen as _, FourBar};
+    ///
+    /// let fb = FourBar::example();
+    /// let curves = fb.curves_angle(90);
+    /// assert_eq!(curves.len(), 90);
+    /// assert_eq!(curves[0], (0., fb.curves(90)[0]));
+    /// ```
+    fn curves_angle(&self, res: usize) -> Vec<(f64, [[f64; D]; 3])> {
+        let Some([start, end]) = self.angle_bound().to_value() else {
+            return Vec::new();
+        };
+        let inv = self.inv();
+        linspace(start, end, res)
+            .filter_map(|t| {
+                let [.., p3, p4, p5] = self.pos_s(t, inv)?;
+                Some((t, [p3, p4, p5]))
+            })
+            .collect()
+    }
+
     /// Generator for coupler curve by an input angle list.
     fn curve_by(&self, t: &[f64]) -> Vec<[f64; D]> {
         self.pos_iter(t.iter().copied())
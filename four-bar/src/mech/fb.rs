@@ -2,7 +2,50 @@
 #[doc(no_inline)]
 pub use super::*;
 use efd::na;
-use std::f64::consts::FRAC_PI_6;
+use std::f64::consts::{FRAC_PI_2, FRAC_PI_6, PI, TAU};
+
+/// Resolution of the quantization grid used by the canonical keys.
+///
+/// See [`FourBar::canonical_key()`] and [`NormFourBar::canonical_key()`].
+pub const KEY_RES: f64 = 1e-6;
+
+fn quantize(x: f64) -> u64 {
+    (x / KEY_RES).round() as i64 as u64
+}
+
+// URL-safe Base64 alphabet (RFC 4648 §5), used by the share codes
+const B64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+// Base64 encoding without the padding
+fn b64_encode(bytes: &[u8]) -> String {
+    let mut s = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let mut buf = [0; 3];
+        buf[..chunk.len()].copy_from_slice(chunk);
+        let n = u32::from_be_bytes([0, buf[0], buf[1], buf[2]]);
+        for i in 0..=chunk.len() {
+            s.push(B64[((n >> (18 - 6 * i)) & 63) as usize] as char);
+        }
+    }
+    s
+}
+
+// Base64 decoding without the padding, return `None` if the string is invalid
+fn b64_decode(s: &str) -> Option<Vec<u8>> {
+    let mut bytes = Vec::with_capacity(s.len() * 3 / 4);
+    for chunk in s.as_bytes().chunks(4) {
+        if chunk.len() < 2 {
+            return None;
+        }
+        let mut n = 0;
+        for (i, c) in chunk.iter().enumerate() {
+            n |= (B64.iter().position(|b| b == c)? as u32) << (18 - 6 * i);
+        }
+        let [_, b0, b1, b2] = n.to_be_bytes();
+        bytes.extend_from_slice(&[b0, b1, b2][..chunk.len() - 1]);
+    }
+    Some(bytes)
+}
 
 /// Unnormalized part of four-bar linkage.
 ///
@@ -100,6 +143,84 @@ impl NormFourBar {
     pub fn is_open(&self) -> bool {
         Statable::is_open(self)
     }
+
+    /// Rearrange the links into a closed curve linkage.
+    ///
+    /// The shortest link becomes the driver link and the longest link becomes
+    /// the ground link, which is a Grashof crank rocker, then the lengths are
+    /// normalized by the driver link. The closed curve linkage is returned
+    /// directly. Return `None` if the links are not Grashof.
+    ///
+    /// ```
+    /// use four_bar::{mech::Normalized as _, FourBar, NormFourBar};
+    ///
+    /// // Double rocker
+    /// let (_, fb) = FourBar::benchmarks().swap_remove(2);
+    /// let norm = NormFourBar::normalize(fb);
+    /// assert!(norm.is_open());
+    /// assert!(!norm.to_closed_curve().unwrap().is_open());
+    /// ```
+    pub fn to_closed_curve(self) -> Option<Self> {
+        if !self.ty().is_grashof() {
+            return None;
+        } else if self.ty().is_closed_curve() {
+            return Some(self);
+        }
+        let [s, p, q, l] = self.sorted_loop();
+        let stat = if self.stat.is_c1() {
+            Stat::C1B1
+        } else {
+            Stat::C2B1
+        };
+        Some(Self::from_loop([l, s, q, p], self.l5, self.g, stat))
+    }
+
+    /// Rearrange the links into an open curve linkage.
+    ///
+    /// For the Grashof linkages, the shortest link becomes the coupler link
+    /// and the longest link becomes the ground link, which is a Grashof double
+    /// rocker, then the lengths are normalized by the driver link. The
+    /// non-Grashof linkages are always open, so they are returned directly.
+    ///
+    /// ```
+    /// use four_bar::{mech::Normalized as _, FourBar, NormFourBar};
+    ///
+    /// let norm = NormFourBar::normalize(FourBar::example());
+    /// assert!(!norm.is_open());
+    /// assert!(norm.to_open_curve().is_open());
+    /// ```
+    pub fn to_open_curve(self) -> Self {
+        if self.ty().is_open_curve() {
+            return self;
+        }
+        let [s, p, q, l] = self.sorted_loop();
+        Self::from_loop([l, p, s, q], self.l5, self.g, self.stat)
+    }
+
+    // Sorted planar loop `[s, p, q, l]`
+    fn sorted_loop(&self) -> [f64; 4] {
+        let mut v = self.planar_loop();
+        v.sort_unstable_by(|a, b| a.partial_cmp(b).unwrap());
+        v
+    }
+
+    // Create from the planar loop, normalized by the driver link
+    fn from_loop([l1, l2, l3, l4]: [f64; 4], l5: f64, g: f64, stat: Stat) -> Self {
+        let mut fb = Self { l1, l3, l4, l5, g, stat };
+        fb.scale_inplace(l2.recip());
+        fb
+    }
+
+    /// A hashable canonical key of the linkage.
+    ///
+    /// The parameters are quantized to a grid of [`KEY_RES`] and bit-casted to
+    /// integers, the angle is wrapped to `[0, 2pi)` first. The state is packed
+    /// into the lowest 3 bits of the last element.
+    pub fn canonical_key(&self) -> [u64; 5] {
+        let Self { l1, l3, l4, l5, g, stat } = *self;
+        let g = quantize(g.rem_euclid(TAU)) << 3 | stat as u64;
+        [quantize(l1), quantize(l3), quantize(l4), quantize(l5), g]
+    }
 }
 
 /// Four-bar linkage with offset.
@@ -138,6 +259,67 @@ impl Normalized<2> for NormFourBar {
     }
 }
 
+/// Print the parameters and the type on multiple lines.
+///
+/// The angles are in degrees. The precision is 4 by default and can be
+/// changed by the formatter, e.g. `{:.2}`.
+///
+/// ```
+/// use four_bar::FourBar;
+///
+/// let s = format!("{:.1}", FourBar::example());
+/// assert!(s.contains("Ground: 90.0"));
+/// ```
+impl std::fmt::Display for FourBar {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let p = f.precision().unwrap_or(4);
+        let UnNorm { p1x, p1y, a, l2 } = self.unnorm;
+        let NormFourBar { l1, l3, l4, l5, g, stat } = self.norm;
+        writeln!(f, "Type: {}", self.ty().name())?;
+        writeln!(f, "X offset: {p1x:.p$}")?;
+        writeln!(f, "Y offset: {p1y:.p$}")?;
+        writeln!(f, "Angle offset: {:.p$}°", a.to_degrees())?;
+        writeln!(f, "Ground: {l1:.p$}")?;
+        writeln!(f, "Driver: {l2:.p$}")?;
+        writeln!(f, "Coupler: {l3:.p$}")?;
+        writeln!(f, "Follower: {l4:.p$}")?;
+        writeln!(f, "Extended: {l5:.p$}")?;
+        writeln!(f, "Angle: {:.p$}°", g.to_degrees())?;
+        write!(f, "State: {stat} (inv: {})", self.inv())
+    }
+}
+
+/// Structural comparison between two planar four-bar linkages.
+///
+/// Please see [`FourBar::compare()`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct Comparison {
+    /// Parameter differences `other - self` in the order of
+    /// `[p1x, p1y, a, l1, l2, l3, l4, l5, g]`
+    ///
+    /// The angle differences are wrapped to `[-pi, pi)`.
+    pub diff: [f64; 9],
+    /// Hausdorff distance between the coupler curves
+    pub hausdorff: f64,
+    /// True if the linkages are the same type
+    pub same_ty: bool,
+}
+
+impl Comparison {
+    /// Parameter names of [`Comparison::diff`].
+    pub const NAMES: [&'static str; 9] = [
+        "X offset",
+        "Y offset",
+        "Angle offset",
+        "Ground",
+        "Driver",
+        "Coupler",
+        "Follower",
+        "Extended",
+        "Angle",
+    ];
+}
+
 impl FourBar {
     /// An example crank rocker.
     pub const fn example() -> Self {
@@ -151,6 +333,748 @@ impl FourBar {
         };
         Self::new(UnNorm::from_driver(35.), norm)
     }
+
+    /// A curated set of well-known linkages for tests and demos.
+    ///
+    /// The set contains a crank rocker ([`FourBar::example()`]), a double
+    /// crank, a double rocker, a triple rocker and the Chebyshev's lambda
+    /// linkage for approximate straight line.
+    ///
+    /// ```
+    /// use four_bar::FourBar;
+    ///
+    /// let (name, fb) = &FourBar::benchmarks()[0];
+    /// assert_eq!(*name, "Crank rocker");
+    /// assert_eq!(*fb, FourBar::example());
+    /// ```
+    pub fn benchmarks() -> Vec<(&'static str, Self)> {
+        let fb = |l1, l2, l3, l4, l5, g| {
+            let norm = NormFourBar { l1, l3, l4, l5, g, stat: Stat::C1B1 };
+            Self::new(UnNorm::from_driver(l2), norm)
+        };
+        vec![
+            ("Crank rocker", Self::example()),
+            ("Double crank", fb(30., 70., 80., 75., 40., 0.5)),
+            ("Double rocker", fb(90., 70., 30., 80., 40., FRAC_PI_6)),
+            ("Triple rocker", fb(50., 60., 70., 90., 45., FRAC_PI_6)),
+            ("Chebyshev lambda", fb(40., 20., 50., 50., 100., 0.)),
+        ]
+    }
+
+    /// Position equations of the joints in plain text.
+    ///
+    /// The equations follow the structure of the curve generation, where `t`
+    /// is the input angle, and the numeric lengths are substituted. The
+    /// output is suitable for pasting into a CAS.
+    ///
+    /// ```
+    /// use four_bar::FourBar;
+    ///
+    /// let eq = FourBar::example().to_equations();
+    /// for l in ["90", "35", "70", "45"] {
+    ///     assert!(eq.contains(l));
+    /// }
+    /// ```
+    pub fn to_equations(&self) -> String {
+        let UnNorm { p1x, p1y, a, l2 } = self.unnorm;
+        let NormFourBar { l1, l3, l4, l5, g, .. } = self.norm;
+        // The sign of the follower side
+        let sign = if self.inv() { "-" } else { "+" };
+        format!(
+            "\
+P1x = {p1x}
+P1y = {p1y}
+P2x = P1x + {l1} * cos({a})
+P2y = P1y + {l1} * sin({a})
+P3x = P1x + {l2} * cos({a} + t)
+P3y = P1y + {l2} * sin({a} + t)
+r = sqrt((P2x - P3x)^2 + (P2y - P3y)^2)
+c = ({l3}^2 - {l4}^2 + r^2) / (2 * r)
+s = {sign}sqrt({l3}^2 - c^2)
+P4x = P3x + (c * (P2x - P3x) - s * (P2y - P3y)) / r
+P4y = P3y + (s * (P2x - P3x) + c * (P2y - P3y)) / r
+P5x = P3x + {l5} * cos({g} + atan2(P4y - P3y, P4x - P3x))
+P5y = P3y + {l5} * sin({g} + atan2(P4y - P3y, P4x - P3x))
+"
+        )
+    }
+
+    /// Shape signature of the coupler curve.
+    ///
+    /// The normalized EFD coefficients of the given `harmonic` are flattened,
+    /// which is invariant to the translation, rotation and scaling of the
+    /// linkage. Return an empty vector if the linkage is invalid.
+    ///
+    /// ```
+    /// use four_bar::FourBar;
+    ///
+    /// let fb1 = FourBar::example();
+    /// let mut fb2 = fb1.clone();
+    /// fb2.unnorm.set_origin(10., -5.);
+    /// fb2.unnorm.set_rotation(1.);
+    /// fb2.unnorm.l2 *= 2.;
+    /// fb2.norm.l1 *= 2.;
+    /// fb2.norm.l3 *= 2.;
+    /// fb2.norm.l4 *= 2.;
+    /// fb2.norm.l5 *= 2.;
+    /// let sig1 = fb1.shape_signature(10);
+    /// let sig2 = fb2.shape_signature(10);
+    /// assert_eq!(sig1.len(), 40);
+    /// assert!(sig1.iter().zip(&sig2).all(|(a, b)| (a - b).abs() < 1e-6));
+    /// ```
+    pub fn shape_signature(&self, harmonic: usize) -> Vec<f64> {
+        const RES: usize = 360;
+        let curve = self.curve(RES);
+        if curve.len() < 2 {
+            return Vec::new();
+        }
+        let efd = efd::Efd2::from_curve_harmonic(curve, self.is_open(), harmonic);
+        let (coeffs, _) = efd.into_inner();
+        coeffs
+            .into_iter()
+            .flat_map(|m| m.data.0)
+            .flatten()
+            .collect()
+    }
+
+    /// A hashable canonical key of the linkage.
+    ///
+    /// The 9 parameters are quantized to a grid of [`KEY_RES`] in the order of
+    /// `[p1x, p1y, a, l2, l1, l3, l4, l5, g]`. Please see
+    /// [`NormFourBar::canonical_key()`] for the packing rules.
+    ///
+    /// ```
+    /// use four_bar::FourBar;
+    /// use std::collections::HashSet;
+    ///
+    /// let fb1 = FourBar::example();
+    /// let mut fb2 = fb1.clone();
+    /// fb2.unnorm.p1x += four_bar::mech::fb::KEY_RES * 0.1;
+    /// let set = HashSet::from([fb1.canonical_key(), fb2.canonical_key()]);
+    /// assert_eq!(set.len(), 1);
+    /// ```
+    pub fn canonical_key(&self) -> [u64; 9] {
+        let UnNorm { p1x, p1y, a, l2 } = self.unnorm;
+        let [l1, l3, l4, l5, g] = self.norm.canonical_key();
+        let a = quantize(a.rem_euclid(TAU));
+        [
+            quantize(p1x),
+            quantize(p1y),
+            a,
+            quantize(l2),
+            l1,
+            l3,
+            l4,
+            l5,
+            g,
+        ]
+    }
+
+    /// Snap the linkage to the nearest Grashof linkage.
+    ///
+    /// The longest link is shortened by the minimum amount to satisfy the
+    /// Grashof condition `s + l < p + q`, and the other links are unchanged.
+    /// Return true if the lengths are changed.
+    ///
+    /// Please note that the coupler curve may be altered.
+    ///
+    /// ```
+    /// use four_bar::{
+    ///     mech::{PlanarLoop as _, Statable as _},
+    ///     FourBar,
+    /// };
+    ///
+    /// let mut fb = FourBar::example();
+    /// fb.norm.l1 = 106.;
+    /// assert_eq!(fb.planar_loop(), [106., 35., 70., 70.]);
+    /// // One unit past the Grashof condition:
+    /// // s + l = 35 + 106 = 141, p + q = 70 + 70 = 140
+    /// assert!(!fb.ty().is_grashof());
+    /// assert!(fb.snap_to_grashof());
+    /// assert!(fb.ty().is_grashof());
+    /// assert!(fb.norm.l1 < 105.);
+    /// assert!(!fb.snap_to_grashof());
+    /// ```
+    pub fn snap_to_grashof(&mut self) -> bool {
+        let mut ls = self.planar_loop().map(|l| l.abs());
+        let mut ind = [0, 1, 2, 3];
+        ind.sort_unstable_by(|a, b| ls[*a].partial_cmp(&ls[*b]).unwrap());
+        let [s, p, q, l] = ind.map(|i| ls[i]);
+        if s + l < p + q {
+            return false;
+        }
+        // Keep a small margin to avoid the change point
+        let eps = 1e-9 * (p + q);
+        ls[ind[3]] = p + q - s - eps;
+        let [l1, l2, l3, l4] = ls;
+        self.norm.l1 = l1;
+        self.unnorm.l2 = l2;
+        self.norm.l3 = l3;
+        self.norm.l4 = l4;
+        true
+    }
+
+    /// Count the branches that pass within `tol` of the `point`.
+    ///
+    /// The both inversion branches (assembly modes) are swept over a full
+    /// rotation of the driver link, so the result is 0, 1 or 2. This explains
+    /// why a target point may be traced by one or both assembly modes.
+    ///
+    /// ```
+    /// use four_bar::{mech::CurveGen as _, FourBar};
+    ///
+    /// let fb = FourBar::example();
+    /// let [.., p] = fb.pos_s(1., false).unwrap();
+    /// assert_eq!(fb.branches_reaching(p, 1e-2), 1);
+    /// assert_eq!(fb.branches_reaching([1e3, 1e3], 1.), 0);
+    /// ```
+    pub fn branches_reaching(&self, point: [f64; 2], tol: f64) -> u8 {
+        let point = na::Point2::from(point);
+        (self.branch_dist(point, false) <= tol) as u8 + (self.branch_dist(point, true) <= tol) as u8
+    }
+
+    /// The minimum distance from the `target_point` to the coupler curve.
+    ///
+    /// The current branch is swept over a full rotation of the driver link.
+    /// This measures how far the linkage is from reaching the target point,
+    /// which is zero if the point is on the coupler curve. Return infinity if
+    /// the linkage cannot be assembled.
+    ///
+    /// ```
+    /// use four_bar::{mech::CurveGen as _, FourBar};
+    ///
+    /// let fb = FourBar::example();
+    /// let [.., p] = fb.pos(1.).unwrap();
+    /// assert!(fb.assemble_error(p) < 1e-2);
+    /// assert!(fb.assemble_error([1e3, 1e3]) > 1e2);
+    /// ```
+    pub fn assemble_error(&self, target_point: [f64; 2]) -> f64 {
+        self.branch_dist(na::Point2::from(target_point), self.inv())
+    }
+
+    /// Angular velocity ratio `ω4/ω2` of the follower link to the driver
+    /// link at the input angle `theta`.
+    ///
+    /// Derived from the velocity equations of the loop closure. Return `None`
+    /// if the linkage cannot be assembled, or at the singular positions where
+    /// the coupler link and the follower link are collinear.
+    ///
+    /// ```
+    /// use four_bar::FourBar;
+    ///
+    /// let fb = FourBar::example();
+    /// let ratio = fb.velocity_ratio(0.).unwrap();
+    /// assert!((ratio + 35. / 55.).abs() < 1e-12);
+    /// ```
+    pub fn velocity_ratio(&self, theta: f64) -> Option<f64> {
+        let [p1, p2, p3, p4, _] = self.pos(theta)?.map(na::Point2::from);
+        let (r2, r3, r4) = (p3 - p1, p4 - p3, p4 - p2);
+        let den = r4.perp(&r3);
+        (den.abs() > 1e-9 * r4.norm() * r3.norm()).then(|| r2.perp(&r3) / den)
+    }
+
+    /// Mechanical advantage `T4/T2` of the output torque on the follower link
+    /// to the input torque on the driver link at the input angle `theta`.
+    ///
+    /// Under the ideal power balance `T2·ω2 = T4·ω4` (friction and inertia
+    /// ignored), it is exactly the reciprocal of
+    /// [`FourBar::velocity_ratio()`], and is calculated from the link vectors
+    /// directly as `(r4 × r3) / (r2 × r3)`. Return `None` if the linkage
+    /// cannot be assembled, or at the toggle positions where the driver link
+    /// and the coupler link are collinear and the advantage diverges.
+    ///
+    /// ```
+    /// use four_bar::FourBar;
+    ///
+    /// let fb = FourBar::example();
+    /// let ma = fb.mechanical_advantage(0.).unwrap();
+    /// assert!((ma + 55. / 35.).abs() < 1e-12);
+    /// ```
+    pub fn mechanical_advantage(&self, theta: f64) -> Option<f64> {
+        let [p1, p2, p3, p4, _] = self.pos(theta)?.map(na::Point2::from);
+        let (r2, r3, r4) = (p3 - p1, p4 - p3, p4 - p2);
+        let den = r2.perp(&r3);
+        (den.abs() > 1e-9 * r2.norm() * r3.norm()).then(|| r4.perp(&r3) / den)
+    }
+
+    /// Input torque required to balance a static `force` at the coupler
+    /// point, at the input angle `theta`.
+    ///
+    /// Derived from the virtual work `τ + F·(dp5/dθ) = 0`, where the friction
+    /// and the inertia are ignored. Return `None` at the same positions as
+    /// [`FourBar::velocity_ratio()`], where the torque approaches infinity.
+    ///
+    /// ```
+    /// use four_bar::FourBar;
+    ///
+    /// let fb = FourBar::example();
+    /// let torque = fb.input_torque(1., [0., -1.]).unwrap();
+    /// assert!(torque.is_finite());
+    /// ```
+    pub fn input_torque(&self, theta: f64, force: [f64; 2]) -> Option<f64> {
+        let [p1, p2, p3, p4, p5] = self.pos(theta)?.map(na::Point2::from);
+        let (r2, r3, r4) = (p3 - p1, p4 - p3, p4 - p2);
+        let den = r3.perp(&r4);
+        if den.abs() <= 1e-9 * r4.norm() * r3.norm() {
+            return None;
+        }
+        // Angular velocity ratio of the coupler link to the driver link
+        let w3 = -r2.perp(&r4) / den;
+        let rot = |v: na::Vector2<f64>| na::Vector2::new(-v.y, v.x);
+        let v5 = rot(r2) + w3 * rot(p5 - p3);
+        Some(-na::Vector2::from(force).dot(&v5))
+    }
+
+    /// Input-output function `(θ2, θ4)` of the driver angle and the follower
+    /// angle, sampled `n` times over the range of motion.
+    ///
+    /// Both angles are measured from the ground link. The follower angle is
+    /// unwrapped to be continuous, so it may exceed `[-π, π]`. Return an empty
+    /// vector if the linkage is invalid.
+    ///
+    /// ```
+    /// use four_bar::FourBar;
+    ///
+    /// let io = FourBar::example().io_function(90);
+    /// assert_eq!(io.len(), 90);
+    /// ```
+    pub fn io_function(&self, n: usize) -> Vec<(f64, f64)> {
+        let Some([start, end]) = self.angle_bound().to_value() else {
+            return Vec::new();
+        };
+        let mut last = None;
+        super::linspace(start, end, n)
+            .filter_map(|t| {
+                let [p1, p2, _, p4, _] = self.pos(t)?;
+                let ground = (p2[1] - p1[1]).atan2(p2[0] - p1[0]);
+                let mut t4 = (p4[1] - p2[1]).atan2(p4[0] - p2[0]) - ground;
+                if let Some(last) = last {
+                    t4 -= ((t4 - last) / TAU).round() * TAU;
+                }
+                last = Some(t4);
+                Some((t, t4))
+            })
+            .collect()
+    }
+
+    /// The vertices `[p3, p4, p5]` of the coupler link at the input angle
+    /// `theta`, which is a rigid triangle.
+    ///
+    /// Return [`Error::Unassemblable`](crate::Error::Unassemblable) if the
+    /// linkage cannot be assembled.
+    ///
+    /// ```
+    /// use four_bar::FourBar;
+    ///
+    /// let fb = FourBar::example();
+    /// let [p3, p4, _] = fb.coupler_triangle(0.).unwrap();
+    /// assert!((p3[0] - 35.).abs() < 1e-12);
+    /// assert!(((p4[0] - p3[0]).hypot(p4[1] - p3[1]) - 70.).abs() < 1e-12);
+    /// ```
+    pub fn coupler_triangle(&self, theta: f64) -> Result<[[f64; 2]; 3], crate::Error> {
+        let [_, _, p3, p4, p5] = self.pos(theta).ok_or(crate::Error::Unassemblable)?;
+        Ok([p3, p4, p5])
+    }
+
+    /// Boundary of the reachable coupler positions.
+    ///
+    /// The both inversion branches (assembly modes) are sampled `n` times over
+    /// a full rotation of the driver link, and the outline is the convex hull
+    /// of the coupler points in counter-clockwise order. The first point is not
+    /// repeated at the end, see [`crate::curve::close_loop()`]. Return an
+    /// empty vector if the linkage cannot be assembled.
+    ///
+    /// ```
+    /// use four_bar::FourBar;
+    ///
+    /// let boundary = FourBar::example().reachable_boundary(90);
+    /// assert!(boundary.len() > 3);
+    /// ```
+    pub fn reachable_boundary(&self, n: usize) -> Vec<[f64; 2]> {
+        let pts = [false, true]
+            .into_iter()
+            .flat_map(|inv| super::linspace(0., TAU, n).filter_map(move |t| self.pos_s(t, inv)))
+            .map(|[.., p5]| p5)
+            .collect();
+        convex_hull(pts)
+    }
+
+    // Minimum distance from the point to the polyline of a branch
+    fn branch_dist(&self, point: na::Point2<f64>, inv: bool) -> f64 {
+        const RES: usize = 720;
+        let mut last = None::<na::Point2<f64>>;
+        (0..=RES)
+            .filter_map(|i| {
+                let t = i as f64 / RES as f64 * TAU;
+                let curr = self.pos_s(t, inv).map(|[.., p5]| na::Point2::from(p5));
+                let prev = std::mem::replace(&mut last, curr);
+                match (prev, curr) {
+                    (Some(a), Some(b)) => Some(seg_dist(point, a, b)),
+                    (None, Some(b)) => Some((point - b).norm()),
+                    _ => None,
+                }
+            })
+            .fold(f64::INFINITY, f64::min)
+    }
+
+    /// Return true if all the joints are finite at the input angle `theta`.
+    ///
+    /// ```
+    /// use four_bar::FourBar;
+    ///
+    /// assert!(FourBar::example().is_assemblable_at(3.));
+    /// // Double rocker
+    /// let (_, fb) = &FourBar::benchmarks()[2];
+    /// assert!(fb.is_assemblable_at(1.));
+    /// assert!(!fb.is_assemblable_at(3.));
+    /// ```
+    pub fn is_assemblable_at(&self, theta: f64) -> bool {
+        self.pos(theta).is_some()
+    }
+
+    /// The input angle intervals where the linkage can be assembled.
+    ///
+    /// An alias of [`FourBar::angle_bounds_all()`].
+    pub fn assemblable_range(&self) -> Vec<[f64; 2]> {
+        self.angle_bounds_all()
+    }
+
+    /// All continuous input angle intervals of the current circuit.
+    ///
+    /// The full rotation of the driver link is scanned for the assembly
+    /// failures, and the boundaries are refined by bisection. Unlike
+    /// [`Statable::angle_bound()`], the disjoint intervals of the non-Grashof
+    /// linkages are all returned. The `end` may be less than the `start` if
+    /// the interval passes through zero, the same as [`AngleBound`].
+    ///
+    /// Return `[[0, 2π]]` for the fully rotatable driver, and an empty vector
+    /// if the linkage cannot be assembled.
+    pub fn angle_bounds_all(&self) -> Vec<[f64; 2]> {
+        const RES: usize = 720;
+        let valid = |t| self.is_assemblable_at(t);
+        let step = TAU / RES as f64;
+        let ok = (0..RES).map(|i| valid(i as f64 * step)).collect::<Vec<_>>();
+        if ok.iter().all(|b| *b) {
+            return vec![[0., TAU]];
+        }
+        // Bisection between an invalid angle and a valid angle
+        let refine = |mut bad: f64, mut good: f64| {
+            for _ in 0..40 {
+                let mid = (bad + good) * 0.5;
+                if valid(mid) {
+                    good = mid;
+                } else {
+                    bad = mid;
+                }
+            }
+            good.rem_euclid(TAU)
+        };
+        let mut bounds = Vec::new();
+        for i in (0..RES).filter(|&i| ok[i] && !ok[(i + RES - 1) % RES]) {
+            let j = (i..i + RES).find(|&j| !ok[(j + 1) % RES]).unwrap();
+            let start = refine((i as f64 - 1.) * step, i as f64 * step);
+            let end = refine((j + 1) as f64 * step, j as f64 * step);
+            bounds.push([start, end]);
+        }
+        bounds
+    }
+
+    /// Coupler curve split into the contiguous segments.
+    ///
+    /// The input angle is sampled `n` times in `[0, 2π)`, and the curve is
+    /// split where the linkage cannot be assembled, so the segments can be
+    /// drawn as disconnected polylines. The segment that passes through zero
+    /// is joined together.
+    ///
+    /// See also [`FourBar::angle_bounds_all()`].
+    ///
+    /// ```
+    /// use four_bar::FourBar;
+    ///
+    /// let segs = FourBar::example().curve_segments(90);
+    /// assert_eq!(segs.len(), 1);
+    /// assert_eq!(segs[0].len(), 90);
+    /// ```
+    pub fn curve_segments(&self, n: usize) -> Vec<Vec<[f64; 2]>> {
+        let inv = self.inv();
+        let step = TAU / n as f64;
+        let mut segs = vec![Vec::new()];
+        for i in 0..n {
+            match self.pos_s(i as f64 * step, inv) {
+                Some([.., p5]) => segs.last_mut().unwrap().push(p5),
+                None if segs.last().unwrap().is_empty() => (),
+                None => segs.push(Vec::new()),
+            }
+        }
+        if segs.last().unwrap().is_empty() {
+            segs.pop();
+        } else if segs.len() > 1 && self.pos_s(0., inv).is_some() {
+            // Join the segment passing through zero
+            let first = segs.swap_remove(0);
+            segs[0].extend(first);
+        }
+        segs
+    }
+
+    /// Check if the linkage passes through a change point in its motion.
+    ///
+    /// At a change point, the driver-coupler joint, the follower-coupler joint
+    /// and the ground joint of the follower are collinear, and the linkage can
+    /// switch to the other branch, so the real mechanism moves
+    /// discontinuously. The discriminant of the coupler-follower circle
+    /// intersection is sampled `n` times in the range of motion, and the defect
+    /// is found if it vanishes or changes its sign. The discriminant may touch
+    /// zero between the samples, so its local minima are refined by the
+    /// ternary search. The toggle positions at the ends of an open range are
+    /// not counted.
+    ///
+    /// ```
+    /// use four_bar::{mech::fb::UnNorm, FourBar, NormFourBar};
+    ///
+    /// assert!(!FourBar::example().has_branch_defect(90));
+    /// // A kite linkage folds at the zero angle
+    /// let norm = NormFourBar { l1: 2., l3: 1., l4: 2., ..Default::default() };
+    /// let fb = FourBar::new(UnNorm::from_driver(1.), norm);
+    /// assert!(fb.has_branch_defect(90));
+    /// ```
+    pub fn has_branch_defect(&self, n: usize) -> bool {
+        let Some([start, end]) = self.angle_bound().to_value() else {
+            return false;
+        };
+        let NormFourBar { l1, l3, l4, .. } = self.norm;
+        let l2 = self.unnorm.l2;
+        // The discriminant (h^2) of the circle intersection, in the local frame
+        let disc = |t: f64| {
+            let r_2 = (l1 - l2 * t.cos()).powi(2) + (l2 * t.sin()).powi(2);
+            let c = (l3 * l3 - l4 * l4 + r_2) / (2. * r_2.sqrt());
+            l3 * l3 - c * c
+        };
+        let tol = 1e-9 * l3 * l3;
+        let is_open = self.is_open();
+        // Skip the toggle position at the start of an open range
+        let ts = super::linspace(start, end, n).skip(usize::from(is_open));
+        let ts = ts.collect::<Vec<_>>();
+        let h = ts.iter().map(|&t| disc(t)).collect::<Vec<_>>();
+        if h.iter().any(|h| h.abs() <= tol) || h.windows(2).any(|w| w[0].signum() != w[1].signum())
+        {
+            return true;
+        } else if h.len() < 3 {
+            return false;
+        }
+        // The neighbors are wrapped around in the closed range
+        let len = h.len();
+        let (prev, next) = (|i| (i + len - 1) % len, |i| (i + 1) % len);
+        let step = ts[1] - ts[0];
+        let range = if is_open { 1..len - 1 } else { 0..len };
+        range
+            .filter(|&i| h[i] <= h[prev(i)] && h[i] <= h[next(i)])
+            .any(|i| {
+                let [mut a, mut b] = [ts[i] - step, ts[i] + step];
+                for _ in 0..50 {
+                    let (t1, t2) = (a + (b - a) / 3., b - (b - a) / 3.);
+                    if disc(t1) < disc(t2) {
+                        b = t2;
+                    } else {
+                        a = t1;
+                    }
+                }
+                disc(0.5 * (a + b)).abs() <= tol
+            })
+    }
+
+    /// Uniformity of the coupler point speed over the motion.
+    ///
+    /// Returns the coefficient of variation (standard deviation over mean) of
+    /// the spacings between the `n` consecutive coupler points, which are
+    /// sampled at the equal driver angles. Lower is smoother, and zero means
+    /// the constant speed with a constant driver speed. Returns `None` if the
+    /// linkage is invalid or the coupler point does not move.
+    ///
+    /// ```
+    /// use four_bar::FourBar;
+    ///
+    /// let cv = FourBar::example().speed_uniformity(180).unwrap();
+    /// assert!(cv > 0.);
+    /// ```
+    pub fn speed_uniformity(&self, n: usize) -> Option<f64> {
+        let curve = self.curve::<2>(n);
+        let curve = if self.is_open() {
+            curve
+        } else {
+            crate::curve::close_loop(&curve)
+        };
+        let step = curve
+            .windows(2)
+            .map(|w| (w[1][0] - w[0][0]).hypot(w[1][1] - w[0][1]))
+            .collect::<Vec<_>>();
+        if step.len() < 2 {
+            return None;
+        }
+        let len = step.len() as f64;
+        let mean = step.iter().sum::<f64>() / len;
+        if mean <= f64::EPSILON {
+            return None;
+        }
+        let var = step.iter().map(|d| (d - mean).powi(2)).sum::<f64>() / len;
+        Some(var.sqrt() / mean)
+    }
+
+    /// Angle of the mirror symmetry axis of the coupler curve.
+    ///
+    /// The coupler curve is generated with `n` points, and it is symmetric if
+    /// its [`curve::mirror_err()`](crate::curve::mirror_err) is less than
+    /// `tol`. The axis passes through the centroid of the curve, and the angle
+    /// is in the range of `[0, π)`. Returns `None` if the curve is not
+    /// symmetric.
+    ///
+    /// ```
+    /// use four_bar::FourBar;
+    ///
+    /// assert_eq!(FourBar::example().coupler_symmetry(180, 1e-2), None);
+    /// ```
+    pub fn coupler_symmetry(&self, n: usize, tol: f64) -> Option<f64> {
+        let curve = self.curve::<2>(n);
+        let is_open = self.is_open();
+        if curve.len() < 3 || crate::curve::mirror_err(&curve, is_open) > tol {
+            return None;
+        }
+        // The axis is one of the principal axes of the first harmonic
+        let efd = efd::Efd2::from_curve(&curve, is_open);
+        let geo = efd.as_geo();
+        let [cx, cy] = geo.trans();
+        let reflect = |phi: f64| {
+            let (sin, cos) = (2. * phi).sin_cos();
+            (curve.iter())
+                .map(|&[x, y]| {
+                    let (dx, dy) = (x - cx, y - cy);
+                    [cx + cos * dx + sin * dy, cy + sin * dx - cos * dy]
+                })
+                .collect::<Vec<_>>()
+        };
+        let phi = geo.rot().angle();
+        [phi, phi + FRAC_PI_2]
+            .into_iter()
+            .map(|phi| (crate::curve::hausdorff(&curve, &reflect(phi)), phi))
+            .min_by(|(a, _), (b, _)| a.total_cmp(b))
+            .map(|(_, phi)| phi.rem_euclid(PI))
+    }
+
+    /// Column names of [`FourBar::to_csv_row()`].
+    pub const CSV_COLUMNS: [&'static str; 10] =
+        ["p1x", "p1y", "a", "l1", "l2", "l3", "l4", "l5", "g", "stat"];
+
+    /// Flatten the linkage into a single CSV row.
+    ///
+    /// The column order is `[p1x, p1y, a, l1, l2, l3, l4, l5, g, stat]`, the
+    /// same as the parameter list of [`FourBar`], followed by the state number
+    /// (see [`Stat`]), where the states 3 and 4 are the inverted ones. The
+    /// angles are in radians.
+    ///
+    /// ```
+    /// use four_bar::FourBar;
+    ///
+    /// let fb = FourBar::example();
+    /// let row = fb.to_csv_row();
+    /// assert_eq!(row[4], 35.);
+    /// assert_eq!(FourBar::from_csv_row(row).unwrap(), fb);
+    /// ```
+    pub fn to_csv_row(&self) -> [f64; 10] {
+        let UnNorm { p1x, p1y, a, l2 } = self.unnorm;
+        let NormFourBar { l1, l3, l4, l5, g, stat } = self.norm;
+        [p1x, p1y, a, l1, l2, l3, l4, l5, g, stat as u8 as f64]
+    }
+
+    /// Parse the linkage from a single CSV row.
+    ///
+    /// See [`FourBar::to_csv_row()`] for the column order. Return
+    /// [`Error::InvalidStat`](crate::Error::InvalidStat) if the state number
+    /// is invalid.
+    pub fn from_csv_row(row: [f64; 10]) -> Result<Self, crate::Error> {
+        let [p1x, p1y, a, l1, l2, l3, l4, l5, g, stat] = row;
+        if stat.fract() != 0. || !(0. ..=u8::MAX as f64).contains(&stat) {
+            return Err(crate::Error::InvalidStat);
+        }
+        let stat = Stat::try_from(stat as u8)?;
+        let unnorm = UnNorm { p1x, p1y, a, l2 };
+        Ok(Self::new(unnorm, NormFourBar { l1, l3, l4, l5, g, stat }))
+    }
+
+    /// Encode the linkage into a compact URL-safe string.
+    ///
+    /// The parameters `[p1x, p1y, a, l1, l2, l3, l4, l5, g]` are stored as
+    /// little-endian `f64` followed by the state number in a byte (the same
+    /// order as [`FourBar::to_csv_row()`]), then encoded by the URL-safe Base64
+    /// without the padding. The code can be used as a URL fragment.
+    ///
+    /// ```
+    /// use four_bar::FourBar;
+    ///
+    /// let fb = FourBar::example();
+    /// let code = fb.to_share_code();
+    /// assert_eq!(code.len(), 98);
+    /// assert_eq!(FourBar::from_share_code(&code), Ok(fb));
+    /// ```
+    pub fn to_share_code(&self) -> String {
+        let row = self.to_csv_row();
+        let mut bytes = row[..9]
+            .iter()
+            .flat_map(|x| x.to_le_bytes())
+            .collect::<Vec<_>>();
+        bytes.push(row[9] as u8);
+        b64_encode(&bytes)
+    }
+
+    /// Decode the linkage from the string of [`FourBar::to_share_code()`].
+    ///
+    /// A leading `#` is ignored, so the URL fragment can be passed directly.
+    /// Return [`Error::InvalidCode`](crate::Error::InvalidCode) if the code is
+    /// malformed, [`Error::InvalidStat`](crate::Error::InvalidStat) if the
+    /// state is invalid, or
+    /// [`Error::InvalidLinkage`](crate::Error::InvalidLinkage)
+    /// if any parameter is not finite.
+    pub fn from_share_code(code: &str) -> Result<Self, crate::Error> {
+        use crate::Error;
+        let bytes = b64_decode(code.strip_prefix('#').unwrap_or(code)).ok_or(Error::InvalidCode)?;
+        let (&stat, params) = bytes.split_last().ok_or(Error::InvalidCode)?;
+        if params.len() != 9 * 8 {
+            return Err(Error::InvalidCode);
+        }
+        let mut row = [0.; 10];
+        for (x, b) in std::iter::zip(&mut row, params.chunks_exact(8)) {
+            *x = f64::from_le_bytes(b.try_into().unwrap());
+        }
+        row[9] = stat as f64;
+        if !row.iter().all(|x| x.is_finite()) {
+            return Err(Error::InvalidLinkage);
+        }
+        Self::from_csv_row(row)
+    }
+
+    /// Compare the structure with another linkage.
+    ///
+    /// The coupler curves are generated with `n` points.
+    ///
+    /// ```
+    /// use four_bar::FourBar;
+    ///
+    /// let fb = FourBar::example();
+    /// let mut other = fb.clone();
+    /// other.l5 += 1.;
+    /// let cmp = fb.compare(&other, 90);
+    /// assert_eq!(cmp.diff[7], 1.);
+    /// assert!(cmp.hausdorff > 0.);
+    /// assert!(cmp.same_ty);
+    /// ```
+    pub fn compare(&self, other: &Self, n: usize) -> Comparison {
+        let (a, b) = (self.to_csv_row(), other.to_csv_row());
+        let mut diff = std::array::from_fn(|i| b[i] - a[i]);
+        for i in [2, 8] {
+            diff[i] = (diff[i] + PI).rem_euclid(TAU) - PI;
+        }
+        let hausdorff = crate::curve::hausdorff(&self.curve::<2>(n), &other.curve::<2>(n));
+        let same_ty = self.ty() == other.ty();
+        Comparison { diff, hausdorff, same_ty }
+    }
 }
 
 impl Statable for NormFourBar {
@@ -188,6 +1112,47 @@ impl CurveGen<2> for FourBar {
     }
 }
 
+// Convex hull in counter-clockwise order by the monotone chain algorithm
+fn convex_hull(mut pts: Vec<[f64; 2]>) -> Vec<[f64; 2]> {
+    pts.sort_unstable_by(|a, b| a.partial_cmp(b).unwrap());
+    pts.dedup();
+    if pts.len() < 3 {
+        return pts;
+    }
+    let cross = |o: [f64; 2], a: [f64; 2], b: [f64; 2]| {
+        (a[0] - o[0]) * (b[1] - o[1]) - (a[1] - o[1]) * (b[0] - o[0])
+    };
+    let half = |pts: &mut dyn Iterator<Item = [f64; 2]>| {
+        let mut chain = Vec::<[f64; 2]>::new();
+        for p in pts {
+            while let [.., o, a] = chain[..] {
+                if cross(o, a, p) > 0. {
+                    break;
+                }
+                chain.pop();
+            }
+            chain.push(p);
+        }
+        // The last point is the first point of the other chain
+        chain.pop();
+        chain
+    };
+    let mut hull = half(&mut pts.iter().copied());
+    hull.extend(half(&mut pts.iter().rev().copied()));
+    hull
+}
+
+fn seg_dist(p: na::Point2<f64>, a: na::Point2<f64>, b: na::Point2<f64>) -> f64 {
+    let ab = b - a;
+    let len2 = ab.norm_squared();
+    let t = if len2 > 0. {
+        ((p - a).dot(&ab) / len2).clamp(0., 1.)
+    } else {
+        0.
+    };
+    (p - (a + t * ab)).norm()
+}
+
 fn angle(p: na::Point2<f64>, d: f64, a: f64) -> na::Point2<f64> {
     p + d * na::Vector2::new(a.cos(), a.sin())
 }
@@ -230,3 +1195,310 @@ pub(crate) fn curve_interval(
     let js = build_coords![p1, p2, p3, p4, p5];
     js.iter().flatten().all(|x| x.is_finite()).then_some(js)
 }
+
+#[test]
+fn branches_reaching_one_side() {
+    use crate::mech::CurveGen as _;
+    let fb = FourBar::example();
+    for t in [0., 1., 2., 3.] {
+        for inv in [false, true] {
+            let [.., p] = fb.pos_s(t, inv).unwrap();
+            // The two branches of the example are at least 9 units apart here
+            assert_eq!(fb.branches_reaching(p, 1e-2), 1);
+            assert_eq!(fb.branches_reaching(p, 5.), 1);
+        }
+    }
+    assert_eq!(fb.branches_reaching([0., 0.], 1e3), 2);
+}
+
+#[test]
+fn benchmarks_valid() {
+    for (name, fb) in FourBar::benchmarks() {
+        assert!(fb.is_valid(), "{name}");
+        assert!(!fb.curve(90).is_empty(), "{name}");
+    }
+}
+
+#[test]
+fn angle_bounds_with_gap() {
+    use approx::assert_abs_diff_eq;
+    assert_eq!(FourBar::example().angle_bounds_all(), [[0., TAU]]);
+    // Double rocker with two disjoint intervals on each circuit
+    let (_, fb) = FourBar::benchmarks().swap_remove(2);
+    for stat in [Stat::C1B1, Stat::C2B1] {
+        let fb = fb.clone().with_stat(stat);
+        let bounds = fb.angle_bounds_all();
+        assert_eq!(bounds.len(), 2);
+        let b1 = fb
+            .clone()
+            .with_stat(Stat::C1B1)
+            .angle_bound()
+            .to_value()
+            .unwrap();
+        let b2 = fb
+            .clone()
+            .with_stat(Stat::C1B2)
+            .angle_bound()
+            .to_value()
+            .unwrap();
+        for (a, b) in std::iter::zip(bounds, [b2, b1]) {
+            assert_abs_diff_eq!(a[0], b[0], epsilon = 1e-6);
+            assert_abs_diff_eq!(a[1], b[1], epsilon = 1e-6);
+        }
+    }
+}
+
+#[test]
+fn curve_angle_spans_bound() {
+    use crate::mech::CurveGen as _;
+    const RES: usize = 180;
+    // Double rocker with an open range of motion
+    let (_, fb) = FourBar::benchmarks().swap_remove(2);
+    let [start, end] = fb.angle_bound().to_value().unwrap();
+    let end = if end > start { end } else { end + TAU };
+    let step = (end - start) / RES as f64;
+    let curve = fb.curve_angle(RES);
+    // The singular positions at the bounds might be skipped
+    let (first, last) = (curve[0].0, curve[curve.len() - 1].0);
+    assert!(first >= start && first - start < 2. * step);
+    assert!(last < end && end - last < 3. * step);
+    assert!(curve.windows(2).all(|w| w[0].0 < w[1].0));
+    let xy = curve.iter().map(|(_, p)| *p).collect::<Vec<_>>();
+    assert_eq!(xy, fb.curve(RES));
+}
+
+#[test]
+fn assemble_error_on_curve() {
+    use crate::mech::CurveGen as _;
+    let fb = FourBar::example();
+    for i in 0..8 {
+        let [.., p] = fb.pos(i as f64 / 8. * TAU).unwrap();
+        approx::assert_abs_diff_eq!(fb.assemble_error(p), 0., epsilon = 1e-9);
+    }
+    // The other assembly mode is away from the curve
+    let [.., p] = fb.pos_s(1., !fb.inv()).unwrap();
+    assert!(fb.assemble_error(p) > 1.);
+}
+
+#[test]
+fn display_params() {
+    let fb = FourBar::example();
+    let s = fb.to_string();
+    assert!(s.contains("Ground"));
+    assert!(s.contains(fb.ty().name()));
+    assert!(s.contains("inv: false"));
+}
+
+#[test]
+fn curve_segments_with_gap() {
+    // Double rocker with two disjoint intervals on each circuit
+    let (_, fb) = FourBar::benchmarks().swap_remove(2);
+    let segs = fb.curve_segments(360);
+    assert_eq!(segs.len(), fb.angle_bounds_all().len());
+    assert_eq!(segs.len(), 2);
+    assert!(segs.iter().all(|s| s.len() > 2));
+}
+
+#[test]
+fn velocity_ratio_symmetric() {
+    // The coupler and follower are equal, so the triangle of them is isosceles
+    // when the driver is on the ground line, then `ω4/ω2 = ±l2/|p2 - p3|`
+    let fb = FourBar::example();
+    for inv in [false, true] {
+        let fb = fb
+            .clone()
+            .with_stat(if inv { Stat::C2B1 } else { Stat::C1B1 });
+        let r0 = fb.velocity_ratio(0.).unwrap();
+        approx::assert_abs_diff_eq!(r0, -35. / (90. - 35.), epsilon = 1e-12);
+        let r1 = fb.velocity_ratio(PI).unwrap();
+        approx::assert_abs_diff_eq!(r1, 35. / (90. + 35.), epsilon = 1e-12);
+    }
+}
+
+#[test]
+fn input_torque_virtual_work() {
+    let fb = FourBar::example();
+    let force = [0.3, -1.];
+    for t in [0., 1., 2., 4.] {
+        // Central difference of the coupler point
+        let h = 1e-6;
+        let [.., p] = fb.pos(t + h).unwrap();
+        let [.., q] = fb.pos(t - h).unwrap();
+        let work = -(force[0] * (p[0] - q[0]) + force[1] * (p[1] - q[1])) / (2. * h);
+        let torque = fb.input_torque(t, force).unwrap();
+        assert!(torque.is_finite());
+        assert!((torque - work).abs() < 1e-4 * work.abs().max(1.));
+    }
+    // Spikes near the toggle position of the triple rocker
+    let (_, fb) = &FourBar::benchmarks()[3];
+    let [start, end] = fb.angle_bound().to_value().unwrap();
+    let mag = |t| {
+        let x = fb.input_torque(t, [1., 0.]).unwrap();
+        let y = fb.input_torque(t, [0., 1.]).unwrap();
+        x.hypot(y)
+    };
+    let mid = start + (end - start).rem_euclid(TAU) * 0.5;
+    assert!(mag(start + 1e-6) > 1e2 * mag(mid));
+}
+
+#[test]
+fn coupler_triangle_rigid() {
+    let fb = FourBar::example();
+    let dist = |a: [f64; 2], b: [f64; 2]| (a[0] - b[0]).hypot(a[1] - b[1]);
+    for theta in [0., 1., 2., 4.] {
+        let tri = fb.coupler_triangle(theta).unwrap();
+        let [.., p3, p4, p5] = fb.pos(theta).unwrap();
+        assert_eq!(tri, [p3, p4, p5]);
+        let [l3, l5] = [dist(p3, p4), dist(p3, p5)];
+        approx::assert_abs_diff_eq!(l3, fb.l3, epsilon = 1e-12);
+        approx::assert_abs_diff_eq!(l5, fb.l5, epsilon = 1e-12);
+    }
+    // Double rocker cannot be assembled at some angles
+    let (_, fb) = FourBar::benchmarks().swap_remove(2);
+    assert_eq!(fb.coupler_triangle(3.), Err(crate::Error::Unassemblable));
+}
+
+#[test]
+fn branch_defect_change_point() {
+    // Parallelogram linkage folds at the zero angle and π
+    let norm = NormFourBar { l1: 2., l3: 2., l4: 1., ..Default::default() };
+    assert!(FourBar::new(UnNorm::from_driver(1.), norm).has_branch_defect(90));
+    for (name, fb) in FourBar::benchmarks() {
+        assert!(!fb.has_branch_defect(360), "{name}");
+    }
+}
+
+#[test]
+fn speed_uniformity_circle() {
+    // The coupler point on the driver joint moves in a circle
+    let mut fb = FourBar::example();
+    fb.norm.l5 = 0.;
+    let cv = fb.speed_uniformity(180).unwrap();
+    assert!(cv < 1e-9, "{cv}");
+    assert!(FourBar::example().speed_uniformity(180).unwrap() > cv);
+}
+
+#[test]
+fn closed_open_curve_types() {
+    for (name, fb) in FourBar::benchmarks() {
+        let norm = NormFourBar::normalize(fb);
+        let ty = norm.ty();
+        let open = norm.clone().to_open_curve();
+        assert!(open.ty().is_open_curve(), "{name}");
+        assert!(open.is_open(), "{name}");
+        match norm.to_closed_curve() {
+            Some(closed) => {
+                let expect = if ty.is_closed_curve() {
+                    ty
+                } else {
+                    FourBarTy::GCRR
+                };
+                assert_eq!(closed.ty(), expect, "{name}");
+                assert!(!closed.is_open(), "{name}");
+                assert!(!closed.curve(90).is_empty(), "{name}");
+            }
+            None => assert!(!ty.is_grashof(), "{name}"),
+        }
+    }
+}
+
+#[test]
+fn share_code_round_trip() {
+    for (name, fb) in FourBar::benchmarks() {
+        let code = fb.to_share_code();
+        assert!(code.bytes().all(|c| B64.contains(&c)), "{name}");
+        assert_eq!(FourBar::from_share_code(&code), Ok(fb.clone()), "{name}");
+        assert_eq!(
+            FourBar::from_share_code(&format!("#{code}")),
+            Ok(fb),
+            "{name}"
+        );
+    }
+    // Malformed codes
+    let code = FourBar::example().to_share_code();
+    for bad in ["", "#", "!!!!", "A", &code[..code.len() - 4], &code[1..]] {
+        let err = FourBar::from_share_code(bad);
+        assert_eq!(err, Err(crate::Error::InvalidCode), "{bad}");
+    }
+    // Invalid state and parameters
+    let mut bytes = b64_decode(&code).unwrap();
+    *bytes.last_mut().unwrap() = u8::MAX;
+    let err = FourBar::from_share_code(&b64_encode(&bytes));
+    assert_eq!(err, Err(crate::Error::InvalidStat));
+    let mut bytes = b64_decode(&code).unwrap();
+    bytes[..8].copy_from_slice(&f64::NAN.to_le_bytes());
+    let err = FourBar::from_share_code(&b64_encode(&bytes));
+    assert_eq!(err, Err(crate::Error::InvalidLinkage));
+}
+
+#[test]
+fn reachable_boundary_contains() {
+    use std::iter::zip;
+    for (name, fb) in FourBar::benchmarks() {
+        let boundary = fb.reachable_boundary(180);
+        assert!(boundary.len() >= 3, "{name}");
+        let next = boundary.iter().cycle().skip(1);
+        for inv in [false, true] {
+            let t = (0..180).step_by(5).map(|i| i as f64 / 180. * TAU);
+            for [.., p] in t.filter_map(|t| fb.pos_s(t, inv)) {
+                // Left side of all the edges in counter-clockwise order
+                let inside = zip(&boundary, next.clone()).all(|(a, b)| {
+                    let cross = (b[0] - a[0]) * (p[1] - a[1]) - (b[1] - a[1]) * (p[0] - a[0]);
+                    cross >= -1e-6 * fb.l1 * fb.l1
+                });
+                assert!(inside, "{name}");
+            }
+        }
+    }
+}
+
+#[test]
+fn compare_self() {
+    for (name, fb) in FourBar::benchmarks() {
+        let cmp = fb.compare(&fb, 90);
+        assert_eq!(cmp.diff, [0.; 9], "{name}");
+        assert_eq!(cmp.hausdorff, 0., "{name}");
+        assert!(cmp.same_ty, "{name}");
+    }
+}
+
+#[test]
+fn mechanical_advantage_reciprocal() {
+    let fb = FourBar::example();
+    for theta in [0.3, 1., 2.5, 4., 5.5] {
+        let ma = fb.mechanical_advantage(theta).unwrap();
+        let ratio = fb.velocity_ratio(theta).unwrap();
+        approx::assert_abs_diff_eq!(ma * ratio, 1., epsilon = 1e-9);
+    }
+}
+
+#[test]
+fn coupler_symmetry_chebyshev_lambda() {
+    // The isosceles coupler of the Chebyshev lambda linkage (not a
+    // parallelogram) is symmetric about the perpendicular line of the ground
+    // link, passing through the follower pivot
+    let (_, mut fb) = FourBar::benchmarks().pop().unwrap();
+    for a in [0., 0.3, 1.2] {
+        fb.unnorm.a = a;
+        let axis = fb.coupler_symmetry(360, 1e-2).unwrap();
+        approx::assert_abs_diff_eq!(axis, FRAC_PI_2 + a, epsilon = 1e-2);
+    }
+    let (_, double_crank) = &FourBar::benchmarks()[1];
+    assert_eq!(double_crank.coupler_symmetry(360, 1e-2), None);
+}
+
+#[test]
+fn io_function_crank_rocker() {
+    let fb = FourBar::example();
+    let io = fb.io_function(360);
+    assert_eq!(io.len(), 360);
+    // The follower swings between two extreme positions, so the function is
+    // monotonic in the two segments between them
+    let diff = std::iter::zip(&io, io.iter().cycle().skip(1))
+        .map(|((_, a), (_, b))| b - a)
+        .collect::<Vec<_>>();
+    let turns = std::iter::zip(&diff, diff.iter().cycle().skip(1))
+        .filter(|(a, b)| a.signum() != b.signum())
+        .count();
+    assert_eq!(turns, 2);
+}
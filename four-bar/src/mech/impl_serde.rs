@@ -1,16 +1,58 @@
 // Flatten is unsupported in RON, so we have to manually implement it.
-use super::{FourBar, MFourBar, SFourBar};
+use super::{FourBar, MFourBar, SFourBar, Stat, Version, FORMAT_VERSION};
 use serde::{de::*, ser::*};
 
+// Reject the newer versions
+fn check_version<E: serde::de::Error>(version: u32) -> Result<u32, E> {
+    if version > FORMAT_VERSION {
+        Err(E::custom(format_args!(
+            "unsupported format version {version}, the latest is {FORMAT_VERSION}"
+        )))
+    } else {
+        Ok(version)
+    }
+}
+
+impl Serialize for Version {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_u32(FORMAT_VERSION)
+    }
+}
+
+impl<'de> Deserialize<'de> for Version {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        check_version(u32::deserialize(deserializer)?).map(|_| Self)
+    }
+}
+
+// The state was a flag `inv` before the versioned format
+fn migrate_inv(inv: bool) -> Stat {
+    if inv {
+        Stat::C2B1
+    } else {
+        Stat::C1B1
+    }
+}
+
 macro_rules! impl_serde {
-    ($ty:ident, $($field:ident $(.$unnorm:ident)?),+) => {
+    ($ty:ident, $($field:ident $(.$unnorm:ident)?),+; $stat:ident) => {
         impl Serialize for $ty {
             fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
             where
                 S: Serializer,
             {
-                let mut s = serializer.serialize_struct(stringify!($ty), 10)?;
+                // The version, the fields, and the state
+                let len = [$(stringify!($field)),+].len() + 2;
+                let mut s = serializer.serialize_struct(stringify!($ty), len)?;
+                s.serialize_field("version", &FORMAT_VERSION)?;
                 $(s.serialize_field(stringify!($field), &self.$($unnorm.)?$field)?;)+
+                s.serialize_field(stringify!($stat), &self.$stat)?;
                 s.end()
             }
         }
@@ -20,10 +62,14 @@ macro_rules! impl_serde {
             where
                 D: Deserializer<'de>,
             {
-                const FIELDS: &[&str] = &[$(stringify!($field)),+];
+                const FIELDS: &[&str] =
+                    &["version", $(stringify!($field),)+ stringify!($stat), "inv"];
                 #[allow(non_camel_case_types)]
                 enum Field {
+                    version,
                     $($field,)+
+                    $stat,
+                    inv,
                 }
                 impl<'de> Deserialize<'de> for Field {
                     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
@@ -41,7 +87,10 @@ macro_rules! impl_serde {
                                 E: serde::de::Error,
                             {
                                 match v {
+                                    "version" => Ok(Field::version),
                                     $(stringify!($field) => Ok(Field::$field),)+
+                                    stringify!($stat) => Ok(Field::$stat),
+                                    "inv" => Ok(Field::inv),
                                     _ => Err(serde::de::Error::unknown_field(v, FIELDS)),
                                 }
                             }
@@ -60,14 +109,34 @@ macro_rules! impl_serde {
                         A: MapAccess<'de>,
                     {
                         // Missing field and duplicated field checkers
+                        let version = std::cell::OnceCell::<u32>::new();
+                        let inv = std::cell::OnceCell::<bool>::new();
                         $(let $field = std::cell::OnceCell::new();)+
+                        let $stat = std::cell::OnceCell::new();
                         while let Some(k) = map.next_key()? {
                             match k {
+                                Field::version => version
+                                    .set(map.next_value()?)
+                                    .map_err(|_| serde::de::Error::duplicate_field("version"))?,
                                 $(Field::$field => $field
                                     .set(map.next_value()?)
                                     .map_err(|_| serde::de::Error::duplicate_field(stringify!($field)))?,)+
+                                Field::$stat => $stat
+                                    .set(map.next_value()?)
+                                    .map_err(|_| serde::de::Error::duplicate_field(stringify!($stat)))?,
+                                Field::inv => inv
+                                    .set(map.next_value()?)
+                                    .map_err(|_| serde::de::Error::duplicate_field("inv"))?,
                             }
                         }
+                        // The unversioned files are treated as version 0
+                        let _version = check_version::<A::Error>(version.into_inner().unwrap_or(0))?;
+                        // Migration hooks
+                        if let Some(inv) = inv.into_inner() {
+                            $stat
+                                .set(migrate_inv(inv))
+                                .map_err(|_| serde::de::Error::duplicate_field(stringify!($stat)))?;
+                        }
                         let mut fb = std::mem::MaybeUninit::<$ty>::uninit();
                         let fb_ptr = fb.as_mut_ptr();
                         // Cannot use `&mut` and `=` on an uninitialized field, so we have to use `write`.
@@ -76,6 +145,11 @@ macro_rules! impl_serde {
                             std::ptr::addr_of_mut!((*fb_ptr).$($unnorm.)?$field)
                                 .write($field.into_inner().ok_or_else(|| serde::de::Error::missing_field(stringify!($field)))?);
                         })+
+                        // SAFETY: We only write them for initialization.
+                        unsafe {
+                            std::ptr::addr_of_mut!((*fb_ptr).$stat)
+                                .write($stat.into_inner().ok_or_else(|| serde::de::Error::missing_field(stringify!($stat)))?);
+                        }
                         // SAFETY: We have initialized all fields.
                         Ok(unsafe { fb.assume_init() })
                     }
@@ -86,9 +160,39 @@ macro_rules! impl_serde {
     };
 }
 
-impl_serde!(FourBar, p1x.unnorm, p1y.unnorm, a.unnorm, l1, l2.unnorm, l3, l4, l5, g, stat);
-impl_serde!(MFourBar, p1x.unnorm, p1y.unnorm, a.unnorm, l1, l2.unnorm, l3, l4, l5, g, e, stat);
+impl_serde!(FourBar, p1x.unnorm, p1y.unnorm, a.unnorm, l1, l2.unnorm, l3, l4, l5, g; stat);
+impl_serde!(MFourBar, p1x.unnorm, p1y.unnorm, a.unnorm, l1, l2.unnorm, l3, l4, l5, g, e; stat);
 impl_serde!(
     SFourBar, ox.unnorm, oy.unnorm, oz.unnorm, r.unnorm, p1i.unnorm, p1j.unnorm, a.unnorm, l1, l2,
-    l3, l4, l5, g, stat
+    l3, l4, l5, g; stat
 );
+
+#[cfg(feature = "ron")]
+#[test]
+fn load_versioned_linkage() {
+    fn versioned<T>(t: T)
+    where
+        T: Serialize + DeserializeOwned + PartialEq + std::fmt::Debug,
+    {
+        let s = ron::to_string(&t).unwrap();
+        let version = format!("version:{FORMAT_VERSION}");
+        assert!(s.contains(&version), "{s}");
+        assert_eq!(ron::from_str::<T>(&s).unwrap(), t);
+        // Newer files are rejected
+        let newer = s.replace(&version, &format!("version:{}", FORMAT_VERSION + 1));
+        assert!(ron::from_str::<T>(&newer).is_err());
+    }
+    // The version field of the other types
+    assert!(ron::from_str::<Version>(&FORMAT_VERSION.to_string()).is_ok());
+    assert!(ron::from_str::<Version>(&(FORMAT_VERSION + 1).to_string()).is_err());
+    versioned(FourBar::example());
+    versioned(MFourBar::example());
+    versioned(SFourBar::example());
+    // Legacy files without the version, and with the state flag
+    const LEGACY: &str =
+        "p1x: 0.0, p1y: 0.0, a: 0.0, l1: 90.0, l2: 35.0, l3: 70.0, l4: 70.0, l5: 45.0, g: 0.5";
+    let legacy = ron::from_str::<FourBar>(&format!("({LEGACY}, stat: c1b2)")).unwrap();
+    assert_eq!(legacy.stat(), Stat::C1B2);
+    let legacy = ron::from_str::<FourBar>(&format!("({LEGACY}, inv: true)")).unwrap();
+    assert_eq!(legacy.stat(), Stat::C2B1);
+}
@@ -115,11 +115,22 @@ impl Normalized<2> for MNormFourBar {
 impl MFourBar {
     /// Create a motion four-bar linkage from a four-bar linkage and a motion
     /// line angle.
+    ///
+    /// See also [`FourBar::into_motion()`] for the default angle.
     pub const fn from_fb_angle(fb: FourBar, e: f64) -> Self {
         Self::new(fb.unnorm, MNormFourBar { base: fb.norm, e })
     }
 
     /// Convert to a four-bar linkage by dropping the motion line angle.
+    ///
+    /// The coupler curve is kept, but the orientation of the coupler is lost,
+    /// so the result can only be used in the path synthesis.
+    ///
+    /// ```
+    /// use four_bar::{FourBar, MFourBar};
+    ///
+    /// assert_eq!(MFourBar::example().into_fb(), FourBar::example());
+    /// ```
     pub const fn into_fb(self) -> FourBar {
         FourBar { unnorm: self.unnorm, norm: self.norm.base }
     }
@@ -149,6 +160,78 @@ impl MFourBar {
     pub const fn example() -> Self {
         Self::from_fb_angle(FourBar::example(), FRAC_PI_6)
     }
+
+    /// Elliptical Fourier descriptor of the motion.
+    ///
+    /// The motion is sampled with `n` points in the range of motion. The
+    /// descriptor has two parts: the curve part describes the coupler curve,
+    /// and the pose part describes the unit vectors of the motion line, which
+    /// are attached to each point of the coupler curve. Both parts keep the
+    /// `harmonic` number of harmonics.
+    ///
+    /// ```
+    /// use four_bar::MFourBar;
+    ///
+    /// let efd = MFourBar::example().motion_efd(360, 10);
+    /// assert_eq!(efd.harmonic(), 10);
+    /// ```
+    pub fn motion_efd(&self, n: usize, harmonic: usize) -> efd::PosedEfd<2> {
+        let (curve, vectors) = PoseGen::pose(self, n);
+        efd::PosedEfd::from_uvec_harmonic(curve, vectors, Statable::is_open(self), harmonic)
+    }
+
+    /// Find the input angle that the coupler pose best matches the target.
+    ///
+    /// The target is a position and an orientation angle of the motion line.
+    /// The motion is sampled with `n` points in the range of motion, returns
+    /// the input angle and the combined error. The error is the distance of
+    /// the positions plus the difference of the angles, which is weighted by
+    /// the driver link `l2` to keep the same unit.
+    ///
+    /// Returns `None` if the linkage is invalid.
+    ///
+    /// ```
+    /// use four_bar::MFourBar;
+    ///
+    /// let fb = MFourBar::example();
+    /// let (_, err) = fb.closest_pose(([0., 0.], 0.), 360).unwrap();
+    /// assert!(err >= 0.);
+    /// ```
+    pub fn closest_pose(&self, target: ([f64; 2], f64), n: usize) -> Option<(f64, f64)> {
+        use std::f64::consts::TAU;
+        let ([x, y], angle) = target;
+        let [start, end] = self.angle_bound().to_value()?;
+        let inv = self.inv();
+        super::linspace(start, end, n)
+            .filter_map(|t| {
+                let [.., p3, p4, p5] = self.pos_s(t, inv)?;
+                let [u, v] = self.uvec(&[p3, p4, p5]);
+                let d = (v.atan2(u) - angle).rem_euclid(TAU);
+                let err = (p5[0] - x).hypot(p5[1] - y) + self.unnorm.l2 * d.min(TAU - d);
+                Some((t, err))
+            })
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+    }
+}
+
+impl FourBar {
+    /// Lift to a motion four-bar linkage.
+    ///
+    /// The motion line is along the coupler link (`e = 0`), that is, the pose
+    /// follows the direction from the driver tip to the follower tip. Use
+    /// [`MFourBar::from_fb_angle()`] for the other angles. The coupler curve is
+    /// unchanged, and [`MFourBar::into_fb()`] recovers the linkage.
+    ///
+    /// ```
+    /// use four_bar::FourBar;
+    ///
+    /// let mfb = FourBar::example().into_motion();
+    /// assert_eq!(mfb.e, 0.);
+    /// assert_eq!(mfb.into_fb(), FourBar::example());
+    /// ```
+    pub const fn into_motion(self) -> MFourBar {
+        MFourBar::from_fb_angle(self, 0.)
+    }
 }
 
 impl PlanarLoop for MNormFourBar {
@@ -183,3 +266,32 @@ impl PoseGen<2> for MFourBar {
         [angle.cos(), angle.sin()]
     }
 }
+
+#[test]
+fn motion_efd_curve_part() {
+    let fb = MFourBar::example();
+    let efd = fb.motion_efd(360, 10);
+    let curve = efd::Efd2::from_curve_harmonic(fb.curve(360), fb.is_open(), 10);
+    assert!(efd.as_curve().err(&curve) < 1e-12);
+}
+
+#[test]
+fn closest_pose_on_motion() {
+    let fb = MFourBar::example();
+    let [start, end] = fb.angle_bound().to_value().unwrap();
+    let (curve, vectors) = PoseGen::pose(&fb, 360);
+    let [u, v] = vectors[100];
+    let (t, err) = fb.closest_pose((curve[100], v.atan2(u)), 360).unwrap();
+    let expect = super::linspace(start, end, 360).nth(100).unwrap();
+    assert!(err < 1e-9, "{err}");
+    assert!((t - expect).abs() < 1e-9);
+}
+
+#[test]
+fn lift_and_drop() {
+    for (name, fb) in FourBar::benchmarks() {
+        let mfb = fb.clone().into_motion();
+        assert_eq!(mfb.curve(90), fb.curve(90), "{name}");
+        assert_eq!(mfb.into_fb(), fb, "{name}");
+    }
+}
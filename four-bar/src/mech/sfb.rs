@@ -228,6 +228,41 @@ impl SFourBar {
     pub fn sr(&self) -> f64 {
         self.unnorm.r
     }
+
+    /// Shape signature of the coupler curve.
+    ///
+    /// The spherical version of [`FourBar::shape_signature()`], the normalized
+    /// 3D EFD coefficients of the given `harmonic` are flattened, which is
+    /// invariant to the position and radius of the sphere. Return an empty
+    /// vector if the linkage is invalid.
+    ///
+    /// ```
+    /// use four_bar::SFourBar;
+    ///
+    /// let fb1 = SFourBar::example();
+    /// let mut fb2 = fb1.clone();
+    /// fb2.unnorm.ox = 10.;
+    /// fb2.unnorm.oy = -5.;
+    /// fb2.unnorm.r *= 2.;
+    /// let sig1 = fb1.shape_signature(10);
+    /// let sig2 = fb2.shape_signature(10);
+    /// assert_eq!(sig1.len(), 60);
+    /// assert!(sig1.iter().zip(&sig2).all(|(a, b)| (a - b).abs() < 1e-6));
+    /// ```
+    pub fn shape_signature(&self, harmonic: usize) -> Vec<f64> {
+        const RES: usize = 360;
+        let curve = self.curve::<3>(RES);
+        if curve.len() < 2 {
+            return Vec::new();
+        }
+        let efd = efd::Efd3::from_curve_harmonic(curve, self.is_open(), harmonic);
+        let (coeffs, _) = efd.into_inner();
+        coeffs
+            .into_iter()
+            .flat_map(|m| m.data.0)
+            .flatten()
+            .collect()
+    }
 }
 
 impl Statable for SNormFourBar {
@@ -216,6 +216,29 @@ impl AngleBound {
         }
     }
 
+    /// Clamp the input angle into the bound.
+    ///
+    /// Return the angle itself if it is in the bound, otherwise return the
+    /// nearest boundary value, moved slightly inside to keep the position
+    /// solvable. Return `None` if the bound is invalid.
+    pub fn clamp(&self, t: f64) -> Option<f64> {
+        const EPS: f64 = 1e-9;
+        let [a, b] = match self {
+            Self::Closed => return Some(t),
+            Self::OpenC1B2(a) | Self::OpenC2B2(a) => *a,
+            Self::Invalid => return None,
+        };
+        let b = if b > a { b } else { b + TAU };
+        let t_wrap = a + (t - a).rem_euclid(TAU);
+        if t_wrap <= b {
+            Some(t)
+        } else if t_wrap - b < a + TAU - t_wrap {
+            Some(b - EPS)
+        } else {
+            Some(a + EPS)
+        }
+    }
+
     /// Return true if the bounds is open.
     pub fn is_open(&self) -> bool {
         matches!(self, Self::OpenC1B2(_) | Self::OpenC2B2(_))
@@ -417,3 +440,32 @@ pub trait PlanarLoop {
     /// Set the link lengths as the planar loop.
     fn set_to_planar_loop(&mut self) {}
 }
+
+#[test]
+fn clamp_after_flip() {
+    use crate::{mech::CurveGen as _, FourBar, NormFourBar};
+    // Open curve with 2 circuits 2 branches
+    let norm = NormFourBar {
+        l1: 2.,
+        l3: 1.9,
+        l4: 0.5,
+        l5: 1.,
+        g: 0.,
+        stat: Stat::C1B2,
+    };
+    let mut fb = FourBar::from_norm(norm);
+    let t = 1.2;
+    assert_eq!(fb.angle_bound().clamp(t), Some(t));
+    // Flip the branch, the angle is out of the new bound
+    fb.set_stat(Stat::C1B1);
+    let bound = fb.angle_bound();
+    let t_new = bound.clamp(t).unwrap();
+    assert_ne!(t_new, t);
+    assert_eq!(bound.clamp(t_new), Some(t_new));
+    assert!(fb
+        .pos(t_new)
+        .unwrap()
+        .iter()
+        .flatten()
+        .all(|x| x.is_finite()));
+}
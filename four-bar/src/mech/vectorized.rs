@@ -16,6 +16,22 @@ pub trait FromVectorized<const N: usize>: Sized {
     fn from_vectorized_s1(v: [f64; N]) -> Self {
         Self::from_vectorized(v, mech::Stat::C1B1)
     }
+
+    /// Project the vector onto the linkages that generate mirror symmetric
+    /// curves.
+    ///
+    /// Return `None` if the projection is not supported.
+    fn to_symmetric(_v: [f64; N]) -> Option<[f64; N]> {
+        None
+    }
+
+    /// Reflect the vector to the mirror image of the linkage, which generates
+    /// the mirror image of the curves.
+    ///
+    /// Return `None` if the reflection is not supported.
+    fn to_mirror(_v: [f64; N]) -> Option<[f64; N]> {
+        None
+    }
 }
 
 /// Support a type transforming to a vectored number.
@@ -58,6 +74,24 @@ impl FromVectorized<5> for NormFourBar {
         let [l1, l3, l4, l5, g] = v;
         Self { l1, l3, l4, l5, g, stat: stat as Stat }
     }
+
+    /// The coupler curve is symmetric if `l3 = l4 = |p4 - p5|`, that is,
+    /// `l4 = l3` and `l5 = 2 * l3 * cos(g)`. A negative `l5` is flipped by
+    /// rotating `g` a half turn.
+    fn to_symmetric([l1, l3, _, _, g]: [f64; 5]) -> Option<[f64; 5]> {
+        let l5 = 2. * l3 * g.cos();
+        if l5 < 0. {
+            Some([l1, l3, l3, -l5, (g + PI) % TAU])
+        } else {
+            Some([l1, l3, l3, l5, g])
+        }
+    }
+
+    /// Reflected about the ground link, the angle of the extended link is
+    /// negated. The inversion of the mirror image is covered by the states.
+    fn to_mirror([l1, l3, l4, l5, g]: [f64; 5]) -> Option<[f64; 5]> {
+        Some([l1, l3, l4, l5, (TAU - g) % TAU])
+    }
 }
 
 impl IntoVectorized for NormFourBar {
@@ -64,15 +64,30 @@ pub(crate) fn to_f((x, y): (i32, i32)) -> (f32, f32) {
     (x as f32, y as f32)
 }
 
+// Check the font family can be loaded by the text layout of the backends
+fn font_available(family: &str) -> bool {
+    if cfg!(target_arch = "wasm32") {
+        // The browser handles the fallback fonts
+        return true;
+    }
+    FontDesc::new(FontFamily::Name(family), 12., style::FontStyle::Normal)
+        .box_size("A")
+        .is_ok()
+}
+
 // Rounding float numbers without trailing zeros
 pub(crate) fn formatter(v: &f64) -> String {
-    let mut s = format!("{v:.04}");
-    let sub = s.trim_end_matches('0');
-    s.truncate(sub.strip_suffix('.').unwrap_or(sub).len());
-    if s == "-0" {
-        s.remove(0);
+    TickFormat::Auto.format(*v)
+}
+
+// Remove the trailing zeros of the decimal part
+fn trim_zeros(s: &str) -> &str {
+    if s.contains('.') {
+        let sub = s.trim_end_matches('0');
+        sub.strip_suffix('.').unwrap_or(sub)
+    } else {
+        s
     }
-    s
 }
 
 /// The extreme values of the data.
@@ -206,6 +221,9 @@ impl Style {
         Self::Square,
     ];
 
+    /// Marker style list.
+    pub const MARKER_LIST: [Self; 4] = [Self::Circle, Self::Triangle, Self::Cross, Self::Square];
+
     /// Get the style names.
     pub const fn name(&self) -> &'static str {
         match self {
@@ -228,6 +246,31 @@ impl Style {
         )
     }
 
+    // The marker at `pos` with the pixel `offset`, where the `size` is the
+    // radius of the circle marker. The line styles are drawn as the circle.
+    pub(crate) fn marker<DB, C>(
+        &self,
+        pos: C,
+        offset: (i32, i32),
+        size: u32,
+        style: ShapeStyle,
+    ) -> DynElement<'static, DB, C>
+    where
+        DB: DrawingBackend,
+        C: Clone + 'static,
+    {
+        let at = EmptyElement::at(pos);
+        match self {
+            Self::Triangle => (at + TriangleMarker::new(offset, size, style)).into_dyn(),
+            Self::Cross => (at + Cross::new(offset, size, style)).into_dyn(),
+            Self::Square => {
+                let ((x, y), r) = (offset, size as i32);
+                (at + Rectangle::new([(x - r, y - r), (x + r, y + r)], style)).into_dyn()
+            }
+            _ => (at + Circle::new(offset, size, style)).into_dyn(),
+        }
+    }
+
     pub(crate) fn draw<'a, DB, CT, I>(
         &self,
         chart: &mut ChartContext<'a, DB, CT>,
@@ -403,6 +446,83 @@ impl LegendPos {
     }
 }
 
+/// Number format of the tick labels.
+///
+/// The trailing zeros of the decimal part are removed in all formats.
+///
+/// ```
+/// use four_bar::plot::TickFormat;
+///
+/// assert_eq!(TickFormat::Fixed(2).format(0.125), "0.12");
+/// assert_eq!(TickFormat::Scientific(2).format(1250.), "1.25e3");
+/// assert_eq!(TickFormat::Auto.format(0.5), "0.5");
+/// ```
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub enum TickFormat {
+    /// Fixed decimal places
+    Fixed(usize),
+    /// Scientific notation with the decimal places of the mantissa
+    Scientific(usize),
+    /// Fixed 4 decimal places for the typical ranges, and the scientific
+    /// notation for the values out of `[1e-3, 1e5)`
+    #[default]
+    Auto,
+}
+
+impl TickFormat {
+    /// Get the format names.
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::Fixed(_) => "Fixed",
+            Self::Scientific(_) => "Scientific",
+            Self::Auto => "Auto",
+        }
+    }
+
+    /// Format a number.
+    pub fn format(&self, v: f64) -> String {
+        // Avoid the negative zero
+        let v = if v == 0. { 0. } else { v };
+        match *self {
+            Self::Fixed(n) => match trim_zeros(&format!("{v:.n$}")) {
+                "-0" => "0".to_string(),
+                s => s.to_string(),
+            },
+            Self::Scientific(n) => {
+                let s = format!("{v:.n$e}");
+                match s.split_once('e') {
+                    Some((m, exp)) => format!("{}e{exp}", trim_zeros(m)),
+                    None => s,
+                }
+            }
+            Self::Auto if v != 0. && !(1e-3..1e5).contains(&v.abs()) => {
+                Self::Scientific(4).format(v)
+            }
+            Self::Auto => Self::Fixed(4).format(v),
+        }
+    }
+
+    /// Format a tick number of an axis with the `span` of the axis range.
+    ///
+    /// The values that are negligible to the span are the floating-point
+    /// noises of the tick positions, which are formatted as zero.
+    ///
+    /// ```
+    /// use four_bar::plot::TickFormat;
+    ///
+    /// assert_eq!(TickFormat::Auto.format_tick(2.7756e-17, 2.), "0");
+    /// assert_eq!(
+    ///     TickFormat::Auto.format_tick(2.7756e-17, 1e-16),
+    ///     "2.7756e-17"
+    /// );
+    /// ```
+    pub fn format_tick(&self, v: f64, span: f64) -> String {
+        let v = if v.abs() < span.abs() * 1e-9 { 0. } else { v };
+        self.format(v)
+    }
+}
+
 /// Line type of the [`LineData`].
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[derive(Clone)]
@@ -425,6 +545,13 @@ impl<'a, C: Clone> LineType<'a, C> {
     pub const fn new_empty() -> Self {
         Self::Line(Cow::Borrowed(&[]))
     }
+
+    /// Get the curve points, the pose data is excluded.
+    pub fn curve(&self) -> &[C] {
+        match self {
+            Self::Line(curve) | Self::Pose { curve_p: curve, .. } => curve,
+        }
+    }
 }
 
 impl<'a, const D: usize> LineType<'a, [f64; D]> {
@@ -462,6 +589,10 @@ pub struct LineData<'a, C: Clone> {
     /// Line color
     #[cfg_attr(feature = "serde", serde(with = "ShapeStyleSerde"))]
     pub color: ShapeStyle,
+    /// Show the line or not
+    pub visible: bool,
+    /// Annotations `(index, text)` of the curve points
+    pub annotations: Vec<(usize, String)>,
 }
 
 impl<const D: usize> LineData<'_, [f64; D]> {
@@ -476,7 +607,10 @@ impl<const D: usize> LineData<'_, [f64; D]> {
         CT: CoordTranslate,
         CT::From: From<[f64; D]> + Clone + 'static,
     {
-        let LineData { label, line, style, color } = self;
+        let LineData { label, line, style, color, visible, .. } = self;
+        if !visible {
+            return Ok(());
+        }
         let color = color.stroke_width(stroke);
         match line {
             LineType::Line(line) => {
@@ -505,6 +639,32 @@ impl<const D: usize> LineData<'_, [f64; D]> {
             }
         }
     }
+
+    fn draw_annotations<'a, DB, CT>(
+        &self,
+        chart: &mut ChartContext<'a, DB, CT>,
+        stroke: u32,
+        font: &FontDesc,
+    ) -> PResult<(), DB>
+    where
+        DB: DrawingBackend + 'a,
+        CT: CoordTranslate,
+        CT::From: From<[f64; D]> + Clone + 'static,
+    {
+        if !self.visible || self.annotations.is_empty() {
+            return Ok(());
+        }
+        let curve = self.line.curve();
+        let size = stroke as i32 * 2;
+        let color = self.color;
+        let anno = self.annotations.iter().filter_map(|(i, text)| {
+            let c = CT::From::from(*curve.get(*i)?);
+            let text = Text::new(text.clone(), (size, -size), font.color(&color.color));
+            Some(EmptyElement::at(c) + Circle::new((0, 0), size, color.filled()) + text)
+        });
+        chart.draw_series(anno)?;
+        Ok(())
+    }
 }
 
 #[cfg(feature = "serde")]
@@ -522,6 +682,29 @@ struct ShapeStyleSerde {
     stroke_width: u32,
 }
 
+#[cfg(feature = "serde")]
+mod opt_color_serde {
+    use super::*;
+
+    #[derive(serde::Serialize, serde::Deserialize)]
+    struct Color(#[serde(with = "RGBAColorSerde")] RGBAColor);
+
+    pub(super) fn serialize<S>(color: &Option<RGBAColor>, s: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serde::Serialize::serialize(&color.map(Color), s)
+    }
+
+    pub(super) fn deserialize<'de, D>(d: D) -> Result<Option<RGBAColor>, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let color = <Option<Color> as serde::Deserialize>::deserialize(d)?;
+        Ok(color.map(|Color(c)| c))
+    }
+}
+
 impl<'a, C: Clone> Default for LineData<'a, C> {
     fn default() -> Self {
         Self {
@@ -529,6 +712,8 @@ impl<'a, C: Clone> Default for LineData<'a, C> {
             line: LineType::default(),
             style: Style::default(),
             color: RED.into(),
+            visible: true,
+            annotations: Vec::new(),
         }
     }
 }
@@ -550,6 +735,44 @@ pub trait Plot {
     }
 }
 
+/// Wrap an SVG image into a standalone HTML document with a caption.
+///
+/// The caption is escaped, and the SVG image is embedded inline, so the
+/// document can be shared as a single file.
+pub fn svg_to_html(svg: &str, caption: &str) -> String {
+    let mut cap = String::with_capacity(caption.len());
+    for c in caption.chars() {
+        match c {
+            '&' => cap.push_str("&amp;"),
+            '<' => cap.push_str("&lt;"),
+            '>' => cap.push_str("&gt;"),
+            '"' => cap.push_str("&quot;"),
+            c => cap.push(c),
+        }
+    }
+    format!(
+        "\
+<!DOCTYPE html>
+<html>
+<head>
+<meta charset=\"utf-8\">
+<title>{cap}</title>
+<style>
+body {{ margin: 0; font-family: sans-serif; text-align: center; }}
+svg {{ max-width: 100%; height: auto; }}
+</style>
+</head>
+<body>
+<figure>
+{svg}
+<figcaption>{cap}</figcaption>
+</figure>
+</body>
+</html>
+"
+    )
+}
+
 /// Option type base.
 #[cfg_attr(
     feature = "serde",
@@ -558,12 +781,17 @@ pub trait Plot {
 )]
 #[derive(Clone)]
 pub struct FigureBase<'a, 'b, M: Clone, C: Clone> {
+    /// Format version
+    pub version: crate::mech::Version,
     /// Linkage
     pub fb: Option<Cow<'b, M>>,
     /// Line data
     pub lines: Vec<LineData<'a, C>>,
     /// Drawing options
     pub opt: Opt<'a>,
+    /// Rotation index of the automatic line styles
+    #[cfg_attr(feature = "serde", serde(skip))]
+    auto: usize,
 }
 
 impl<M: Clone, C: Clone> Default for FigureBase<'_, '_, M, C> {
@@ -575,7 +803,13 @@ impl<M: Clone, C: Clone> Default for FigureBase<'_, '_, M, C> {
 impl<'a, 'b, M: Clone, C: Clone> FigureBase<'a, 'b, M, C> {
     /// Create a new figure without linkage.
     pub const fn new() -> Self {
-        Self { fb: None, lines: Vec::new(), opt: Opt::new() }
+        Self {
+            fb: None,
+            lines: Vec::new(),
+            opt: Opt::new(),
+            auto: 0,
+            version: crate::mech::Version,
+        }
     }
 
     /// Create from an optional linkage.
@@ -584,6 +818,8 @@ impl<'a, 'b, M: Clone, C: Clone> FigureBase<'a, 'b, M, C> {
             fb: Some(Cow::Owned(fb)),
             lines: Vec::new(),
             opt: Opt::new(),
+            auto: 0,
+            version: crate::mech::Version,
         }
     }
 
@@ -593,6 +829,8 @@ impl<'a, 'b, M: Clone, C: Clone> FigureBase<'a, 'b, M, C> {
             fb: Some(Cow::Borrowed(fb)),
             lines: Vec::new(),
             opt: Opt::new(),
+            auto: 0,
+            version: crate::mech::Version,
         }
     }
 
@@ -628,6 +866,36 @@ impl<'a, 'b, M: Clone, C: Clone> FigureBase<'a, 'b, M, C> {
         fn axis(bool)
         /// Set legend position.
         fn legend(LegendPos)
+        /// Show the grid on the sphere surface.
+        fn sphere_grid(bool)
+        /// Set the angle interval of the sphere grid.
+        fn sphere_grid_step(f64)
+        /// Highlight the coupler point with a filled marker.
+        fn coupler_marker(bool)
+        /// Set the size of the coupler point marker.
+        fn coupler_marker_size(u32)
+        /// Set the color of the coupler point marker.
+        fn coupler_marker_color(RGBAColor)
+        /// Fill the coupler link as a rigid body (planar plots only).
+        fn coupler_body(bool)
+        /// Shade the reachable region of the coupler point.
+        fn reachable_region(bool)
+        /// Set the marker of the grounded pivots.
+        fn ground_marker(Style)
+        /// Set the marker of the joints.
+        fn joint_marker(Style)
+        /// Shade the swept region of the coupler link, `None` to disable.
+        fn sweep_alpha(Option<f64>)
+        /// Set the input angles of the ghost poses.
+        fn ghost_angles(Vec<f64>)
+        /// Scale the stroke with the canvas size.
+        fn scale_stroke_with_size(bool)
+        /// Set the margin around the data.
+        fn margin(f64)
+        /// Set the background color, `None` for transparent.
+        fn background(Option<RGBAColor>)
+        /// Set the number format of the tick labels.
+        fn tick_format(TickFormat)
     }
 
     /// Set the inner options.
@@ -655,6 +923,18 @@ impl<'a, 'b, M: Clone, C: Clone> FigureBase<'a, 'b, M, C> {
         self
     }
 
+    /// Add a line with the next style and color of a rotating palette.
+    ///
+    /// See also [`Self::push_line_auto()`].
+    pub fn add_line_auto<S, L>(mut self, label: S, line: L) -> Self
+    where
+        S: Into<Cow<'a, str>>,
+        L: Into<Cow<'a, [C]>>,
+    {
+        self.push_line_auto(label, line);
+        self
+    }
+
     /// Add a line from a [`LineData`] instance.
     pub fn add_line_data(mut self, data: LineData<'a, C>) -> Self {
         self.push_line_data(data);
@@ -673,6 +953,8 @@ impl<'a, 'b, M: Clone, C: Clone> FigureBase<'a, 'b, M, C> {
             line: LineType::Line(line.into()),
             style,
             color: color.into(),
+            visible: true,
+            annotations: Vec::new(),
         });
     }
 
@@ -685,11 +967,41 @@ impl<'a, 'b, M: Clone, C: Clone> FigureBase<'a, 'b, M, C> {
         self.push_line(label, line, Style::default(), RED);
     }
 
+    /// Add a line with the next style and color of a rotating palette
+    /// in-placed.
+    ///
+    /// The consecutive lines are visually distinct without specifying their
+    /// styles.
+    pub fn push_line_auto<S, L>(&mut self, label: S, line: L)
+    where
+        S: Into<Cow<'a, str>>,
+        L: Into<Cow<'a, [C]>>,
+    {
+        let i = self.auto;
+        self.auto += 1;
+        let style = Style::LIST[i % Style::LIST.len()];
+        self.push_line(label, line, style, Palette99::pick(i));
+    }
+
     /// Add a line from a [`LineData`] instance in-placed.
     pub fn push_line_data(&mut self, data: LineData<'a, C>) {
         self.lines.push(data);
     }
 
+    /// Annotate the points of the last added line.
+    ///
+    /// Each annotation is an `(index, text)` pair of the curve points.
+    pub fn annotate<S>(mut self, annotations: impl IntoIterator<Item = (usize, S)>) -> Self
+    where
+        S: Into<String>,
+    {
+        if let Some(data) = self.lines.last_mut() {
+            let iter = annotations.into_iter().map(|(i, s)| (i, s.into()));
+            data.annotations.extend(iter);
+        }
+        self
+    }
+
     /// Iterate over lines.
     pub fn lines(&self) -> impl Iterator<Item = &LineData<'a, C>> {
         self.lines.iter()
@@ -715,7 +1027,7 @@ impl<'a, 'b, M: Clone, C: Clone> FigureBase<'a, 'b, M, C> {
     }
 
     pub(crate) fn has_legend(&self) -> bool {
-        self.lines.iter().any(|data| !data.label.is_empty())
+        (self.lines.iter()).any(|data| data.visible && !data.label.is_empty())
     }
 
     #[inline]
@@ -750,6 +1062,15 @@ impl<'a, 'b, M: Clone, C: Clone> FigureBase<'a, 'b, M, C> {
     }
 
     pub(crate) fn get_joints_auto<F, const D: usize>(&self, coord_map: F) -> Option<[[f64; D]; 5]>
+    where
+        M: crate::mech::CurveGen<D>,
+        F: Fn([f64; D]) -> na::Point2<f64>,
+    {
+        self.get_joints(self.get_t_auto(coord_map)?)
+    }
+
+    // The input angle where the links are spread the most
+    pub(crate) fn get_t_auto<F, const D: usize>(&self, coord_map: F) -> Option<f64>
     where
         M: crate::mech::CurveGen<D>,
         F: Fn([f64; D]) -> na::Point2<f64>,
@@ -777,18 +1098,49 @@ impl<'a, 'b, M: Clone, C: Clone> FigureBase<'a, 'b, M, C> {
                 (t, min_angle)
             })
             .max_by(|(_, a1), (_, a2)| a1.partial_cmp(a2).unwrap())?;
-        fb.pos(t)
+        Some(t)
+    }
+
+    pub(crate) fn fill_background<B>(&self, root: &Canvas<B>) -> PResult<(), B>
+    where
+        B: DrawingBackend,
+    {
+        match self.background {
+            Some(color) => root.fill(&color),
+            None => Ok(()),
+        }
+    }
+
+    // (size, style) of the coupler point marker if enabled
+    pub(crate) fn get_marker_style(&self) -> Option<(u32, ShapeStyle)> {
+        let color = self.coupler_marker_color;
+        let style = ShapeStyle { color, filled: true, stroke_width: 0 };
+        self.coupler_marker
+            .then_some((self.coupler_marker_size, style))
+    }
+
+    // Effective stroke on the canvas `(width, height)`
+    pub(crate) fn get_stroke(&self, (w, h): (u32, u32)) -> u32 {
+        if self.scale_stroke_with_size {
+            let scale = w.min(h) as f64 / Opt::REF_SIZE as f64;
+            ((self.stroke as f64 * scale).round() as u32).max(1)
+        } else {
+            self.stroke
+        }
     }
 
     // (stroke, dot_size)
-    pub(crate) fn get_dot_size(&self) -> (u32, u32) {
-        (self.stroke, (self.stroke as f32 * 1.5) as u32)
+    pub(crate) fn get_dot_size(&self, size: (u32, u32)) -> (u32, u32) {
+        let stroke = self.get_stroke(size);
+        (stroke, (stroke as f32 * 1.5) as u32)
     }
 
-    #[inline]
-    fn get_family(&self) -> &str {
-        const DEFAULT_FONT: &str = "Times New Roman";
-        self.font_family.as_deref().unwrap_or(DEFAULT_FONT)
+    fn get_family(&self) -> FontFamily<'_> {
+        if self.is_font_fallback() {
+            FontFamily::SansSerif
+        } else {
+            FontFamily::Name(self.font_family.as_deref().unwrap_or(Opt::DEFAULT_FONT))
+        }
     }
 
     pub(crate) fn get_font(&self) -> FontDesc<'_> {
@@ -835,6 +1187,58 @@ impl<'a, 'b, M: Clone, C: Clone> FigureBase<'a, 'b, M, C> {
         Plot::plot(self, root)
     }
 
+    /// Plot the legend only into an SVG string.
+    ///
+    /// The labels and the styles of the visible lines are drawn at the upper
+    /// left corner without the chart and the linkage, which is useful to
+    /// compose the multi-panel figures.
+    ///
+    /// ```
+    /// use four_bar::{plot::*, FourBar};
+    /// let fb = FourBar::example();
+    /// let svg = fb::Figure::new()
+    ///     .add_line("Coupler Curve", fb.curve(180), Style::Line, BLACK)
+    ///     .plot_legend_only((400, 100));
+    /// assert!(svg.contains("Coupler Curve"));
+    /// ```
+    pub fn plot_legend_only(&self, size: (u32, u32)) -> String {
+        let mut buf = String::new();
+        let root = SVGBackend::with_string(&mut buf, size).into_drawing_area();
+        // Drawing into the string never fails
+        let _ = self.draw_legend_only(&root);
+        drop(root);
+        buf
+    }
+
+    fn draw_legend_only<B>(&self, root: &Canvas<B>) -> PResult<(), B>
+    where
+        B: DrawingBackend,
+    {
+        self.fill_background(root)?;
+        let stroke = self.get_stroke(root.dim_in_pixel());
+        let mut chart = ChartBuilder::on(root).build_cartesian_2d(0f64..1., 0f64..1.)?;
+        for data in self
+            .lines()
+            .filter(|data| data.visible && !data.label.is_empty())
+        {
+            let color = data.color.stroke_width(stroke);
+            let line = std::iter::empty();
+            data.style
+                .draw(&mut chart, line, &color, &data.label, self.font)?;
+        }
+        if self.has_legend() {
+            chart
+                .configure_series_labels()
+                .legend_area_size(self.font)
+                .position(SeriesLabelPosition::UpperLeft)
+                .background_style(WHITE)
+                .border_style(BLACK)
+                .label_font(self.get_font())
+                .draw()?;
+        }
+        root.present()
+    }
+
     /// Plot the 2D curve and linkages dynamically.
     ///
     /// This is the `curr`/`total` frame of the animation.
@@ -852,6 +1256,31 @@ impl<'a, 'b, M: Clone, C: Clone> FigureBase<'a, 'b, M, C> {
     {
         Plot::plot_by(self, &Canvas::from(root), Some(self.get_t(curr, total)))
     }
+
+    /// Render the figure into a standalone HTML document.
+    ///
+    /// The SVG image is embedded, and the labels of the visible lines are used
+    /// as the caption. See also [`svg_to_html()`].
+    ///
+    /// ```
+    /// use four_bar::plot::*;
+    ///
+    /// let fig = fb::Figure::new().add_line("Line", vec![[0.; 2], [1.; 2]], Style::Line, BLACK);
+    /// assert!(fig.to_html().unwrap().contains("<svg"));
+    /// ```
+    pub fn to_html(&self) -> Result<String, DrawingAreaErrorKind<std::io::Error>>
+    where
+        Self: Plot,
+    {
+        let mut buf = String::new();
+        Plot::plot(self, SVGBackend::with_string(&mut buf, (1600, 1600)))?;
+        let caption = (self.lines.iter())
+            .filter(|data| data.visible && !data.label.is_empty())
+            .map(|data| &*data.label)
+            .collect::<Vec<_>>()
+            .join(", ");
+        Ok(svg_to_html(&buf, &caption))
+    }
 }
 
 impl<'a, M: Clone, const D: usize> FigureBase<'a, '_, M, [f64; D]> {
@@ -940,7 +1369,16 @@ impl<'a, M: Clone, const D: usize> FigureBase<'a, '_, M, [f64; D]> {
             curve_q: curve_q.into(),
             is_frame,
         };
-        self.push_line_data(LineData { label, line, style, color: color.into() });
+        let color = color.into();
+        let annotations = Vec::new();
+        self.push_line_data(LineData {
+            label,
+            line,
+            style,
+            color,
+            visible: true,
+            annotations,
+        });
     }
 
     /// Add two lines as a motion with default settings.
@@ -978,7 +1416,12 @@ pub struct Opt<'a> {
     pub stroke: u32,
     /// Font size
     pub font: f64,
-    /// Font family
+    /// Font family, default to [`Opt::DEFAULT_FONT`]
+    ///
+    /// Only the names of the installed families are supported, a path of the
+    /// font file is not loaded. Fall back to the sans-serif family of the
+    /// system if the family is not available (see [`Opt::is_font_fallback()`]),
+    /// which is usually able to render the CJK characters.
     pub font_family: Option<Cow<'a, str>>,
     /// Show grid
     pub grid: bool,
@@ -986,9 +1429,66 @@ pub struct Opt<'a> {
     pub axis: bool,
     /// Legend position
     pub legend: LegendPos,
+    /// Show the latitude/longitude grid on the sphere surface (spherical
+    /// plots only)
+    pub sphere_grid: bool,
+    /// Angle interval of the sphere grid in radians
+    pub sphere_grid_step: f64,
+    /// Highlight the coupler point of the linkage with a filled marker
+    pub coupler_marker: bool,
+    /// Size of the coupler point marker
+    pub coupler_marker_size: u32,
+    /// Color of the coupler point marker
+    #[cfg_attr(feature = "serde", serde(with = "RGBAColorSerde"))]
+    pub coupler_marker_color: RGBAColor,
+    /// Fill the coupler link as a rigid body (planar plots only)
+    pub coupler_body: bool,
+    /// Shade the reachable region of the coupler point over the both branches
+    /// (planar plots only)
+    pub reachable_region: bool,
+    /// Marker of the grounded pivots, the line styles are drawn as the circle
+    pub ground_marker: Style,
+    /// Marker of the joints, the line styles are drawn as the circle
+    pub joint_marker: Style,
+    /// Shade the region swept by the coupler link with the opacity of each
+    /// position, disabled if `None` (planar plots only)
+    ///
+    /// The coupler triangles are drawn at [`Opt::SWEEP_NUM`] angles over the
+    /// range of motion.
+    pub sweep_alpha: Option<f64>,
+    /// Input angles of the ghost poses, the linkage is drawn faintly at each
+    /// angle behind the current pose (planar plots only)
+    ///
+    /// The angles that cannot be assembled are skipped.
+    pub ghost_angles: Vec<f64>,
+    /// Scale the stroke with the canvas size
+    ///
+    /// The stroke is in pixels for the canvas of [`Opt::REF_SIZE`], and is
+    /// multiplied by the ratio of the shorter side of the canvas to it.
+    pub scale_stroke_with_size: bool,
+    /// Margin around the plotted data, a fraction of the data range
+    pub margin: f64,
+    /// Background color, transparent if `None`
+    #[cfg_attr(feature = "serde", serde(with = "opt_color_serde"))]
+    pub background: Option<RGBAColor>,
+    /// Number format of the tick labels
+    pub tick_format: TickFormat,
 }
 
 impl Opt<'_> {
+    /// Reference canvas size of the stroke scaling.
+    ///
+    /// See [`Opt::scale_stroke_with_size`].
+    pub const REF_SIZE: u32 = 1600;
+    /// Number of the coupler positions of the swept region.
+    ///
+    /// See [`Opt::sweep_alpha`].
+    pub const SWEEP_NUM: usize = 36;
+    /// Default font family.
+    ///
+    /// See [`Opt::font_family`].
+    pub const DEFAULT_FONT: &'static str = "Times New Roman";
+
     /// Create a new instance.
     pub const fn new() -> Self {
         Self {
@@ -998,8 +1498,29 @@ impl Opt<'_> {
             grid: false,
             axis: true,
             legend: LegendPos::UR,
+            sphere_grid: false,
+            sphere_grid_step: std::f64::consts::FRAC_PI_6,
+            coupler_marker: false,
+            coupler_marker_size: 20,
+            coupler_marker_color: RGBAColor(255, 0, 0, 1.),
+            coupler_body: false,
+            reachable_region: false,
+            ground_marker: Style::Triangle,
+            joint_marker: Style::Circle,
+            sweep_alpha: None,
+            ghost_angles: Vec::new(),
+            scale_stroke_with_size: false,
+            margin: 0.2,
+            background: Some(RGBAColor(255, 255, 255, 1.)),
+            tick_format: TickFormat::Auto,
         }
     }
+
+    /// Return true if the font family is not available, and the sans-serif
+    /// family of the system is used instead.
+    pub fn is_font_fallback(&self) -> bool {
+        !font_available(self.font_family.as_deref().unwrap_or(Self::DEFAULT_FONT))
+    }
 }
 
 impl Default for Opt<'_> {
@@ -1007,3 +1528,93 @@ impl Default for Opt<'_> {
         Self::new()
     }
 }
+
+#[test]
+fn auto_line_styles() {
+    let line = vec![[0.; 2], [1.; 2]];
+    let fig = fb::Figure::new()
+        .add_line_auto("a", line.clone())
+        .add_line_auto("b", line.clone())
+        .add_line_auto("c", line);
+    let styles = fig.lines().map(|data| data.style).collect::<Vec<_>>();
+    assert_eq!(styles.len(), 3);
+    assert!(styles[0] != styles[1] && styles[1] != styles[2] && styles[0] != styles[2]);
+}
+
+#[test]
+fn legend_only_labels() {
+    let fig = fb::Figure::new()
+        .add_line("Target", vec![[0., 0.], [1., 1.]], Style::Circle, RED)
+        .add_line(
+            "Optimized",
+            vec![[0., 1.], [1., 0.]],
+            Style::DashedLine,
+            BLUE,
+        );
+    let svg = fig.plot_legend_only((400, 200));
+    assert!(svg.contains("Target") && svg.contains("Optimized"));
+}
+
+#[test]
+fn stroke_scaled_with_size() {
+    let fig = fb::Figure::new().stroke(8);
+    assert_eq!(fig.get_stroke((800, 800)), 8);
+    let fig = fig.scale_stroke_with_size(true);
+    assert_eq!(fig.get_stroke((800, 600)), 3);
+    assert_eq!(fig.get_stroke((1600, 1600)), 8);
+    assert_eq!(fig.get_stroke((3200, 3200)), 16);
+    assert_eq!(fig.get_dot_size((3200, 3200)), (16, 24));
+    // At least one pixel
+    assert_eq!(fig.get_stroke((10, 10)), 1);
+}
+
+#[test]
+fn tick_format_magnitudes() {
+    let fmt = |f: TickFormat| [1e-5, 1e5, -0.].map(|v| f.format(v));
+    assert_eq!(fmt(TickFormat::Fixed(4)), ["0", "100000", "0"]);
+    assert_eq!(fmt(TickFormat::Fixed(5)), ["0.00001", "100000", "0"]);
+    assert_eq!(fmt(TickFormat::Scientific(2)), ["1e-5", "1e5", "0e0"]);
+    assert_eq!(fmt(TickFormat::Auto), ["1e-5", "1e5", "0"]);
+    assert_eq!(TickFormat::Auto.format(12.34567), "12.3457");
+    // The noise of the tick at zero
+    assert_eq!(TickFormat::Auto.format(0.1 + 0.2 - 0.3), "5.5511e-17");
+    assert_eq!(TickFormat::Auto.format_tick(0.1 + 0.2 - 0.3, 0.6), "0");
+    assert_eq!(TickFormat::Auto.format_tick(1e-5, 1e-4), "1e-5");
+}
+
+#[test]
+fn missing_font_family() {
+    let fig = fb::Figure::new()
+        .font_family("No Such Font Family")
+        .add_line("曲線", vec![[0., 0.], [1., 1.]], Style::Line, BLACK);
+    assert!(cfg!(target_arch = "wasm32") || fig.is_font_fallback());
+    assert!(fig.to_html().is_ok());
+}
+
+#[test]
+fn annotated_points() {
+    let fig = fb::Figure::new()
+        .add_line(
+            "Target",
+            vec![[0., 0.], [1., 1.], [2., 0.]],
+            Style::Circle,
+            RED,
+        )
+        .annotate([(0, "P1"), (2, "P3"), (9, "Out")]);
+    let svg = fig.to_html().unwrap();
+    assert!(svg.contains("P1") && svg.contains("P3") && !svg.contains("Out"));
+}
+
+#[cfg(feature = "ron")]
+#[test]
+fn versioned_figure() {
+    let fig = fb::Figure::new_fb(crate::FourBar::example());
+    let s = ron::to_string(&fig).unwrap();
+    let version = format!("version:{}", crate::mech::FORMAT_VERSION);
+    assert!(s.starts_with(&format!("({version},")), "{s}");
+    assert!(ron::from_str::<fb::Figure>(&s).is_ok());
+    // Legacy files without the version
+    assert!(ron::from_str::<fb::Figure>(&s.replace(&format!("{version},"), "")).is_ok());
+    let newer = format!("version:{}", crate::mech::FORMAT_VERSION + 1);
+    assert!(ron::from_str::<fb::Figure>(&s.replace(&version, &newer)).is_err());
+}
@@ -14,27 +14,61 @@ pub use super::*;
 pub type Figure<'a, 'b> = FigureBase<'a, 'b, FourBar, [f64; 2]>;
 
 /// Plot the synthesis history.
-pub fn history<B, R, H>(root: R, history: H) -> PResult<(), B>
+///
+/// Draw a small circle marker on the line at every `stride` generations, zero
+/// to disable the markers.
+pub fn history<B, R, H>(root: R, history: H, stride: usize) -> PResult<(), B>
+where
+    B: DrawingBackend,
+    Canvas<B>: From<R>,
+    H: AsRef<[f64]>,
+{
+    history_impl(root, history.as_ref(), &[], &[], stride)
+}
+
+/// Plot the synthesis history and the cumulative best fitness
+/// (best-so-far).
+///
+/// The history may be the best fitness of each generation, which is not
+/// monotonic, and the cumulative best shows the true convergence. See
+/// [`history()`] for the markers.
+pub fn history_best<B, R, H, C>(root: R, history: H, best: C, stride: usize) -> PResult<(), B>
 where
     B: DrawingBackend,
     Canvas<B>: From<R>,
     H: AsRef<[f64]>,
+    C: AsRef<[f64]>,
 {
-    history_pareto(root, history, [])
+    history_impl(root, history.as_ref(), best.as_ref(), &[], stride)
 }
 
 /// Plot the synthesis history and the size of the Pareto front.
-pub fn history_pareto<B, R, H, P>(root: R, history: H, pareto: P) -> PResult<(), B>
+///
+/// See [`history()`] for the markers.
+pub fn history_pareto<B, R, H, P>(root: R, history: H, pareto: P, stride: usize) -> PResult<(), B>
 where
     B: DrawingBackend,
     Canvas<B>: From<R>,
     H: AsRef<[f64]>,
     P: AsRef<[usize]>,
 {
+    history_impl(root, history.as_ref(), &[], pareto.as_ref(), stride)
+}
+
+fn history_impl<B, R>(
+    root: R,
+    history: &[f64],
+    best: &[f64],
+    pareto: &[usize],
+    stride: usize,
+) -> PResult<(), B>
+where
+    B: DrawingBackend,
+    Canvas<B>: From<R>,
+{
+    use full_palette::GREEN_900;
     const FONT_SIZE: i32 = 24;
     let font = ("Times New Roman", FONT_SIZE).into_font().color(&BLACK);
-    let history = history.as_ref();
-    let pareto = pareto.as_ref();
     let root = Canvas::from(root);
     root.fill(&WHITE)?;
     let max_fitness = history
@@ -68,10 +102,33 @@ where
                 .legend(|c| {
                     EmptyElement::at(c) + PathElement::new([(1, 0), (FONT_SIZE - 1, 0)], BLUE)
                 });
+            if stride > 0 {
+                let markers = history.iter().copied().enumerate().step_by(stride);
+                $chart.draw_series(markers.map(|p| Circle::new(p, 3, BLUE.filled())))?;
+            }
+            if !best.is_empty() {
+                $chart
+                    .draw_series(LineSeries::new(best.iter().copied().enumerate(), GREEN_900))?
+                    .label("Best So Far")
+                    .legend(|c| {
+                        let line = [(1, 0), (FONT_SIZE - 1, 0)];
+                        EmptyElement::at(c) + PathElement::new(line, GREEN_900)
+                    });
+            }
         };
     }
     if pareto.is_empty() {
         impl_history!(chart);
+        if !best.is_empty() {
+            chart
+                .configure_series_labels()
+                .legend_area_size(FONT_SIZE)
+                .position(SeriesLabelPosition::UpperRight)
+                .background_style(WHITE)
+                .border_style(BLACK)
+                .label_font(font)
+                .draw()?;
+        }
     } else {
         let max_pareto = pareto.iter().max().unwrap();
         let mut chart = chart.set_secondary_coord(0..history.len() - 1, 0..max_pareto + 1);
@@ -154,12 +211,19 @@ impl Plot for Figure<'_, '_> {
         B: DrawingBackend,
     {
         self.check_empty::<B>()?;
-        root.fill(&WHITE)?;
-        let (stroke, dot_size) = self.get_dot_size();
-        let joints = t
-            .and_then(|t| self.get_joints(t))
-            .or_else(|| self.get_joints_auto(Into::into));
-        let Opt { grid, axis, legend, .. } = self.opt;
+        self.fill_background(root)?;
+        let (stroke, dot_size) = self.get_dot_size(root.dim_in_pixel());
+        let t_joints = (t.filter(|&t| self.get_joints::<2>(t).is_some()))
+            .or_else(|| self.get_t_auto(Into::into));
+        let joints = t_joints.and_then(|t| self.get_joints(t));
+        let Opt { grid, axis, legend, sweep_alpha, .. } = self.opt;
+        let sweep = sweep_alpha.map(|_| self.sweep_bodies()).unwrap_or_default();
+        let region = (self.as_fb().filter(|_| self.reachable_region))
+            .map(|fb| fb.reachable_boundary(360))
+            .unwrap_or_default();
+        let ghosts = (self.ghost_angles.iter())
+            .filter_map(|&t| self.get_joints::<2>(t))
+            .collect::<Vec<_>>();
         let [x_spec, y_spec] = {
             use mech::CurveGen as _;
             let joints = joints.into_iter().flatten().collect();
@@ -169,9 +233,14 @@ impl Plot for Figure<'_, '_> {
                 .flat_map(|fb| fb.curves(8))
                 .flatten()
                 .collect();
+            let sweep = sweep.iter().flatten().collect();
+            let region = region.iter().collect();
+            let ghosts = ghosts.iter().flatten().collect();
             let iter = self.lines().map(|data| data.line.boundary());
-            area2d(iter.chain([joints, possible_p]), root.dim_in_pixel())
+            let pts = iter.chain([joints, possible_p, sweep, region, ghosts]);
+            area2d_margin(pts, root.dim_in_pixel(), self.margin)
         };
+        let [x_span, y_span] = [x_spec.end - x_spec.start, y_spec.end - y_spec.start];
         let mut chart = ChartBuilder::on(root)
             .set_label_area_size(LabelAreaPosition::Left, (8).percent())
             .set_label_area_size(LabelAreaPosition::Bottom, (4).percent())
@@ -185,32 +254,74 @@ impl Plot for Figure<'_, '_> {
         if !axis {
             mesh.disable_axes();
         }
+        let x_fmt = |v: &f64| self.tick_format.format_tick(*v, x_span);
+        let y_fmt = |v: &f64| self.tick_format.format_tick(*v, y_span);
         mesh.label_style(self.get_font())
-            .x_label_formatter(&formatter)
-            .y_label_formatter(&formatter)
+            .x_label_formatter(&x_fmt)
+            .y_label_formatter(&y_fmt)
             .draw()?;
+        // Draw reachable region
+        if !region.is_empty() {
+            let region = region.iter().map(|&[x, y]| (x, y)).collect::<Vec<_>>();
+            chart.draw_series([Polygon::new(region, BLUE.mix(0.1).filled())])?;
+        }
         // Draw curve
         for data in self.lines() {
             data.draw(&mut chart, stroke, self.font)?;
         }
+        let font = self.get_font();
+        for data in self.lines() {
+            data.draw_annotations(&mut chart, stroke, &font)?;
+        }
+        // Draw swept region of the coupler
+        if let Some(alpha) = sweep_alpha {
+            let style = BLACK.mix(alpha).filled();
+            let bodies = sweep
+                .iter()
+                .map(|body| Polygon::new(body.map(|[x, y]| (x, y)), style));
+            chart.draw_series(bodies)?;
+        }
+        // Draw coupler body
+        let body = (t_joints.filter(|_| self.coupler_body))
+            .and_then(|t| self.as_fb()?.coupler_triangle(t).ok());
+        if let Some(body) = body {
+            let body = body.map(|[x, y]| (x, y));
+            chart.draw_series([Polygon::new(body, BLACK.mix(0.2).filled())])?;
+        }
+        // Draw ghost poses
+        let ghost_style = BLACK.mix(0.25);
+        for joints in &ghosts {
+            for line in link_paths(joints) {
+                chart.draw_series(LineSeries::new(line, ghost_style.stroke_width(stroke)))?;
+            }
+            let markers = (joints.iter()).map(|&[x, y]| {
+                (self.joint_marker).marker((x, y), (0, 0), dot_size, ghost_style.filled())
+            });
+            chart.draw_series(markers)?;
+        }
         // Draw Linkage
-        if let Some(joints @ [p1, p2, p3, p4, p5]) = joints {
-            for line in [[p1, p3].as_slice(), &[p3, p5, p4, p3], &[p2, p4]] {
-                let line = line.iter().map(|&[x, y]| (x, y));
+        if let Some(joints) = joints {
+            for line in link_paths(&joints) {
                 chart.draw_series(LineSeries::new(line, BLACK.stroke_width(stroke)))?;
             }
             let grounded = joints[..2].iter().map(|&[x, y]| {
-                EmptyElement::at((x, y))
-                    + TriangleMarker::new((0, 10), dot_size + 3, BLACK.filled())
+                (self.ground_marker).marker((x, y), (0, 10), dot_size + 3, BLACK.filled())
             });
             chart.draw_series(grounded)?;
-            let joints = joints.iter().enumerate().map(|(n, &[x, y])| {
+            let markers = (joints.iter()).map(|&[x, y]| {
+                (self.joint_marker).marker((x, y), (0, 0), dot_size, BLACK.filled())
+            });
+            chart.draw_series(markers)?;
+            let labels = joints.iter().enumerate().map(|(n, &[x, y])| {
                 let t_style = self.get_big_font().color(&BLUE);
                 EmptyElement::at((x, y))
-                    + Circle::new((0, 0), dot_size, BLACK.filled())
                     + Text::new(format!("p{}", Subscript(n + 1)), (5, 5), t_style)
             });
-            chart.draw_series(joints)?;
+            chart.draw_series(labels)?;
+        }
+        // Draw coupler point marker
+        if let (Some([.., [x, y]]), Some((size, style))) = (joints, self.get_marker_style()) {
+            chart.draw_series([Circle::new((x, y), size, style)])?;
         }
         // Draw legend
         if let Some(legend) = legend.to_plotter_pos().filter(|_| self.has_legend()) {
@@ -227,12 +338,143 @@ impl Plot for Figure<'_, '_> {
     }
 }
 
+// The polylines of the driver, the coupler and the follower
+fn link_paths(&[p1, p2, p3, p4, p5]: &[[f64; 2]; 5]) -> [Vec<(f64, f64)>; 3] {
+    let path = |line: &[[f64; 2]]| line.iter().map(|&[x, y]| (x, y)).collect();
+    [path(&[p1, p3]), path(&[p3, p5, p4, p3]), path(&[p2, p4])]
+}
+
+impl Figure<'_, '_> {
+    // The coupler triangles of the swept region
+    fn sweep_bodies(&self) -> Vec<[[f64; 2]; 3]> {
+        let Some(fb) = self.as_fb() else {
+            return Vec::new();
+        };
+        (0..Opt::SWEEP_NUM)
+            .filter_map(|i| fb.coupler_triangle(self.get_t::<2>(i, Opt::SWEEP_NUM)).ok())
+            .collect()
+    }
+
+    /// Render the figure into an animated SVG image.
+    ///
+    /// Unlike [`FigureBase::plot_video()`], the linkage motion is encoded as
+    /// `frames` keyframes of the SMIL `<animate>` elements instead of the
+    /// rasterized images, so the image is resolution-independent. The visible
+    /// lines are drawn as plain polylines without the axis and legend.
+    ///
+    /// ```
+    /// use four_bar::{plot::fb, FourBar};
+    ///
+    /// let fb = FourBar::example();
+    /// let svg = fb::Figure::new_ref(&fb)
+    ///     .add_line_default("Coupler Curve", fb.curve(90))
+    ///     .plot_animated_svg(60);
+    /// assert!(svg.contains("<animate"));
+    /// ```
+    pub fn plot_animated_svg(&self, frames: usize) -> String {
+        use std::fmt::Write as _;
+        // Duration of a loop in seconds
+        const DUR: f64 = 4.;
+        const SIZE: u32 = Opt::REF_SIZE;
+        let joints = (0..frames)
+            .filter_map(|i| self.get_joints::<2>(self.get_t(i, frames)))
+            .collect::<Vec<_>>();
+        let [x, y] = {
+            let iter = self.lines().map(|data| data.line.boundary());
+            let joints = joints.iter().flatten().collect();
+            area2d_margin(iter.chain([joints]), (SIZE, SIZE), self.margin)
+        };
+        let [w, h] = [x.end - x.start, y.end - y.start];
+        // The stroke is scaled from the pixel size to the data unit
+        let stroke = self.stroke as f64 * w / SIZE as f64;
+        let rgb = |RGBAColor(r, g, b, _): RGBAColor| format!("rgb({r},{g},{b})");
+        // The y-axis is flipped in the SVG coordinates
+        let mut svg = format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="{} {} {w} {h}" width="{SIZE}" height="{}">"#,
+            x.start,
+            -y.end,
+            (SIZE as f64 * h / w).round(),
+        );
+        if let Some(bg) = self.background {
+            let _ = write!(
+                svg,
+                r#"<rect x="{}" y="{}" width="{w}" height="{h}" fill="{}" fill-opacity="{}"/>"#,
+                x.start,
+                -y.end,
+                rgb(bg),
+                bg.3
+            );
+        }
+        for data in self.lines().filter(|data| data.visible) {
+            let (LineType::Line(line) | LineType::Pose { curve_p: line, .. }) = &data.line;
+            let pts = line.iter().map(|[x, y]| format!("{x},{}", -y));
+            let _ = write!(
+                svg,
+                r#"<polyline points="{}" fill="none" stroke="{}" stroke-opacity="{}" stroke-width="{stroke}"/>"#,
+                pts.collect::<Vec<_>>().join(" "),
+                rgb(data.color.color),
+                data.color.color.3,
+            );
+        }
+        // Keyframes of the coordinate `k` of the joint `i`
+        let animate = |attr: &str, i: usize, k: usize| {
+            let sign = if k == 0 { 1. } else { -1. };
+            let values = joints.iter().map(|p| (sign * p[i][k]).to_string());
+            format!(
+                r#"<animate attributeName="{attr}" values="{}" dur="{DUR}s" repeatCount="indefinite"/>"#,
+                values.collect::<Vec<_>>().join(";")
+            )
+        };
+        if let Some(first) = joints.first() {
+            for [i, j] in [[0, 2], [2, 4], [4, 3], [3, 2], [1, 3]] {
+                let [[x1, y1], [x2, y2]] = [first[i], first[j]];
+                let _ = write!(
+                    svg,
+                    r#"<line x1="{x1}" y1="{}" x2="{x2}" y2="{}" stroke="black" stroke-width="{stroke}">{}{}{}{}</line>"#,
+                    -y1,
+                    -y2,
+                    animate("x1", i, 0),
+                    animate("y1", i, 1),
+                    animate("x2", j, 0),
+                    animate("y2", j, 1),
+                );
+            }
+            for (i, [x, y]) in first.iter().enumerate() {
+                let _ = write!(
+                    svg,
+                    r#"<circle cx="{x}" cy="{}" r="{}" fill="black">{}{}</circle>"#,
+                    -y,
+                    stroke * 1.5,
+                    animate("cx", i, 0),
+                    animate("cy", i, 1),
+                );
+            }
+        }
+        svg += "</svg>";
+        svg
+    }
+}
+
 /// Get the 1:1 bounding box of the 2D coordinates.
+///
+/// The default margin of [`Opt::margin`] is used, see [`area2d_margin()`].
 pub fn area2d<I>(pts: I, area: (u32, u32)) -> [std::ops::Range<f64>; 2]
 where
     I: IntoIterator,
     ExtBound<2>: FromIterator<I::Item>,
 {
+    area2d_margin(pts, area, Opt::new().margin)
+}
+
+/// Get the 1:1 bounding box of the 2D coordinates with a margin.
+///
+/// The margin is a fraction of the data range, added to both sides.
+pub fn area2d_margin<I>(pts: I, area: (u32, u32), margin: f64) -> [std::ops::Range<f64>; 2]
+where
+    I: IntoIterator,
+    ExtBound<2>: FromIterator<I::Item>,
+{
+    let scale = 0.5 * (1. + margin);
     let [w, h] = [area.0 as f64, area.1 as f64];
     let [[x_min, x_max], [y_min, y_max]] = ExtBound::from_iter(pts).map_to(|min, max| [min, max]);
     let dx = (x_max - x_min).abs();
@@ -241,14 +483,84 @@ where
     let y_cen = (y_min + y_max) * 0.5;
     match (dx > dy, w > h, dx / dy < w / h) {
         (true, true, false) | (false, false, false) | (true, false, _) => {
-            let x_r = dx * 0.5 * 1.2;
-            let y_r = dx / w * h * 0.5 * 1.2;
+            let x_r = dx * scale;
+            let y_r = dx / w * h * scale;
             [x_cen - x_r..x_cen + x_r, y_cen - y_r..y_cen + y_r]
         }
         (true, true, true) | (false, false, true) | (false, true, _) => {
-            let y_r = dy * 0.5 * 1.2;
-            let x_r = dy / h * w * 0.5 * 1.2;
+            let y_r = dy * scale;
+            let x_r = dy / h * w * scale;
             [x_cen - x_r..x_cen + x_r, y_cen - y_r..y_cen + y_r]
         }
     }
 }
+
+#[test]
+fn area_margin_symmetric() {
+    let pts = [[0., 0.], [2., 1.], [1., 3.]];
+    let center = |r: &std::ops::Range<f64>| (r.start + r.end) * 0.5;
+    let [x1, y1] = area2d_margin(pts, (800, 800), 0.2);
+    let [x2, y2] = area2d_margin(pts, (800, 800), 0.5);
+    for (r1, r2) in [(&x1, &x2), (&y1, &y2)] {
+        approx::assert_abs_diff_eq!(center(r1), center(r2), epsilon = 1e-12);
+        let ratio = (r2.end - r2.start) / (r1.end - r1.start);
+        approx::assert_abs_diff_eq!(ratio, 1.5 / 1.2, epsilon = 1e-12);
+    }
+    assert_eq!(area2d(pts, (800, 800)), [x1, y1]);
+}
+
+#[test]
+fn animated_svg_keyframes() {
+    let fb = FourBar::example();
+    let svg = Figure::new_ref(&fb).plot_animated_svg(30);
+    assert!(svg.starts_with("<svg") && svg.ends_with("</svg>"));
+    // 5 links with 4 coordinates and 5 joints with 2 coordinates
+    assert_eq!(svg.matches("<animate").count(), 30);
+    let values = svg.split("values=\"").nth(1).unwrap();
+    let values = values.split('"').next().unwrap();
+    assert_eq!(values.split(';').count(), 30);
+    // Without linkage
+    let svg = Figure::new()
+        .add_line_default("Curve", fb.curve(30))
+        .plot_animated_svg(30);
+    assert!(svg.contains("<polyline") && !svg.contains("<animate"));
+}
+
+#[test]
+fn sweep_bodies_over_motion() {
+    let fb = FourBar::example();
+    let fig = Figure::new_ref(&fb);
+    let bodies = fig.sweep_bodies();
+    assert_eq!(bodies.len(), Opt::SWEEP_NUM);
+    // The coupler points of the swept bodies are on the coupler curve
+    for (i, [.., p5]) in bodies.into_iter().enumerate() {
+        let t = fig.get_t::<2>(i, Opt::SWEEP_NUM);
+        assert_eq!(fig.get_joints::<2>(t).unwrap()[4], p5);
+    }
+    assert!(Figure::new().sweep_bodies().is_empty());
+}
+
+#[test]
+fn ghost_poses_drawn() {
+    let fb = FourBar::example();
+    let count = |fig: &Figure| fig.to_html().unwrap().matches("<polyline").count();
+    let base = count(&Figure::new_ref(&fb));
+    let fig = Figure::new_ref(&fb).ghost_angles(vec![0.5, 1.5, 2.5]);
+    // Three links of each pose
+    assert_eq!(count(&fig), base + 3 * 3);
+}
+
+#[test]
+fn history_markers_stride() {
+    let history = (0..10).map(|i| 1. / (i + 1) as f64).collect::<Vec<_>>();
+    for (stride, n) in [(0, 0), (1, 10), (3, 4), (20, 1)] {
+        let mut buf = String::new();
+        history(
+            SVGBackend::with_string(&mut buf, (800, 600)),
+            &history,
+            stride,
+        )
+        .unwrap();
+        assert_eq!(buf.matches("<circle").count(), n, "{stride}");
+    }
+}
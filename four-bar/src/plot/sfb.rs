@@ -45,18 +45,19 @@ impl Plot for Figure<'_, '_> {
         B: DrawingBackend,
     {
         self.check_empty::<B>()?;
-        root.fill(&WHITE)?;
+        self.fill_background(root)?;
         // Draw axis description
         xyz_label(root, self.font, ["x", "y", "z"])?;
-        let (stroke, dot_size) = self.get_dot_size();
+        let (stroke, dot_size) = self.get_dot_size(root.dim_in_pixel());
         let sphere = self.get_sphere_center_radius();
         let [x_spec, y_spec, z_spec] = if let Some((sc, r)) = &sphere {
             debug_assert!(*r > 0.);
             [sc.x - r..sc.x + r, sc.y - r..sc.y + r, sc.z - r..sc.z + r]
         } else {
-            area3d(self.lines().map(|data| data.line.boundary()))
+            area3d_margin(self.lines().map(|data| data.line.boundary()), self.margin)
         };
         let Opt { grid, axis, legend, .. } = self.opt;
+        let spans = [&x_spec, &y_spec, &z_spec].map(|r| r.end - r.start);
         let mut chart = ChartBuilder::on(root)
             .set_label_area_size(LabelAreaPosition::Left, (8).percent())
             .set_label_area_size(LabelAreaPosition::Bottom, (4).percent())
@@ -76,6 +77,8 @@ impl Plot for Figure<'_, '_> {
             pb.into_matrix()
         });
         if axis {
+            let fmt = |i: usize| move |v: &f64| self.tick_format.format_tick(*v, spans[i]);
+            let [x_fmt, y_fmt, z_fmt] = [fmt(0), fmt(1), fmt(2)];
             let mut axes = chart.configure_axes();
             if !grid {
                 axes.max_light_lines(0);
@@ -85,9 +88,9 @@ impl Plot for Figure<'_, '_> {
                 .axis_panel_style(TRANSPARENT)
                 .x_labels(4)
                 .z_labels(4)
-                .x_formatter(&formatter)
-                .y_formatter(&formatter)
-                .z_formatter(&formatter)
+                .x_formatter(&x_fmt)
+                .y_formatter(&y_fmt)
+                .z_formatter(&z_fmt)
                 .draw()?;
         }
         // Draw grid
@@ -95,10 +98,31 @@ impl Plot for Figure<'_, '_> {
             let p = (sc.x, sc.y + *r, sc.z);
             chart.draw_series(Ball::new((sc.x, sc.y, sc.z), p, LIGHTGRAY.filled()).series())?;
         }
+        if let Some((sc, r)) = sphere.filter(|_| self.sphere_grid && self.sphere_grid_step > 0.) {
+            use std::f64::consts::{PI, TAU};
+            const N: usize = 180;
+            let style = LIGHTGRAY.stroke_width((stroke / 3).max(1));
+            let at = |theta: f64, phi: f64| {
+                let (st, ct) = theta.sin_cos();
+                let (sp, cp) = phi.sin_cos();
+                (sc.x + r * sp * ct, sc.y + r * sp * st, sc.z + r * cp)
+            };
+            let t = |i: usize| i as f64 / N as f64 * TAU;
+            let n = (PI / self.sphere_grid_step).ceil() as usize;
+            // Latitude circles
+            for phi in (1..n).map(|i| i as f64 * self.sphere_grid_step) {
+                chart.draw_series(LineSeries::new((0..=N).map(|i| at(t(i), phi)), style))?;
+            }
+            // Longitude great circles
+            for theta in (0..n).map(|i| i as f64 * self.sphere_grid_step) {
+                chart.draw_series(LineSeries::new((0..=N).map(|i| at(theta, t(i))), style))?;
+            }
+        }
         // Draw layer 1: Draw linkage in the back of the sphere
         let mut link_front = Vec::new();
         let mut grounded_front = Vec::new();
         let mut joints_front = Vec::new();
+        let mut labels_front = Vec::new();
         if let (Some((sc, _)), Some(joints)) = (sphere, joints) {
             let [p1, p2, p3, p4, p5] = joints;
             for line in [[p1, p3].as_slice(), &[p3, p5, p4, p3], &[p2, p4]] {
@@ -145,8 +169,12 @@ impl Plot for Figure<'_, '_> {
             for &[x, y, z] in &joints[..2] {
                 let is_front = is_front_of_sphere(sc, na::Point3::new(x, y, z), yaw);
                 let style = if is_front { BLACK } else { BACKLINK }.filled();
-                let grounded = EmptyElement::at((x, y, z))
-                    + TriangleMarker::new((0, dot_size as i32), dot_size * 2, style);
+                let grounded = (self.ground_marker).marker(
+                    (x, y, z),
+                    (0, dot_size as i32),
+                    dot_size * 2,
+                    style,
+                );
                 if is_front {
                     grounded_front.push(grounded);
                 } else {
@@ -158,13 +186,15 @@ impl Plot for Figure<'_, '_> {
                 let color = if is_front { BLACK } else { BACKLINK }.to_rgba();
                 let style = ShapeStyle { color, filled: n == 4, stroke_width: stroke };
                 let t_style = self.get_big_font().color(&BLUE);
-                let joint = EmptyElement::at((x, y, z))
-                    + Circle::new((0, 0), dot_size, style)
+                let joint = (self.joint_marker).marker((x, y, z), (0, 0), dot_size, style);
+                let label = EmptyElement::at((x, y, z))
                     + Text::new(format!("p{}", Subscript(n + 1)), (15, 15), t_style);
                 if is_front {
                     joints_front.push(joint);
+                    labels_front.push(label);
                 } else {
                     chart.draw_series([joint])?;
+                    chart.draw_series([label])?;
                 }
             }
         }
@@ -172,12 +202,23 @@ impl Plot for Figure<'_, '_> {
         for data in self.lines() {
             data.draw(&mut chart, stroke, self.font)?;
         }
+        let font = self.get_font();
+        for data in self.lines() {
+            data.draw_annotations(&mut chart, stroke, &font)?;
+        }
         // Draw layer 3: Draw linkage in the front of the sphere
         for line in link_front {
             chart.draw_series(line)?;
         }
         chart.draw_series(grounded_front)?;
         chart.draw_series(joints_front)?;
+        chart.draw_series(labels_front)?;
+        // Draw coupler point marker
+        if let (Some(_), Some([.., [x, y, z]]), Some((size, style))) =
+            (sphere, joints, self.get_marker_style())
+        {
+            chart.draw_series([Circle::new((x, y, z), size, style)])?;
+        }
         // Draw legend
         if let Some(legend) = legend.to_plotter_pos().filter(|_| self.has_legend()) {
             chart
@@ -194,13 +235,26 @@ impl Plot for Figure<'_, '_> {
 }
 
 /// Get the area of a set of points in 3D.
+///
+/// The default margin of [`Opt::margin`] is used, see [`area3d_margin()`].
 pub fn area3d<I>(pts: I) -> [std::ops::Range<f64>; 3]
+where
+    I: IntoIterator,
+    ExtBound<3>: FromIterator<I::Item>,
+{
+    area3d_margin(pts, Opt::new().margin)
+}
+
+/// Get the area of a set of points in 3D with a margin.
+///
+/// The margin is a fraction of the data range, added to both sides.
+pub fn area3d_margin<I>(pts: I, margin: f64) -> [std::ops::Range<f64>; 3]
 where
     I: IntoIterator,
     ExtBound<3>: FromIterator<I::Item>,
 {
     ExtBound::from_iter(pts)
-        .to_square(0.2)
+        .to_square(margin)
         .map_to(|min, max| min..max)
 }
 
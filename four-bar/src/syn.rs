@@ -18,6 +18,7 @@ pub use self::{
     dd_path::{DDPathSyn, FbDDSyn, SFbDDSyn},
     motion::{MFbSyn, MOFit, MotionSyn},
     path::{FbSyn, PathSyn, SFbSyn},
+    point::{FbPointSyn, PointSyn},
 };
 use crate::*;
 
@@ -25,6 +26,7 @@ mod dd_motion;
 mod dd_path;
 mod motion;
 mod path;
+mod point;
 
 /// Base type of a mechanism `M` synthesis.
 pub struct Syn<T, M, const N: usize, const D: usize> {
@@ -34,10 +36,22 @@ pub struct Syn<T, M, const N: usize, const D: usize> {
     pub(crate) mode: Mode,
     // How many points need to be generated and compared
     pub(crate) res: usize,
+    // The joint that traces the objective curve
+    pivot: Pivot,
+    // Normalization options of the EFD comparison
+    pub(crate) norm: NormOpt,
+    // The planar target curve is counter-clockwise, set by the constructors
+    pub(crate) tar_ccw: bool,
     // Constrain the origin of the mechanism
     origin: Option<[f64; D]>,
     // Constrain the scale of the mechanism
     scale: Option<f64>,
+    // Custom bounds of the variables
+    bound: Option<Vec<[f64; 2]>>,
+    // Constrain the input angle range of the mechanism
+    pub(crate) input_range: Option<[f64; 2]>,
+    // Project the candidates onto the symmetric linkages
+    symmetric: bool,
     // Marker of the mechanism
     _marker: std::marker::PhantomData<M>,
 }
@@ -48,8 +62,14 @@ impl<T, M, const N: usize, const D: usize> Syn<T, M, N, D> {
             tar,
             mode,
             res: 180,
+            pivot: Pivot::default(),
+            norm: NormOpt::default(),
+            tar_ccw: true,
             origin: None,
             scale: None,
+            bound: None,
+            input_range: None,
+            symmetric: false,
             _marker: std::marker::PhantomData,
         }
     }
@@ -60,6 +80,20 @@ impl<T, M, const N: usize, const D: usize> Syn<T, M, N, D> {
         Self { res, ..self }
     }
 
+    /// Set the joint that traces the objective curve.
+    ///
+    /// Only used in the path synthesis, defaults to [`Pivot::Coupler`].
+    pub fn pivot(self, pivot: Pivot) -> Self {
+        Self { pivot, ..self }
+    }
+
+    /// Set the normalization options of the EFD comparison.
+    ///
+    /// Only used in the path synthesis, see [`NormOpt`].
+    pub fn norm_opt(self, norm: NormOpt) -> Self {
+        Self { norm, ..self }
+    }
+
     /// Specify the mechanism is on origin and unit scale.
     pub fn on_unit(self) -> Self {
         self.origin([0.; D]).scale(1.)
@@ -76,6 +110,136 @@ impl<T, M, const N: usize, const D: usize> Syn<T, M, N, D> {
         Self { scale: Some(scale), ..self }
     }
 
+    /// Generate the curves of the candidates only over the input angle range
+    /// `[start, end]`, instead of the whole range of motion.
+    ///
+    /// The candidates that cannot be driven over the range are infeasible. The
+    /// generated curves are open, so return [`Error::ClosedTarget`] if the
+    /// target is closed. Not used in the partial mode, which has its own input
+    /// angle range.
+    ///
+    /// [`Error::ClosedTarget`]: crate::Error::ClosedTarget
+    pub fn input_range(self, range: [f64; 2]) -> Result<Self, crate::Error> {
+        if self.mode.is_target_open() {
+            Ok(Self { input_range: Some(range), ..self })
+        } else {
+            Err(crate::Error::ClosedTarget)
+        }
+    }
+
+    /// Exploit the mirror symmetry of the target `curve` if it is detected.
+    ///
+    /// The curve is symmetric if its [`curve::mirror_err()`] is small enough.
+    /// Then the candidates are projected onto the linkages that generate
+    /// symmetric coupler curves, which reduces the search space. The
+    /// projection is supported by the planar four-bar linkages only, see
+    /// [`mech::FromVectorized::to_symmetric()`]. Not used if the pivot is not
+    /// the coupler.
+    pub fn exploit_symmetry(self, curve: &[[f64; D]]) -> Self
+    where
+        efd::U<D>: efd::EfdDim<D>,
+    {
+        const TOL: f64 = 1e-2;
+        let symmetric =
+            curve.len() > 2 && crate::curve::mirror_err(curve, self.mode.is_target_open()) < TOL;
+        Self { symmetric, ..self }
+    }
+
+    /// Return true if the symmetry of the target is exploited.
+    pub fn is_symmetric(&self) -> bool {
+        self.symmetric && self.pivot == Pivot::Coupler
+    }
+
+    /// Pin the variables to the fixed values.
+    ///
+    /// The lower and upper bounds of the variable `xs[i]` are collapsed to
+    /// `fixed[i]` if it is provided. For example, the variables of
+    /// [`NormFourBar`] are `[l1, l3, l4, l5, g]`.
+    pub fn fixed(self, fixed: &[Option<f64>]) -> Self
+    where
+        M: mech::FromVectorized<N>,
+    {
+        if fixed.iter().all(Option::is_none) {
+            return Self { bound: None, ..self };
+        }
+        let mut bound = self.default_bound().to_vec();
+        for (b, v) in std::iter::zip(&mut bound, fixed) {
+            if let Some(v) = v {
+                *b = [*v; 2];
+            }
+        }
+        Self { bound: Some(bound), ..self }
+    }
+
+    /// Evaluate the parameters `xs` without running the solver, return the
+    /// cost and the linkage.
+    ///
+    /// This function wraps [`mh::ObjFunc::fitness()`], so the objective can
+    /// be used in the other optimizers. The length of `xs` should match the
+    /// bounds of the task, see [`mh::Bounded::bound()`]. The cost of an
+    /// infeasible candidate is very large.
+    ///
+    /// ```
+    /// use four_bar::{
+    ///     mech::{CurveGen as _, IntoVectorized as _, Normalized as _},
+    ///     syn, FourBar, NormFourBar,
+    /// };
+    ///
+    /// let fb = FourBar::example();
+    /// let func = syn::FbSyn::from_curve(fb.curve(90), syn::Mode::Closed).res(90);
+    /// let (xs, _) = NormFourBar::normalize(fb).into_vectorized();
+    /// let (cost, _) = func.eval(&xs).unwrap();
+    /// assert!(cost < 1e-6);
+    /// assert!(func.eval(&xs[1..]).is_err());
+    /// ```
+    pub fn eval<Fit>(&self, xs: &[f64]) -> Result<(f64, M::De), crate::Error>
+    where
+        Self: mh::ObjFunc<Ys = mh::WithProduct<Fit, M::De>> + mh::Bounded,
+        M: mech::Normalized<D>,
+        Fit: mh::Fitness<Eval = f64>,
+        efd::U<D>: efd::EfdDim<D>,
+    {
+        use mh::{Bounded as _, Fitness as _, ObjFunc as _};
+        let expected = self.bound().len();
+        if xs.len() != expected {
+            return Err(crate::Error::ParamLength { expected, found: xs.len() });
+        }
+        let ys = self.fitness(xs);
+        Ok((ys.eval(), ys.into_product()))
+    }
+
+    fn default_bound(&self) -> &[[f64; 2]]
+    where
+        M: mech::FromVectorized<N>,
+    {
+        if self.mode == Mode::Partial {
+            M::BOUND_PARTIAL
+        } else {
+            &M::BOUND
+        }
+    }
+
+    pub(crate) fn get_bound(&self) -> &[[f64; 2]]
+    where
+        M: mech::FromVectorized<N>,
+    {
+        self.bound
+            .as_deref()
+            .unwrap_or_else(|| self.default_bound())
+    }
+
+    pub(crate) fn pivot_curve(&self, fb: &M, start: f64, end: f64) -> Vec<[f64; D]>
+    where
+        M: mech::CurveGen<D>,
+    {
+        match self.pivot {
+            Pivot::Coupler => fb.curve_in(start, end, self.res),
+            pivot => (fb.curves_in(start, end, self.res).into_iter())
+                .map(|c| c[pivot.index()])
+                .collect(),
+        }
+    }
+
     pub(crate) fn unit_err(&self, geo: &efd::GeoVar<efd::Rot<D>, D>) -> f64
     where
         efd::U<D>: efd::EfdDim<D>,
@@ -101,15 +265,25 @@ pub enum Mode {
     /// Closed path matching
     Closed,
     /// Use closed path to match open path
+    ///
+    /// The input angle range `[start, end]` is appended to the variables, for
+    /// both path and motion synthesis. For motion synthesis, the curve and
+    /// pose errors of the segment are summed.
     Partial,
     /// Open path matching
     Open,
+    /// Choose [`Mode::Closed`] or [`Mode::Open`] by the target curve
+    ///
+    /// The mode is resolved when creating the task from the target curve, see
+    /// [`Mode::resolve()`]. An unresolved automatic mode is treated as the
+    /// closed mode.
+    Auto,
 }
 
 impl Mode {
     /// Return true if the target curve is open.
     pub const fn is_target_open(&self) -> bool {
-        !matches!(self, Self::Closed)
+        matches!(self, Self::Open | Self::Partial)
     }
 
     /// Return true if the synthesis curve is open.
@@ -121,6 +295,126 @@ impl Mode {
     pub const fn is_partial(&self) -> bool {
         matches!(self, Self::Partial)
     }
+
+    /// Return true if the synthesis mode is automatic.
+    pub const fn is_auto(&self) -> bool {
+        matches!(self, Self::Auto)
+    }
+
+    /// Resolve the automatic mode by the target curve.
+    ///
+    /// Return [`Mode::Closed`] if the target curve is a closed loop (see
+    /// [`crate::curve::is_closed()`]), otherwise [`Mode::Open`]. The other
+    /// modes are returned unchanged.
+    ///
+    /// ```
+    /// use four_bar::syn::Mode;
+    ///
+    /// let arc = [[1., 0.], [0., 1.], [-1., 0.]];
+    /// assert_eq!(Mode::Auto.resolve(&arc), Mode::Open);
+    /// assert_eq!(Mode::Partial.resolve(&arc), Mode::Partial);
+    /// ```
+    pub fn resolve<const D: usize>(self, curve: &[[f64; D]]) -> Self {
+        match self {
+            Self::Auto if crate::curve::is_closed(curve) => Self::Closed,
+            Self::Auto => Self::Open,
+            mode => mode,
+        }
+    }
+}
+
+/// Normalization options of the EFD comparison in the path synthesis.
+///
+/// The candidate curves are normalized before comparing with the target, the
+/// defaults are the same as the plain EFD normalization.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub struct NormOpt {
+    /// Match the mirror image of the target by also comparing the mirror
+    /// image of the candidate linkage, see
+    /// [`mech::FromVectorized::to_mirror()`]
+    ///
+    /// The EFD normalization cannot match the reflected curves, since the
+    /// rotation cannot turn a curve into its mirror image. The reflection
+    /// reverses the traversal direction as well, so the curves of the mirror
+    /// linkage are compared in the reversed direction. The image with the lower
+    /// error is taken. Only supported by the planar linkages.
+    pub mirror: bool,
+    /// Also compare the other choice of the starting point and the rotation
+    /// of the first harmonic ellipse
+    ///
+    /// The EFD normalization takes one end of the major axis of the first
+    /// harmonic ellipse as the starting point, the other end is the same curve
+    /// rotated by a half turn. The nearly circular ellipses may pick the
+    /// different ends for the similar curves, which causes a spurious high
+    /// error. Only used for the closed planar curves.
+    pub flip: bool,
+    /// Orient the target and the candidate curves to counter-clockwise
+    /// before comparing, see [`curve::ensure_ccw()`]
+    ///
+    /// The direction mismatch between the target and the candidate causes a
+    /// spurious high error. The direction of the target is detected by the
+    /// constructors, and the clockwise target is compared as its reversed
+    /// curve, so the candidates are reversed instead. Only used for the
+    /// planar curves.
+    pub ccw: bool,
+}
+
+impl NormOpt {
+    // Orient the candidate curve to the direction of the target, which is
+    // the same as orienting both curves to counter-clockwise
+    pub(crate) fn orient<const D: usize>(&self, c: &mut [[f64; D]], tar_ccw: bool) {
+        if !self.ccw || D != 2 {
+            return;
+        }
+        if is_ccw(c) != tar_ccw {
+            c.reverse();
+        }
+    }
+}
+
+// Return true if the planar projection of the curve is counter-clockwise
+pub(crate) fn is_ccw<const D: usize>(c: &[[f64; D]]) -> bool {
+    let planar = c.iter().map(|p| [p[0], p[1]]).collect::<Vec<_>>();
+    curve::signed_area(&planar) >= 0.
+}
+
+/// The joint that traces the objective curve.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub enum Pivot {
+    /// The end of the driver link
+    Driver,
+    /// The end of the follower link
+    Follower,
+    /// The coupler point
+    #[default]
+    Coupler,
+}
+
+impl Pivot {
+    /// Pivot list.
+    pub const LIST: [Self; 3] = [Self::Driver, Self::Follower, Self::Coupler];
+
+    /// Get the pivot names.
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::Driver => "Driver",
+            Self::Follower => "Follower",
+            Self::Coupler => "Coupler",
+        }
+    }
+
+    /// Index of the curves from [`mech::CurveGen::curves()`].
+    pub const fn index(&self) -> usize {
+        match self {
+            Self::Driver => 0,
+            Self::Follower => 1,
+            Self::Coupler => 2,
+        }
+    }
 }
 
 pub(crate) trait Infeasible {
@@ -153,8 +447,23 @@ const fn slice_to_array<const N: usize>(slice: &[f64]) -> [f64; N] {
     out
 }
 
+// Return true if the input angle range `[t1, t2]` is inside the bound
+fn range_in_bound(bound: &mech::AngleBound, [t1, t2]: [f64; 2]) -> bool {
+    use std::f64::consts::TAU;
+    match bound.to_value() {
+        Some(_) if !bound.is_open() => true,
+        Some([a, b]) => {
+            let wrap = |t: f64| (t - a).rem_euclid(TAU);
+            wrap(t1) <= wrap(t2) && wrap(t2) <= wrap(b)
+        }
+        None => false,
+    }
+}
+
 pub(crate) fn impl_fitness<M, S, Fit, F1, F2, const N: usize, const D: usize>(
     mode: Mode,
+    range: Option<[f64; 2]>,
+    symmetric: bool,
     xs: &[f64],
     get_series: F1,
     get_err: F2,
@@ -170,9 +479,18 @@ where
 {
     #[cfg(feature = "rayon")]
     use mh::rayon::prelude::*;
-    let mut fb = M::from_vectorized_s1(slice_to_array(xs));
+    let mut code = slice_to_array(xs);
+    if symmetric {
+        code = M::to_symmetric(code).unwrap_or(code);
+    }
+    let mut fb = M::from_vectorized_s1(code);
     fb.set_to_planar_loop();
-    let bound = fb.angle_bound().check_mode(mode.is_result_open());
+    // The curves over the input range are open, no matter the linkage type
+    let range = range.filter(|_| !mode.is_partial());
+    let bound = match range {
+        Some(_) => fb.angle_bound(),
+        None => fb.angle_bound().check_mode(mode.is_result_open()),
+    };
     let states = fb.states_from_bound(bound);
     let gen_series = &get_series;
     let f = |[t1, t2]: [f64; 2]| {
@@ -185,11 +503,12 @@ where
     };
     use mh::Fitness as _;
     match mode {
-        Mode::Closed | Mode::Open => bound
-            .check_min()
-            .to_value()
-            .and_then(|t| f(t).min_by(|a, b| a.eval().partial_cmp(&b.eval()).unwrap()))
-            .unwrap_or_else(infeasible),
+        Mode::Closed | Mode::Open | Mode::Auto => match range {
+            Some(range) => range_in_bound(&bound, range).then_some(range),
+            None => bound.check_min().to_value(),
+        }
+        .and_then(|t| f(t).min_by(|a, b| a.eval().partial_cmp(&b.eval()).unwrap()))
+        .unwrap_or_else(infeasible),
         Mode::Partial if !bound.is_valid() => infeasible(),
         Mode::Partial => {
             let bound = mech::AngleBound::open_and_rev_at(xs[N], xs[N + 1]);
@@ -222,5 +541,286 @@ assert_impl!(
     MFbSyn,
     FbDDSyn,
     SFbDDSyn,
-    MFbDDSyn
+    MFbDDSyn,
+    FbPointSyn
 );
+
+#[test]
+fn motion_partial_target() {
+    use mech::{IntoVectorized as _, Normalized as _, PoseGen as _};
+    use mh::{Fitness as _, ObjFunc as _};
+    const RES: usize = 90;
+    let fb = MFourBar::example();
+    let (start, end) = (0.5, 2.5);
+    let (curve, uvec) = fb.pose_in(start, end, RES);
+    let func = MFbSyn::from_uvec(curve, uvec, Mode::Partial).res(RES);
+    let (mut xs, _) = MNormFourBar::normalize(fb).into_vectorized();
+    xs.extend([start, end]);
+    assert_eq!(xs.len(), mh::Bounded::bound(&func).len());
+    let err = func.fitness(&xs).eval();
+    assert!(err < 1e-6, "{err}");
+    // A different linkage or segment is worse
+    let mut xs_l1 = xs.clone();
+    xs_l1[0] *= 1.2;
+    assert!(func.fitness(&xs_l1).eval() > err);
+    let mut xs_seg = xs.clone();
+    xs_seg[7] += 0.5;
+    assert!(func.fitness(&xs_seg).eval() > err);
+}
+
+#[test]
+fn fixed_extension_result() {
+    use mech::Normalized as _;
+    let curve = FourBar::example().curve(90);
+    let (l5, g) = (0.8, 1.2);
+    let func = FbSyn::from_curve(&curve, Mode::Closed).res(90).fixed(&[
+        None,
+        None,
+        None,
+        Some(l5),
+        Some(g),
+    ]);
+    let s = mh::Solver::build(mh::Rga::default(), func)
+        .seed(Some(0u64))
+        .pop_num(20)
+        .task(|ctx| ctx.gen >= 5)
+        .solve();
+    let (_, fb) = s.into_err_result();
+    let norm = NormFourBar::normalize(fb);
+    assert!((norm.l5 - l5).abs() < 1e-9, "{}", norm.l5);
+    assert!((norm.g - g).abs() < 1e-9, "{}", norm.g);
+}
+
+#[test]
+fn follower_pivot_target() {
+    use mech::{CurveGen as _, IntoVectorized as _, Normalized as _};
+    use mh::{Fitness as _, ObjFunc as _};
+    const RES: usize = 90;
+    let fb = FourBar::example();
+    let curve = (fb.curves(RES).into_iter())
+        .map(|c| c[Pivot::Follower.index()])
+        .collect::<Vec<_>>();
+    let (xs, _) = NormFourBar::normalize(fb).into_vectorized();
+    let func = FbSyn::from_curve(&curve, Mode::Closed).res(RES);
+    let err = func.pivot(Pivot::Follower).fitness(&xs).eval();
+    assert!(err < 1e-6, "{err}");
+    let func = FbSyn::from_curve(&curve, Mode::Closed).res(RES);
+    assert!(func.fitness(&xs).eval() > err);
+}
+
+#[test]
+fn auto_mode_target() {
+    use mech::CurveGen as _;
+    const RES: usize = 90;
+    let fb = FourBar::example();
+    // The full loop of a crank rocker
+    let curve = fb.curve(RES);
+    assert_eq!(FbSyn::from_curve(&curve, Mode::Auto).mode, Mode::Closed);
+    // A segment of the loop
+    let curve = fb.curve_in(0.5, 2.5, RES);
+    assert_eq!(FbSyn::from_curve(&curve, Mode::Auto).mode, Mode::Open);
+    // The explicit modes are kept
+    assert_eq!(FbSyn::from_curve(&curve, Mode::Closed).mode, Mode::Closed);
+}
+
+#[test]
+fn short_target_curve() {
+    let curve = FourBar::example().curve(90);
+    assert!(FbSyn::try_from_curve(&curve, Mode::Closed).is_ok());
+    let err = FbSyn::try_from_curve(&curve[..2], Mode::Closed).err();
+    assert_eq!(err, Some(crate::Error::CurveTooShort));
+}
+
+#[test]
+fn mirror_norm_target() {
+    use mech::{IntoVectorized as _, Normalized as _};
+    use mh::{Fitness as _, ObjFunc as _};
+    const RES: usize = 90;
+    // The mirror image of the coupler curve
+    let fb = FourBar::example();
+    let curve = fb
+        .curve(RES)
+        .into_iter()
+        .map(|[x, y]| [-x, y])
+        .collect::<Vec<_>>();
+    let (xs, _) = NormFourBar::normalize(fb).into_vectorized();
+    let func = FbSyn::from_curve(&curve, Mode::Closed).res(RES);
+    let err = func.fitness(&xs).eval();
+    let func = func.norm_opt(NormOpt { mirror: true, ..NormOpt::default() });
+    let ys = func.fitness(&xs);
+    let err_mirror = ys.eval();
+    assert!(err_mirror < 1e-6, "{err_mirror}");
+    assert!(err > 1e-2, "{err}");
+    // The result is the mirror linkage that traces the target
+    let dist = curve::hausdorff(&ys.into_product().curve(RES), &curve);
+    assert!(dist < 1e-4, "{dist}");
+}
+
+#[test]
+fn ccw_norm_target() {
+    use mech::{IntoVectorized as _, Normalized as _};
+    use mh::{Fitness as _, ObjFunc as _};
+    const RES: usize = 90;
+    let fb = FourBar::example();
+    let (xs, _) = NormFourBar::normalize(fb.clone()).into_vectorized();
+    let norm = NormOpt { ccw: true, ..NormOpt::default() };
+    // The target in both directions, without orienting it before
+    for rev in [false, true] {
+        let mut curve = fb.curve(RES);
+        if rev {
+            curve.reverse();
+        }
+        let func = FbSyn::from_curve(&curve, Mode::Closed).res(RES);
+        assert_eq!(func.tar_ccw, curve::signed_area(&curve) > 0.);
+        let err = func.norm_opt(norm).fitness(&xs).eval();
+        assert!(err < 1e-6, "{err}");
+        let func = FbDDSyn::from_curve(&curve, Mode::Closed).res(RES);
+        assert_eq!(func.tar_ccw, curve::signed_area(&curve) > 0.);
+    }
+}
+
+#[test]
+fn ccw_second_harmonic_target() {
+    use std::f64::consts::TAU;
+    // A clockwise curve, but its first harmonic is counter-clockwise
+    let curve = (0..90)
+        .map(|i| TAU * i as f64 / 90.)
+        .map(|t| {
+            [
+                t.cos() + 0.8 * (2. * t).cos(),
+                t.sin() - 0.8 * (2. * t).sin(),
+            ]
+        })
+        .collect::<Vec<_>>();
+    assert!(curve::signed_area(&curve) < 0.);
+    let func = FbSyn::from_curve(&curve, Mode::Closed);
+    assert!(func.harmonic() >= 2);
+    assert!(!func.tar_ccw);
+}
+
+#[test]
+fn flip_norm_target() {
+    use mech::{IntoVectorized as _, Normalized as _};
+    use mh::{Fitness as _, ObjFunc as _};
+    const RES: usize = 90;
+    // The target with the other end of the first harmonic as the starting point
+    let fb = FourBar::example();
+    let curve = fb.curve(RES);
+    let efd = efd::Efd2::from_curve(&curve, false);
+    // Which is the curve rotated by a half turn about its center
+    let [cx, cy] = efd.as_geo().trans();
+    let tar_curve = (curve.iter())
+        .map(|[x, y]| [2. * cx - x, 2. * cy - y])
+        .collect::<Vec<_>>();
+    let (mut coeffs, geo) = efd.into_inner();
+    coeffs
+        .iter_mut()
+        .skip(1)
+        .step_by(2)
+        .for_each(|m| m.neg_mut());
+    let tar = efd::Efd::from_parts_unchecked(coeffs, geo);
+    let (xs, _) = NormFourBar::normalize(fb).into_vectorized();
+    let func = FbSyn::from_efd(tar, Mode::Closed).res(RES);
+    let err = func.fitness(&xs).eval();
+    let func = func.norm_opt(NormOpt { flip: true, ..NormOpt::default() });
+    let ys = func.fitness(&xs);
+    let err_flip = ys.eval();
+    assert!(err_flip < 1e-6, "{err_flip}");
+    assert!(err > 1e-2, "{err}");
+    // The result is placed and oriented as the flipped target
+    let dist = curve::hausdorff(&ys.into_product().curve(RES), &tar_curve);
+    assert!(dist < 1e-4, "{dist}");
+}
+
+#[test]
+fn input_range_target() {
+    use mech::{CurveGen as _, IntoVectorized as _, Normalized as _};
+    use mh::{Fitness as _, ObjFunc as _};
+    const RES: usize = 90;
+    let fb = FourBar::example();
+    let range = [0.5, 2.5];
+    let curve = fb.curve_in(range[0], range[1], RES);
+    let (xs, _) = NormFourBar::normalize(fb.clone()).into_vectorized();
+    let func = FbSyn::from_curve(&curve, Mode::Open).res(RES);
+    // The crank rocker has no open curve over the whole range of motion
+    let err = func.fitness(&xs).eval();
+    let err_range = func.input_range(range).unwrap().fitness(&xs).eval();
+    assert!(err_range < 1e-6, "{err_range}");
+    assert!(err > 1., "{err}");
+    // The closed target is not traced by the open curves
+    let func = FbSyn::from_curve(fb.curve(RES), Mode::Closed);
+    assert_eq!(
+        func.input_range(range).err(),
+        Some(crate::Error::ClosedTarget)
+    );
+}
+
+#[test]
+fn exploit_symmetry_target() {
+    use mech::CurveGen as _;
+    use std::f64::consts::FRAC_PI_6;
+    const RES: usize = 90;
+    // The coupler curve is symmetric if `l3 = l4 = |p4 - p5|`
+    let norm = NormFourBar {
+        l1: 90.,
+        l3: 70.,
+        l4: 70.,
+        l5: 140. * FRAC_PI_6.cos(),
+        g: FRAC_PI_6,
+        stat: mech::Stat::C1B1,
+    };
+    let curve = FourBar::new(mech::fb::UnNorm::from_driver(35.), norm).curve(RES);
+    let asym = FourBar::example().curve(RES);
+    let func = FbSyn::from_curve(&asym, Mode::Closed).exploit_symmetry(&asym);
+    assert!(!func.is_symmetric());
+    let solve = |exploit: bool| {
+        let mut func = FbSyn::from_curve(&curve, Mode::Closed).res(RES);
+        if exploit {
+            func = func.exploit_symmetry(&curve);
+            assert!(func.is_symmetric());
+        }
+        let s = mh::Solver::build(mh::Rga::default(), func)
+            .seed(Some(0u64))
+            .pop_num(40)
+            .task(|ctx| ctx.gen >= 20)
+            .solve();
+        s.into_err_result().0
+    };
+    let (err, err_sym) = (solve(false), solve(true));
+    assert!(err_sym <= err, "{err_sym} > {err}");
+}
+
+#[test]
+fn eval_solver_pool() {
+    use mech::CurveGen as _;
+    use mh::Fitness as _;
+    use std::sync::{Arc, Mutex};
+    const RES: usize = 90;
+    let curve = FourBar::example().curve(RES);
+    let func = || FbSyn::from_curve(&curve, Mode::Closed).res(RES);
+    let last = Arc::new(Mutex::new(None));
+    let last_w = last.clone();
+    mh::Solver::build(mh::Rga::default(), func())
+        .seed(Some(0u64))
+        .pop_num(10)
+        .task(|ctx| ctx.gen >= 5)
+        .callback(move |ctx| {
+            let best = (ctx.pool.clone(), ctx.pool_y.clone());
+            *last_w.lock().unwrap() = Some(best);
+        })
+        .solve();
+    let (pool, pool_y) = last.lock().unwrap().take().unwrap();
+    let func = func();
+    for (xs, ys) in std::iter::zip(&pool, &pool_y) {
+        let (cost, _) = func.eval(xs).unwrap();
+        assert_eq!(cost, ys.eval());
+    }
+}
+
+#[test]
+fn unresolved_auto_as_closed() {
+    let (auto, closed) = (Mode::Auto, Mode::Closed);
+    assert_eq!(auto.is_target_open(), closed.is_target_open());
+    assert_eq!(auto.is_result_open(), closed.is_result_open());
+    assert_eq!(auto.is_partial(), closed.is_partial());
+}
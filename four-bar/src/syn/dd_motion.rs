@@ -17,8 +17,9 @@ where
         C1: efd::Curve<D>,
         C2: efd::Curve<D>,
     {
-        assert_ne!(mode, Mode::Closed, "Closed mode is not supported");
         let curve = curve1.as_curve();
+        let mode = mode.resolve(curve);
+        assert_ne!(mode, Mode::Closed, "Closed mode is not supported");
         let vectors = zip(curve, curve2.as_curve())
             .map(|(a, b)| renorm(std::array::from_fn(|i| b[i] - a[i])))
             .collect::<Vec<_>>();
@@ -31,6 +32,7 @@ where
         C: efd::Curve<D>,
         V: efd::Curve<D>,
     {
+        let mode = mode.resolve(curve.as_curve());
         let sig = efd::MotionSig::new(curve, vectors, mode.is_target_open());
         Self::new(sig, mode)
     }
@@ -44,11 +46,7 @@ where
 {
     #[inline]
     fn bound(&self) -> &[[f64; 2]] {
-        if self.mode == Mode::Partial {
-            M::BOUND_PARTIAL
-        } else {
-            &M::BOUND
-        }
+        self.get_bound()
     }
 }
 
@@ -67,23 +65,30 @@ where
             let (curve, pose) = fb.pose_in(start, end, self.res);
             (curve.len() > 2).then_some((curve, pose))
         };
-        impl_fitness(self.mode, xs, get_series, |((c, v), fb)| {
-            let efd = efd::PosedEfd::from_uvec(c, v, is_open);
-            let geo = efd.as_curve().as_geo().to(self.tar.as_geo());
-            let fb = fb.clone().trans_denorm(&geo);
-            use efd::Distance as _;
-            let curve = zip(
-                efd.as_curve().recon_norm_by(self.tar.as_t()),
-                &self.tar.curve,
-            )
-            .map(|(a, b)| a.l2_err(b))
-            .fold(0., f64::max);
-            let pose = zip(efd.as_pose().recon_by(self.tar.as_t()), &self.tar.vectors)
+        impl_fitness(
+            self.mode,
+            self.input_range,
+            self.is_symmetric(),
+            xs,
+            get_series,
+            |((c, v), fb)| {
+                let efd = efd::PosedEfd::from_uvec(c, v, is_open);
+                let geo = efd.as_curve().as_geo().to(self.tar.as_geo());
+                let fb = fb.clone().trans_denorm(&geo);
+                use efd::Distance as _;
+                let curve = zip(
+                    efd.as_curve().recon_norm_by(self.tar.as_t()),
+                    &self.tar.curve,
+                )
                 .map(|(a, b)| a.l2_err(b))
                 .fold(0., f64::max);
-            let err = curve.max(pose).max(self.unit_err(&geo));
-            mh::WithProduct::new(err, fb)
-        })
+                let pose = zip(efd.as_pose().recon_by(self.tar.as_t()), &self.tar.vectors)
+                    .map(|(a, b)| a.l2_err(b))
+                    .fold(0., f64::max);
+                let err = curve.max(pose).max(self.unit_err(&geo));
+                mh::WithProduct::new(err, fb)
+            },
+        )
     }
 }
 
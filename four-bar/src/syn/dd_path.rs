@@ -12,12 +12,17 @@ where
     efd::U<D>: efd::EfdDim<D>,
 {
     /// Create a new task from target curve.
+    ///
+    /// The direction of the planar target is detected for the
+    /// [`NormOpt::ccw`] option.
     pub fn from_curve<C>(curve: C, mode: Mode) -> Self
     where
         C: efd::Curve<D>,
     {
+        let mode = mode.resolve(curve.as_curve());
+        let tar_ccw = D != 2 || is_ccw(curve.as_curve());
         let sig = efd::PathSig::new(curve, mode.is_target_open());
-        Self::new(sig, mode)
+        Self { tar_ccw, ..Self::new(sig, mode) }
     }
 }
 
@@ -29,11 +34,7 @@ where
 {
     #[inline]
     fn bound(&self) -> &[[f64; 2]] {
-        if self.mode == Mode::Partial {
-            M::BOUND_PARTIAL
-        } else {
-            &M::BOUND
-        }
+        self.get_bound()
     }
 }
 
@@ -49,14 +50,22 @@ where
     fn fitness(&self, xs: &[f64]) -> Self::Ys {
         let is_open = self.mode.is_target_open();
         let get_series = |fb: &M, start, end| {
-            let curve = fb.curve_in(start, end, self.res);
+            let curve = self.pivot_curve(fb, start, end);
             (curve.len() > 2).then_some(curve)
         };
-        impl_fitness(self.mode, xs, get_series, |(c, fb)| {
-            let efd = efd::Efd::from_curve(c, is_open);
-            let geo = efd.as_geo().to(self.tar.as_geo());
-            let fb = fb.clone().trans_denorm(&geo);
-            mh::WithProduct::new(efd.err_sig(&self.tar).max(self.unit_err(&geo)), fb)
-        })
+        impl_fitness(
+            self.mode,
+            self.input_range,
+            self.is_symmetric(),
+            xs,
+            get_series,
+            |(mut c, fb)| {
+                self.norm.orient(&mut c, self.tar_ccw);
+                let efd = efd::Efd::from_curve(c, is_open);
+                let geo = efd.as_geo().to(self.tar.as_geo());
+                let fb = fb.clone().trans_denorm(&geo);
+                mh::WithProduct::new(efd.err_sig(&self.tar).max(self.unit_err(&geo)), fb)
+            },
+        )
     }
 }
@@ -16,6 +16,7 @@ where
         C1: efd::Curve<D>,
         C2: efd::Curve<D>,
     {
+        let mode = mode.resolve(curve1.as_curve());
         let efd = efd::PosedEfd::from_series(curve1, curve2, mode.is_target_open());
         Self::from_efd(efd, mode)
     }
@@ -27,6 +28,7 @@ where
         C: efd::Curve<D>,
         V: efd::Curve<D>,
     {
+        let mode = mode.resolve(curve.as_curve());
         let efd = efd::PosedEfd::from_uvec(curve, vectors, mode.is_target_open());
         Self::from_efd(efd, mode)
     }
@@ -50,11 +52,7 @@ where
 {
     #[inline]
     fn bound(&self) -> &[[f64; 2]] {
-        if self.mode == Mode::Partial {
-            M::BOUND_PARTIAL
-        } else {
-            &M::BOUND
-        }
+        self.get_bound()
     }
 }
 
@@ -107,22 +105,29 @@ where
             let (curve, pose) = fb.pose_in(start, end, self.res);
             (curve.len() > 2).then_some((curve, pose))
         };
-        impl_fitness(self.mode, xs, get_series, |((c, v), fb)| {
-            let efd = efd::PosedEfd::from_uvec_harmonic(c, v, is_open, self.harmonic());
-            let geo = efd.as_curve().as_geo().to(self.tar.as_curve().as_geo());
-            let fb = fb.clone().trans_denorm(&geo);
-            let err = MOFit {
-                curve: efd.as_curve().err(self.tar.as_curve()),
-                pose: efd.as_pose().err(self.tar.as_pose()),
-                center: {
-                    use efd::Distance as _;
-                    let me = efd.as_pose().as_geo().trans();
-                    let tar = self.tar.as_pose().as_geo().trans();
-                    me.l2_err(tar)
-                },
-                unit: self.unit_err(&geo),
-            };
-            mh::WithProduct::new(err, fb)
-        })
+        impl_fitness(
+            self.mode,
+            self.input_range,
+            self.is_symmetric(),
+            xs,
+            get_series,
+            |((c, v), fb)| {
+                let efd = efd::PosedEfd::from_uvec_harmonic(c, v, is_open, self.harmonic());
+                let geo = efd.as_curve().as_geo().to(self.tar.as_curve().as_geo());
+                let fb = fb.clone().trans_denorm(&geo);
+                let err = MOFit {
+                    curve: efd.as_curve().err(self.tar.as_curve()),
+                    pose: efd.as_pose().err(self.tar.as_pose()),
+                    center: {
+                        use efd::Distance as _;
+                        let me = efd.as_pose().as_geo().trans();
+                        let tar = self.tar.as_pose().as_geo().trans();
+                        me.l2_err(tar)
+                    },
+                    unit: self.unit_err(&geo),
+                };
+                mh::WithProduct::new(err, fb)
+            },
+        )
     }
 }
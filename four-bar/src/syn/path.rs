@@ -17,12 +17,38 @@ where
     where
         C: efd::Curve<D>,
     {
+        let mode = mode.resolve(curve.as_curve());
         Self::from_efd(efd::Efd::from_curve(curve, mode.is_target_open()), mode)
     }
 
+    /// Create a new task from target curve like [`PathSyn::from_curve()`],
+    /// but return [`Error::CurveTooShort`](crate::Error::CurveTooShort) if the
+    /// curve has less than 3 points.
+    pub fn try_from_curve<C>(curve: C, mode: Mode) -> Result<Self, crate::Error>
+    where
+        C: efd::Curve<D>,
+    {
+        if curve.as_curve().len() < 3 {
+            return Err(crate::Error::CurveTooShort);
+        }
+        Ok(Self::from_curve(curve, mode))
+    }
+
     /// Create a new task from target EFD coefficients.
+    ///
+    /// The direction of the planar target is detected for the
+    /// [`NormOpt::ccw`] option.
     pub fn from_efd(efd: efd::Efd<D>, mode: Mode) -> Self {
-        Self::new(efd, mode)
+        // The signed area is the sum of the determinants of the harmonics
+        // weighted by their orders, which is kept by the normalization
+        let area = (efd.coeffs_iter().enumerate())
+            .map(|(i, m)| {
+                let m = m.as_slice();
+                (i + 1) as f64 * (m[0] * m[3] - m[1] * m[2])
+            })
+            .sum::<f64>();
+        let tar_ccw = D != 2 || area >= 0.;
+        Self { tar_ccw, ..Self::new(efd, mode) }
     }
 
     /// The harmonic used of target EFD.
@@ -39,11 +65,7 @@ where
 {
     #[inline]
     fn bound(&self) -> &[[f64; 2]] {
-        if self.mode == Mode::Partial {
-            M::BOUND_PARTIAL
-        } else {
-            &M::BOUND
-        }
+        self.get_bound()
     }
 }
 
@@ -59,14 +81,67 @@ where
     fn fitness(&self, xs: &[f64]) -> Self::Ys {
         let is_open = self.mode.is_target_open();
         let get_series = |fb: &M, start, end| {
-            let curve = fb.curve_in(start, end, self.res);
+            let curve = self.pivot_curve(fb, start, end);
             (curve.len() > 2).then_some(curve)
         };
-        impl_fitness(self.mode, xs, get_series, |(c, fb)| {
+        let get_err = |c: &[[f64; D]]| {
             let efd = efd::Efd::from_curve_harmonic(c, is_open, self.harmonic());
             let geo = efd.as_geo().to(self.tar.as_geo());
-            let fb = fb.clone().trans_denorm(&geo);
-            mh::WithProduct::new(efd.err(&self.tar).max(self.unit_err(&geo)), fb)
-        })
+            let (mut err, mut geo) = (efd.err(&self.tar).max(self.unit_err(&geo)), geo);
+            if self.norm.flip && !is_open && D == 2 {
+                // The same curve rotated by a half turn about the center
+                let center = efd.as_geo().trans();
+                let c = (c.iter())
+                    .map(|p| std::array::from_fn(|i| 2. * center[i] - p[i]))
+                    .collect::<Vec<[f64; D]>>();
+                let efd_rot = efd::Efd::from_curve_harmonic(c, is_open, self.harmonic());
+                let geo_flip = efd_rot.as_geo().to(self.tar.as_geo());
+                // Another starting point, the even harmonics are negated
+                let (mut coeffs, _) = efd.into_inner();
+                coeffs
+                    .iter_mut()
+                    .skip(1)
+                    .step_by(2)
+                    .for_each(|m| m.neg_mut());
+                let efd = efd::Efd::from_parts_unchecked(coeffs, efd::GeoVar::identity());
+                let err_flip = efd.err(&self.tar).max(self.unit_err(&geo_flip));
+                if err_flip < err {
+                    (err, geo) = (err_flip, geo_flip);
+                }
+            }
+            (err, geo)
+        };
+        let fit = |xs: &[f64], rev: bool| {
+            impl_fitness(
+                self.mode,
+                self.input_range,
+                self.is_symmetric(),
+                xs,
+                &get_series,
+                |(mut c, fb): (Vec<_>, &M)| {
+                    if rev {
+                        c.reverse();
+                    }
+                    self.norm.orient(&mut c, self.tar_ccw);
+                    let (err, geo) = get_err(&c);
+                    let fb = fb.clone().trans_denorm(&geo);
+                    mh::WithProduct::new(err, fb)
+                },
+            )
+        };
+        let ys = fit(xs, false);
+        let code = self.norm.mirror.then(|| M::to_mirror(slice_to_array(xs)));
+        let Some(code) = code.flatten() else {
+            return ys;
+        };
+        let mut xs = xs.to_vec();
+        xs[..N].copy_from_slice(&code);
+        let ys_mirror = fit(&xs, true);
+        use mh::Fitness as _;
+        if ys_mirror.eval() < ys.eval() {
+            ys_mirror
+        } else {
+            ys
+        }
     }
 }
@@ -0,0 +1,165 @@
+use super::*;
+use efd::na;
+use std::f64::consts::TAU;
+
+/// Precision point synthesis task of planar four-bar linkage.
+pub type FbPointSyn = PointSyn<NormFourBar, 5>;
+/// Precision point synthesis of a planar mechanism `M`.
+///
+/// The target is a small set of the precision points instead of a curve. The
+/// candidate curve is aligned to the points by a similarity transformation,
+/// then each point is matched to the nearest position on the curve.
+pub type PointSyn<M, const N: usize> = Syn<Vec<[f64; 2]>, M, N, 2>;
+
+// Number of the initial rotations of the alignment
+const STARTS: usize = 4;
+// Number of the refinement of the alignment
+const ITER: usize = 5;
+
+impl<M, const N: usize> PointSyn<M, N> {
+    /// Create a new task from the precision points.
+    ///
+    /// # Panics
+    ///
+    /// Panic if there are less than two points.
+    pub fn from_curve<C>(curve: C, mode: Mode) -> Self
+    where
+        C: efd::Curve<2>,
+    {
+        let pts = curve.as_curve().to_vec();
+        assert!(pts.len() > 1, "at least two precision points are required");
+        let mode = mode.resolve(&pts);
+        Self::new(pts, mode)
+    }
+}
+
+impl<M, const N: usize> mh::Bounded for PointSyn<M, N>
+where
+    Self: mh::ObjFunc,
+    M: mech::FromVectorized<N>,
+{
+    #[inline]
+    fn bound(&self) -> &[[f64; 2]] {
+        self.get_bound()
+    }
+}
+
+impl<M, const N: usize> mh::ObjFunc for PointSyn<M, N>
+where
+    M: SynBound<N> + mech::Normalized<2> + mech::CurveGen<2>,
+    M::De: Default + Clone + Sync + Send + 'static,
+{
+    type Ys = mh::WithProduct<f64, M::De>;
+
+    fn fitness(&self, xs: &[f64]) -> Self::Ys {
+        let closed = !self.mode.is_result_open();
+        let get_series = |fb: &M, start, end| {
+            let curve = self.pivot_curve(fb, start, end);
+            (curve.len() > 2).then_some(curve)
+        };
+        impl_fitness(
+            self.mode,
+            self.input_range,
+            self.is_symmetric(),
+            xs,
+            get_series,
+            |(c, fb)| {
+                let (err, geo) = align(&c, &self.tar, closed);
+                let fb = fb.clone().trans_denorm(&geo);
+                mh::WithProduct::new(err.max(self.unit_err(&geo)), fb)
+            },
+        )
+    }
+}
+
+// Align the curve to the points, return the sum of squared distances
+// (relative to the size of the points) and the transformation
+//
+// The transformation `[a, b, tx, ty]` maps `[x, y]` to
+// `[a * x - b * y + tx, b * x + a * y + ty]`, which is linear to the
+// parameters, so each refinement is a least squares problem.
+fn align(curve: &[[f64; 2]], pts: &[[f64; 2]], closed: bool) -> (f64, efd::GeoVar2) {
+    let center = |c: &[[f64; 2]]| {
+        let n = c.len() as f64;
+        let [x, y] = c.iter().fold([0.; 2], |[x, y], [a, b]| [x + a, y + b]);
+        [x / n, y / n]
+    };
+    let radius2 = |c: &[[f64; 2]], [cx, cy]: [f64; 2]| {
+        let r = c.iter().map(|[x, y]| (x - cx).powi(2) + (y - cy).powi(2));
+        r.sum::<f64>() / c.len() as f64
+    };
+    let tf = |[a, b, tx, ty]: [f64; 4], [x, y]: [f64; 2]| [a * x - b * y + tx, b * x + a * y + ty];
+    let seg_num = if closed { curve.len() } else { curve.len() - 1 };
+    let next = |i: usize| (i + 1) % curve.len();
+    let (pc, cc) = (center(pts), center(curve));
+    let size = radius2(pts, pc);
+    let s0 = (size / radius2(curve, cc)).sqrt();
+    let mut best = (f64::INFINITY, [1., 0., 0., 0.]);
+    for k in 0..STARTS {
+        let (sin, cos) = (k as f64 / STARTS as f64 * TAU).sin_cos();
+        let [a, b] = [s0 * cos, s0 * sin];
+        let [x, y] = tf([a, b, 0., 0.], cc);
+        let mut m = [a, b, pc[0] - x, pc[1] - y];
+        for i in 0..=ITER {
+            let tc = curve.iter().map(|c| tf(m, *c)).collect::<Vec<_>>();
+            // Nearest positions on the curve `(distance^2, segment, ratio)`
+            let near = pts
+                .iter()
+                .map(|p| {
+                    (0..seg_num)
+                        .map(|j| seg_near(*p, tc[j], tc[next(j)], j))
+                        .min_by(|a, b| a.0.total_cmp(&b.0))
+                        .unwrap()
+                })
+                .collect::<Vec<_>>();
+            let err = near.iter().map(|(d, ..)| d).sum::<f64>();
+            if err < best.0 {
+                best = (err, m);
+            }
+            if i == ITER {
+                break;
+            }
+            // Minimize the point-to-line distances, with a small portion of
+            // the point-to-point distances to avoid the singularity
+            const W: f64 = 1e-2;
+            let mut lhs = na::Matrix4::zeros();
+            let mut rhs = na::Vector4::zeros();
+            for (p, &(_, j, t)) in std::iter::zip(pts, &near) {
+                let [c0, c1] = [curve[j], curve[next(j)]];
+                let [x, y] = [c0[0] + t * (c1[0] - c0[0]), c0[1] + t * (c1[1] - c0[1])];
+                let jx = na::Vector4::new(x, -y, 1., 0.);
+                let jy = na::Vector4::new(y, x, 0., 1.);
+                let tan = na::Vector2::new(tc[next(j)][0] - tc[j][0], tc[next(j)][1] - tc[j][1]);
+                let mut rows = vec![(jx, p[0], W), (jy, p[1], W)];
+                if let Some(tan) = tan.try_normalize(f64::EPSILON) {
+                    let [nx, ny] = [-tan.y, tan.x];
+                    rows.push((jx * nx + jy * ny, p[0] * nx + p[1] * ny, 1.));
+                }
+                for (jac, v, w) in rows {
+                    lhs += w * jac * jac.transpose();
+                    rhs += w * v * jac;
+                }
+            }
+            match lhs.lu().solve(&rhs) {
+                Some(sol) => m = [sol.x, sol.y, sol.z, sol.w],
+                None => break,
+            }
+        }
+    }
+    let (err, [a, b, tx, ty]) = best;
+    let geo = efd::GeoVar2::new([tx, ty], na::UnitComplex::new(b.atan2(a)), a.hypot(b));
+    (err / size, geo)
+}
+
+// Nearest position of the point on the segment `(distance^2, segment, ratio)`
+fn seg_near(p: [f64; 2], a: [f64; 2], b: [f64; 2], j: usize) -> (f64, usize, f64) {
+    let ab = [b[0] - a[0], b[1] - a[1]];
+    let len2 = ab[0] * ab[0] + ab[1] * ab[1];
+    let t = if len2 > 0. {
+        (((p[0] - a[0]) * ab[0] + (p[1] - a[1]) * ab[1]) / len2).clamp(0., 1.)
+    } else {
+        0.
+    };
+    let d = (p[0] - a[0] - t * ab[0]).powi(2) + (p[1] - a[1] - t * ab[1]).powi(2);
+    (d, j, t)
+}